@@ -3,6 +3,8 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion, BenchmarkId};
 use offline_election::engine::ElectionEngine;
 use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::election_overrides::ElectionOverrides;
+use offline_election::session::ElectionSession;
 use offline_election::types::AlgorithmType;
 
 // Import test utilities
@@ -70,6 +72,12 @@ fn benchmark_election_execution(c: &mut Criterion) {
             algorithm: AlgorithmType::SequentialPhragmen,
             overrides: None,
             block_number: None,
+            strict_snapshot: false,
+            strict: false,
+            min_nominator_bond: None,
+            min_validator_bond: None,
+            chain_profile: None,
+            address_format: None,
         };
         
         group.bench_with_input(
@@ -101,6 +109,12 @@ fn benchmark_different_active_set_sizes(c: &mut Criterion) {
             algorithm: AlgorithmType::SequentialPhragmen,
             overrides: None,
             block_number: None,
+            strict_snapshot: false,
+            strict: false,
+            min_nominator_bond: None,
+            min_validator_bond: None,
+            chain_profile: None,
+            address_format: None,
         };
         
         group.bench_with_input(
@@ -118,6 +132,44 @@ fn benchmark_different_active_set_sizes(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(benches, benchmark_election_execution, benchmark_different_active_set_sizes);
+fn benchmark_what_if_reruns(c: &mut Criterion) {
+    let engine = ElectionEngine::new();
+    let election_data = generate_benchmark_data(1_000, 10_000);
+    let session = ElectionSession::new(election_data.clone()).unwrap();
+
+    // A typical what-if: one nominator's stake changes between runs
+    let mut overrides = ElectionOverrides::new();
+    overrides
+        .set_nominator_stake("5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty42".to_string(), 1_000_000_000_000)
+        .unwrap();
+    let config = ElectionConfiguration::new()
+        .active_set_size(100)
+        .overrides(overrides);
+
+    let mut group = c.benchmark_group("what_if_rerun");
+    group.sample_size(10);
+
+    group.bench_function("engine_1kc_10kn", |b| {
+        b.iter(|| {
+            let result = engine.execute(black_box(&config), black_box(&election_data));
+            black_box(result)
+        })
+    });
+    group.bench_function("session_1kc_10kn", |b| {
+        b.iter(|| {
+            let result = session.run(black_box(&config));
+            black_box(result)
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    benchmark_election_execution,
+    benchmark_different_active_set_sizes,
+    benchmark_what_if_reruns
+);
 criterion_main!(benches);
 
@@ -0,0 +1,877 @@
+//! Interned account indices for the solver
+//!
+//! `sp-npos-elections` is generic over the account ID type, and hashing and
+//! cloning `String` IDs dominates its run time on large snapshots. Elections
+//! hand it dense `u32` indices instead and map back to account IDs only when
+//! building the [`ElectionResult`].
+//!
+//! The engine indexes its data for every run, and an
+//! [`ElectionSession`](crate::session::ElectionSession) once for all of its
+//! runs. Either way a run applies its overrides as a [`Delta`] on top of the
+//! [`Index`] and goes through the same [`IndexedElection`] steps.
+
+use crate::diagnostics::models::{ExcludedRole, Exclusion, ExclusionReason};
+use crate::engine::Warnings;
+use crate::error::ElectionError;
+use crate::models::chain_profile::ChainProfile;
+use crate::models::election_config::ElectionConfiguration;
+use crate::models::election_data::ElectionData;
+use crate::models::election_overrides::{EdgeAction, ElectionOverrides};
+use crate::models::election_result::{
+    ElectionResult, ExecutionMetadata, SelectedValidator, StakeAccounting, StakeAllocation, UnassignedReason,
+    UnassignedStake,
+};
+use crate::models::nominator::Nominator;
+use crate::models::validator::ValidatorCandidate;
+use crate::models::warning::Warning;
+use crate::types::AlgorithmType;
+use sp_runtime::Perbill;
+use std::collections::{HashMap, HashSet};
+
+/// Solution of `sp-npos-elections` over interned indices
+pub(crate) type Solution = sp_npos_elections::ElectionResult<u32, Perbill>;
+
+/// Voter as `sp-npos-elections` takes it: ID, stake and targets
+pub(crate) type Voter = (u32, u64, Vec<u32>);
+
+/// Account IDs of election data interned as dense indices
+///
+/// Candidate `i` is the data's `i`th candidate and nominator `j` its `j`th
+/// nominator. Each nominator's targets are kept as candidate indices;
+/// targets that are not candidates are dropped.
+pub(crate) struct Index {
+    candidates: HashMap<String, u32>,
+    nominators: HashMap<String, u32>,
+    targets: Vec<Vec<u32>>,
+}
+
+impl Index {
+    pub(crate) fn new(data: &ElectionData) -> Result<Self, ElectionError> {
+        if data.candidates.len() + data.nominators.len() > u32::MAX as usize {
+            return Err(ElectionError::ValidationError {
+                message: format!(
+                    "Too many accounts to index: {} candidates and {} nominators",
+                    data.candidates.len(),
+                    data.nominators.len()
+                ),
+                field: None,
+            });
+        }
+
+        let candidates: HashMap<String, u32> = data
+            .candidates
+            .iter()
+            .enumerate()
+            .map(|(index, candidate)| (candidate.account_id.clone(), index as u32))
+            .collect();
+        let nominators = data
+            .nominators
+            .iter()
+            .enumerate()
+            .map(|(index, nominator)| (nominator.account_id.clone(), index as u32))
+            .collect();
+        let targets = data
+            .nominators
+            .iter()
+            .map(|nominator| {
+                nominator
+                    .targets
+                    .iter()
+                    .filter_map(|target| candidates.get(target).copied())
+                    .collect()
+            })
+            .collect();
+
+        Ok(Self {
+            candidates,
+            nominators,
+            targets,
+        })
+    }
+}
+
+/// Candidates and nominators of an election, looked up by index
+pub(crate) trait Accounts {
+    fn candidate(&self, index: u32) -> &ValidatorCandidate;
+
+    fn candidate_stake(&self, index: u32) -> u128;
+
+    fn nominator(&self, index: u32) -> &Nominator;
+}
+
+impl Accounts for ElectionData {
+    fn candidate(&self, index: u32) -> &ValidatorCandidate {
+        &self.candidates[index as usize]
+    }
+
+    fn candidate_stake(&self, index: u32) -> u128 {
+        self.candidates[index as usize].stake
+    }
+
+    fn nominator(&self, index: u32) -> &Nominator {
+        &self.nominators[index as usize]
+    }
+}
+
+/// Overrides of one run on top of an index
+///
+/// Candidates and nominators keep the index's positions; those added by
+/// overrides are numbered after them. Only what the overrides touch is
+/// stored, so the data is never copied.
+pub(crate) struct Delta<'a> {
+    data: &'a ElectionData,
+    index: &'a Index,
+    added_candidates: &'a [ValidatorCandidate],
+    added_nominators: &'a [Nominator],
+    added_candidate_index: HashMap<&'a str, u32>,
+    added_nominator_index: HashMap<&'a str, u32>,
+    added_targets: Vec<Vec<u32>>,
+    removed_candidates: HashSet<u32>,
+    removed_nominators: HashSet<u32>,
+    candidate_stakes: HashMap<u32, u128>,
+    nominator_stakes: HashMap<u32, u128>,
+    targets: HashMap<u32, Vec<u32>>,
+    weights: HashMap<(u32, u32), u128>,
+}
+
+impl<'a> Delta<'a> {
+    /// Apply overrides to indexed data
+    ///
+    /// Accounts are removed first, then added, then stakes and voting edges
+    /// are changed. Overrides naming an account or edge that is not in the
+    /// data are reported as warnings.
+    pub(crate) fn new(
+        data: &'a ElectionData,
+        index: &'a Index,
+        overrides: &'a ElectionOverrides,
+        warnings: &mut Warnings,
+    ) -> Result<Self, ElectionError> {
+        let unmatched = |target: &str, account_id: &str| Warning::UnmatchedOverride {
+            target: target.to_string(),
+            account_id: account_id.to_string(),
+        };
+        let mut delta = Self {
+            data,
+            index,
+            added_candidates: &overrides.added_candidates,
+            added_nominators: &overrides.added_nominators,
+            added_candidate_index: HashMap::new(),
+            added_nominator_index: HashMap::new(),
+            added_targets: Vec::new(),
+            removed_candidates: HashSet::new(),
+            removed_nominators: HashSet::new(),
+            candidate_stakes: HashMap::new(),
+            nominator_stakes: HashMap::new(),
+            targets: HashMap::new(),
+            weights: HashMap::new(),
+        };
+
+        // Remove whole accounts; nominations of removed candidates go with them
+        for account_id in &overrides.removed_nominators {
+            match index.nominators.get(account_id) {
+                Some(&nominator) if delta.removed_nominators.insert(nominator) => {}
+                _ => warnings.push(unmatched("nominator-removal", account_id))?,
+            }
+        }
+        for account_id in &overrides.removed_candidates {
+            match index.candidates.get(account_id) {
+                Some(&candidate) if delta.removed_candidates.insert(candidate) => {}
+                _ => warnings.push(unmatched("candidate-removal", account_id))?,
+            }
+        }
+
+        // Add whole accounts
+        let base_candidates = delta.base_candidates();
+        for (offset, candidate) in overrides.added_candidates.iter().enumerate() {
+            if delta.find_candidate(&candidate.account_id).is_some() {
+                return Err(ElectionError::ValidationError {
+                    message: format!("Duplicate candidate account ID: {}", candidate.account_id),
+                    field: Some("candidates".to_string()),
+                });
+            }
+            delta
+                .added_candidate_index
+                .insert(&candidate.account_id, base_candidates + offset as u32);
+        }
+        if delta.candidates().next().is_none() {
+            return Err(ElectionError::ValidationError {
+                message: "Election data must contain at least one validator candidate, but found 0. Please add at least one candidate.".to_string(),
+                field: Some("candidates".to_string()),
+            });
+        }
+        let base_nominators = delta.base_nominators();
+        for (offset, nominator) in overrides.added_nominators.iter().enumerate() {
+            if delta.find_nominator(&nominator.account_id).is_some() {
+                return Err(ElectionError::ValidationError {
+                    message: format!("Duplicate nominator account ID: {}", nominator.account_id),
+                    field: Some("nominators".to_string()),
+                });
+            }
+            delta
+                .added_nominator_index
+                .insert(&nominator.account_id, base_nominators + offset as u32);
+        }
+        if u32::try_from(delta.added_candidates.len() + delta.added_nominators.len())
+            .ok()
+            .and_then(|added| added.checked_add(base_candidates))
+            .and_then(|total| total.checked_add(base_nominators))
+            .is_none()
+        {
+            return Err(ElectionError::ValidationError {
+                message: "Too many accounts to index after overrides".to_string(),
+                field: Some("overrides".to_string()),
+            });
+        }
+        for nominator in &overrides.added_nominators {
+            let targets = nominator
+                .targets
+                .iter()
+                .map(|target| {
+                    delta.find_candidate(target).ok_or_else(|| ElectionError::ValidationError {
+                        message: format!(
+                            "Nominator '{}' votes for non-existent candidate '{}'",
+                            nominator.account_id, target
+                        ),
+                        field: Some("nominators.targets".to_string()),
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            delta.added_targets.push(targets);
+        }
+
+        // Apply stake overrides
+        for (account_id, stake) in &overrides.candidate_stakes {
+            match delta.find_candidate(account_id) {
+                Some(candidate) => {
+                    delta.candidate_stakes.insert(candidate, *stake);
+                }
+                None => warnings.push(unmatched("candidate-stake", account_id))?,
+            }
+        }
+        for (account_id, stake) in &overrides.nominator_stakes {
+            match delta.find_nominator(account_id) {
+                Some(nominator) => {
+                    delta.nominator_stakes.insert(nominator, *stake);
+                }
+                None => warnings.push(unmatched("nominator-stake", account_id))?,
+            }
+        }
+
+        // Apply voting edge modifications
+        for edge_mod in &overrides.voting_edges {
+            let edge_name = format!("{} -> {}", edge_mod.nominator_id, edge_mod.candidate_id);
+            let candidate = delta.find_candidate(&edge_mod.candidate_id);
+            let Some(nominator) = delta.find_nominator(&edge_mod.nominator_id) else {
+                warnings.push(unmatched("voting-edge", &edge_name))?;
+                continue;
+            };
+            if !delta.targets.contains_key(&nominator) {
+                let targets = delta.current_targets(nominator);
+                delta.targets.insert(nominator, targets);
+            }
+            let targets = delta.targets.get_mut(&nominator).expect("targets were just copied");
+            let has_edge = candidate.is_some_and(|c| targets.contains(&c));
+            match (edge_mod.action, candidate) {
+                (EdgeAction::Add, Some(candidate)) => {
+                    if !targets.contains(&candidate) {
+                        targets.push(candidate);
+                    }
+                    match edge_mod.weight {
+                        Some(weight) => delta.weights.insert((nominator, candidate), weight),
+                        None => delta.weights.remove(&(nominator, candidate)),
+                    };
+                }
+                (EdgeAction::Remove, Some(candidate)) if has_edge => {
+                    targets.retain(|&t| t != candidate);
+                    delta.weights.remove(&(nominator, candidate));
+                }
+                (EdgeAction::Modify, Some(candidate)) if has_edge => {
+                    match edge_mod.weight {
+                        Some(weight) => delta.weights.insert((nominator, candidate), weight),
+                        None => delta.weights.remove(&(nominator, candidate)),
+                    };
+                }
+                _ => warnings.push(unmatched("voting-edge", &edge_name))?,
+            }
+        }
+
+        // Weighted edges can't commit more than the nominator has
+        let mut weighted: Vec<u32> = delta.weights.keys().map(|&(nominator, _)| nominator).collect();
+        weighted.sort_unstable();
+        weighted.dedup();
+        for nominator in weighted {
+            let committed: u128 = delta
+                .current_targets(nominator)
+                .iter()
+                .filter_map(|&target| delta.weights.get(&(nominator, target)))
+                .sum();
+            let stake = delta.nominator_stake(nominator);
+            if committed > stake {
+                return Err(ElectionError::ValidationError {
+                    message: format!(
+                        "Edge weights of nominator {} add up to {}, more than its stake of {}",
+                        delta.nominator_id(nominator),
+                        committed,
+                        stake
+                    ),
+                    field: Some("overrides.voting_edges".to_string()),
+                });
+            }
+        }
+
+        Ok(delta)
+    }
+
+    fn base_candidates(&self) -> u32 {
+        self.data.candidates.len() as u32
+    }
+
+    fn base_nominators(&self) -> u32 {
+        self.data.nominators.len() as u32
+    }
+
+    /// Candidates of this run, in data order followed by added ones
+    fn candidates(&self) -> impl Iterator<Item = u32> + '_ {
+        let base = self.base_candidates();
+        (0..base)
+            .filter(|index| !self.removed_candidates.contains(index))
+            .chain(base..base + self.added_candidates.len() as u32)
+    }
+
+    /// Nominators of this run, in data order followed by added ones
+    fn nominators(&self) -> impl Iterator<Item = u32> + '_ {
+        let base = self.base_nominators();
+        (0..base)
+            .filter(|index| !self.removed_nominators.contains(index))
+            .chain(base..base + self.added_targets.len() as u32)
+    }
+
+    fn find_candidate(&self, account_id: &str) -> Option<u32> {
+        if let Some(&index) = self.added_candidate_index.get(account_id) {
+            return Some(index);
+        }
+        self.index
+            .candidates
+            .get(account_id)
+            .copied()
+            .filter(|index| !self.removed_candidates.contains(index))
+    }
+
+    fn find_nominator(&self, account_id: &str) -> Option<u32> {
+        if let Some(&index) = self.added_nominator_index.get(account_id) {
+            return Some(index);
+        }
+        self.index
+            .nominators
+            .get(account_id)
+            .copied()
+            .filter(|index| !self.removed_nominators.contains(index))
+    }
+
+    fn candidate_id(&self, index: u32) -> &str {
+        &self.candidate(index).account_id
+    }
+
+    fn nominator_id(&self, index: u32) -> &str {
+        &self.nominator(index).account_id
+    }
+
+    fn nominator_stake(&self, index: u32) -> u128 {
+        self.nominator_stakes
+            .get(&index)
+            .copied()
+            .unwrap_or_else(|| self.nominator(index).stake)
+    }
+
+    /// Targets of a nominator after the overrides, in nomination order
+    fn current_targets(&self, index: u32) -> Vec<u32> {
+        if let Some(targets) = self.targets.get(&index) {
+            return targets.clone();
+        }
+        match index.checked_sub(self.base_nominators()) {
+            Some(offset) => self.added_targets[offset as usize].clone(),
+            None => self.index.targets[index as usize]
+                .iter()
+                .copied()
+                .filter(|target| !self.removed_candidates.contains(target))
+                .collect(),
+        }
+    }
+
+    /// Whether the data, before overrides, has the nominator voting for the candidate
+    fn is_original_edge(&self, nominator: u32, candidate: u32) -> bool {
+        let nominator = self.index.nominators.get(self.nominator_id(nominator));
+        let candidate = self.index.candidates.get(self.candidate_id(candidate));
+        match (nominator, candidate) {
+            (Some(&nominator), Some(candidate)) => self.index.targets[nominator as usize].contains(candidate),
+            _ => false,
+        }
+    }
+
+    /// Stake committed to each weighted `(nominator, candidate)` edge
+    pub(crate) fn weights(&self) -> &HashMap<(u32, u32), u128> {
+        &self.weights
+    }
+
+    /// Leave out voters, targets and nominations that are not eligible
+    ///
+    /// Nominators below the minimum nominator bond and candidates below the
+    /// minimum validator bond are left out, along with nominations of
+    /// left-out candidates; nominators left without targets are left out
+    /// too. Blocked validators only reject new nominations, so only
+    /// nominations added by overrides are dropped for them; nominations
+    /// already in the data stay, as they do on-chain.
+    pub(crate) fn eligible(
+        &self,
+        config: &ElectionConfiguration,
+        min_validator_bond: Option<u128>,
+    ) -> Result<(IndexedElection<'_, Self>, Vec<Exclusion>), ElectionError> {
+        let mut exclusions = Vec::new();
+        let mut candidates: Vec<u32> = self.candidates().collect();
+        if let Some(min_bond) = min_validator_bond {
+            candidates.retain(|&candidate| {
+                let stake = self.candidate_stake(candidate);
+                if stake >= min_bond {
+                    return true;
+                }
+                exclusions.push(Exclusion {
+                    account_id: self.candidate_id(candidate).to_string(),
+                    role: ExcludedRole::Target,
+                    reason: ExclusionReason::BelowMinValidatorBond { stake, min_bond },
+                });
+                false
+            });
+            if candidates.is_empty() {
+                return Err(ElectionError::ValidationError {
+                    message: format!("No candidate meets the minimum validator bond of {}", min_bond),
+                    field: Some("min_validator_bond".to_string()),
+                });
+            }
+        }
+        let first_voter = self.base_candidates() + self.added_candidates.len() as u32;
+        let mut eligible = vec![false; first_voter as usize];
+        for &candidate in &candidates {
+            eligible[candidate as usize] = true;
+        }
+
+        let mut target_exclusions = Vec::new();
+        let mut voters = Vec::new();
+        let mut ineligible = Vec::new();
+        for nominator in self.nominators() {
+            let stake = self.nominator_stake(nominator);
+            if let Some(min_bond) = config.min_nominator_bond.filter(|&min_bond| stake < min_bond) {
+                exclusions.push(Exclusion {
+                    account_id: self.nominator_id(nominator).to_string(),
+                    role: ExcludedRole::Voter,
+                    reason: ExclusionReason::BelowMinNominatorBond { stake, min_bond },
+                });
+                ineligible.push((nominator, stake, UnassignedReason::BelowMinNominatorBond));
+                continue;
+            }
+            let mut targets = self.current_targets(nominator);
+            let had_targets = !targets.is_empty();
+            targets.retain(|&target| {
+                if !eligible[target as usize] {
+                    return false;
+                }
+                if self.candidate(target).is_blocked() && !self.is_original_edge(nominator, target) {
+                    target_exclusions.push(Exclusion {
+                        account_id: self.nominator_id(nominator).to_string(),
+                        role: ExcludedRole::Voter,
+                        reason: ExclusionReason::BlockedTarget {
+                            validator_id: self.candidate_id(target).to_string(),
+                        },
+                    });
+                    return false;
+                }
+                true
+            });
+            // Voters left without targets would only count as unassigned stake
+            if had_targets && targets.is_empty() {
+                target_exclusions.push(Exclusion {
+                    account_id: self.nominator_id(nominator).to_string(),
+                    role: ExcludedRole::Voter,
+                    reason: ExclusionReason::NoEligibleTargets,
+                });
+                ineligible.push((nominator, stake, UnassignedReason::NoEligibleTargets));
+                continue;
+            }
+            voters.push(IndexedVoter { nominator, stake, targets });
+        }
+        exclusions.extend(target_exclusions);
+
+        let election = IndexedElection {
+            accounts: self,
+            first_voter,
+            candidates,
+            voters,
+            ineligible,
+            split_stakes: HashMap::new(),
+        };
+        Ok((election, exclusions))
+    }
+}
+
+impl Accounts for Delta<'_> {
+    fn candidate(&self, index: u32) -> &ValidatorCandidate {
+        match index.checked_sub(self.base_candidates()) {
+            Some(offset) => &self.added_candidates[offset as usize],
+            None => &self.data.candidates[index as usize],
+        }
+    }
+
+    fn candidate_stake(&self, index: u32) -> u128 {
+        self.candidate_stakes
+            .get(&index)
+            .copied()
+            .unwrap_or_else(|| self.candidate(index).stake)
+    }
+
+    fn nominator(&self, index: u32) -> &Nominator {
+        match index.checked_sub(self.base_nominators()) {
+            Some(offset) => &self.added_nominators[offset as usize],
+            None => &self.data.nominators[index as usize],
+        }
+    }
+}
+
+/// Voter of one run: a nominator, or part of one split off for a weighted edge
+struct IndexedVoter {
+    nominator: u32,
+    stake: u128,
+    targets: Vec<u32>,
+}
+
+/// Candidates and voters of one run, by index
+///
+/// Voters are numbered after every candidate, so voters and candidates
+/// never share an index.
+pub(crate) struct IndexedElection<'a, A: ?Sized> {
+    accounts: &'a A,
+    /// Lowest index a voter can have
+    first_voter: u32,
+    candidates: Vec<u32>,
+    voters: Vec<IndexedVoter>,
+    /// Nominators excluded from voting, with their stake and the reason
+    ineligible: Vec<(u32, u128, UnassignedReason)>,
+    /// Full stake of each nominator split up by weighted edges
+    split_stakes: HashMap<u32, u128>,
+}
+
+impl<'a> IndexedElection<'a, ElectionData> {
+    /// Intern the accounts of `data` as they are
+    ///
+    /// Targets that are not candidates are dropped, and nominators left
+    /// without targets don't vote.
+    pub(crate) fn new(data: &'a ElectionData) -> Result<Self, ElectionError> {
+        if data.candidates.is_empty() {
+            return Err(ElectionError::ValidationError {
+                message: "Cannot run election with zero candidates".to_string(),
+                field: None,
+            });
+        }
+        let index = Index::new(data)?;
+        let voters = data
+            .nominators
+            .iter()
+            .zip(index.targets)
+            .enumerate()
+            .map(|(position, (nominator, targets))| IndexedVoter {
+                nominator: position as u32,
+                stake: nominator.stake,
+                targets,
+            })
+            .collect();
+
+        Ok(Self {
+            accounts: data,
+            first_voter: data.candidates.len() as u32,
+            candidates: (0..data.candidates.len() as u32).collect(),
+            voters,
+            ineligible: Vec::new(),
+            split_stakes: HashMap::new(),
+        })
+    }
+}
+
+impl<A: Accounts + ?Sized> IndexedElection<'_, A> {
+    /// Number of candidates the run can elect
+    pub(crate) fn candidate_count(&self) -> usize {
+        self.candidates.len()
+    }
+
+    /// Apply the snapshot limits of a chain
+    ///
+    /// Nominations beyond `MaxNominations` are dropped, and when there are
+    /// more voters than `MaxElectingVoters`, only the highest-staked ones are
+    /// kept, like the chain's stake-sorted voter list does.
+    pub(crate) fn apply_chain_limits(
+        &mut self,
+        profile: &ChainProfile,
+        warnings: &mut Warnings,
+    ) -> Result<(), ElectionError> {
+        let max_nominations = profile.max_nominations as usize;
+        let mut truncated = 0;
+        for voter in &mut self.voters {
+            if voter.targets.len() > max_nominations {
+                voter.targets.truncate(max_nominations);
+                truncated += 1;
+            }
+        }
+        if truncated > 0 {
+            warnings.push(Warning::TargetsDropped {
+                nominators: truncated,
+                max_nominations: profile.max_nominations,
+            })?;
+        }
+
+        if let Some(max_voters) = profile.max_electing_voters {
+            let max_voters = max_voters as usize;
+            if self.voters.len() > max_voters {
+                // Keep the original order, which algorithms may depend on
+                let mut by_stake: Vec<usize> = (0..self.voters.len()).collect();
+                by_stake.sort_by(|&a, &b| self.voters[b].stake.cmp(&self.voters[a].stake));
+                let mut keep = vec![false; self.voters.len()];
+                for &index in &by_stake[..max_voters] {
+                    keep[index] = true;
+                }
+                let mut keep = keep.into_iter();
+                self.voters.retain(|_| keep.next().unwrap_or(false));
+                warnings.push(Warning::VotersDropped {
+                    dropped: by_stake.len() - max_voters,
+                    max_voters: max_voters as u32,
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Give each weighted edge a voter of its own, right after its nominator
+    ///
+    /// Phragmén spreads a voter's whole stake over its targets as it sees
+    /// fit, so an edge with a fixed weight becomes a separate voter with that
+    /// stake and that single target. The nominator keeps the remaining stake
+    /// and its unweighted targets.
+    pub(crate) fn split_weighted_edges(&mut self, weights: &HashMap<(u32, u32), u128>) {
+        if weights.is_empty() {
+            return;
+        }
+        let mut split = Vec::with_capacity(self.voters.len());
+        for mut voter in std::mem::take(&mut self.voters) {
+            let nominator = voter.nominator;
+            let mut edge_voters = Vec::new();
+            voter.targets.retain(|&target| match weights.get(&(nominator, target)) {
+                Some(&weight) => {
+                    edge_voters.push(IndexedVoter {
+                        nominator,
+                        stake: weight,
+                        targets: vec![target],
+                    });
+                    false
+                }
+                None => true,
+            });
+            if !edge_voters.is_empty() {
+                self.split_stakes.insert(nominator, voter.stake);
+                voter.stake -= edge_voters.iter().map(|v| v.stake).sum::<u128>();
+            }
+            split.push(voter);
+            split.extend(edge_voters);
+        }
+        self.voters = split;
+    }
+
+    /// Only let the solver elect the given candidates
+    ///
+    /// Voters keep their other targets, so their stake still counts as
+    /// having had candidates to back.
+    pub(crate) fn restrict_candidates(&mut self, account_ids: &[String]) {
+        let allowed: HashSet<&str> = account_ids.iter().map(String::as_str).collect();
+        let accounts = self.accounts;
+        self.candidates
+            .retain(|&candidate| allowed.contains(accounts.candidate(candidate).account_id.as_str()));
+    }
+
+    /// Write the run's candidates and voters back out as election data
+    ///
+    /// Used for diagnostics, which work on account IDs.
+    pub(crate) fn to_data(&self) -> ElectionData {
+        let candidates = self
+            .candidates
+            .iter()
+            .map(|&index| {
+                let mut candidate = self.accounts.candidate(index).clone();
+                candidate.stake = self.accounts.candidate_stake(index);
+                candidate
+            })
+            .collect();
+        let nominators = self
+            .voters
+            .iter()
+            .map(|voter| {
+                let mut nominator = self.accounts.nominator(voter.nominator).clone();
+                nominator.stake = voter.stake;
+                nominator.targets = voter
+                    .targets
+                    .iter()
+                    .map(|&target| self.accounts.candidate(target).account_id.clone())
+                    .collect();
+                nominator
+            })
+            .collect();
+        ElectionData {
+            candidates,
+            nominators,
+            metadata: None,
+        }
+    }
+
+    /// Report voters whose stake the solver can't weigh in full
+    ///
+    /// `sp-npos-elections` takes `u64` stakes, so larger stakes are capped at
+    /// `u64::MAX` when winners are chosen. Allocations are still taken from
+    /// the full stake, but the winners may differ from an uncapped election.
+    pub(crate) fn check_solver_stakes(&self, warnings: &mut Warnings) -> Result<(), ElectionError> {
+        let voters = self
+            .voters
+            .iter()
+            .filter(|voter| !voter.targets.is_empty() && voter.stake > u64::MAX as u128)
+            .count();
+        if voters > 0 {
+            warnings.push(Warning::StakeTruncated { voters })?;
+        }
+        Ok(())
+    }
+
+    /// Run `solve` on the interned data and build the result
+    ///
+    /// `solve` gets the number of validators to elect, the candidates in
+    /// data order and the voters in data order.
+    pub(crate) fn elect(
+        self,
+        config: &ElectionConfiguration,
+        algorithm: AlgorithmType,
+        solve: impl FnOnce(usize, Vec<u32>, Vec<Voter>) -> Result<Solution, ElectionError>,
+    ) -> Result<ElectionResult, ElectionError> {
+        let first_voter = self.first_voter;
+        if u32::try_from(self.voters.len())
+            .ok()
+            .and_then(|count| count.checked_add(first_voter))
+            .is_none()
+        {
+            return Err(ElectionError::ValidationError {
+                message: format!("Too many voters to index: {}", self.voters.len()),
+                field: Some("nominators".to_string()),
+            });
+        }
+        let sp_voters = self
+            .voters
+            .iter()
+            .enumerate()
+            .filter(|(_, voter)| !voter.targets.is_empty())
+            .map(|(position, voter)| {
+                // Capped stakes are reported by `check_solver_stakes`
+                let stake = voter.stake.min(u64::MAX as u128) as u64;
+                (first_voter + position as u32, stake, voter.targets.clone())
+            })
+            .collect();
+        let solution = solve(config.active_set_size as usize, self.candidates, sp_voters)?;
+
+        // One pass over the assignments gives allocations, backing and backer counts
+        let accounts = self.accounts;
+        let mut backing = vec![0u128; first_voter as usize];
+        let mut backers = vec![0u32; first_voter as usize];
+        let mut assigned: Vec<Option<u128>> = vec![None; self.voters.len()];
+        let mut stake_distribution = Vec::new();
+        let perbill_denominator = Perbill::one().deconstruct() as f64;
+        for assignment in &solution.assignments {
+            let position = (assignment.who - first_voter) as usize;
+            let voter = &self.voters[position];
+            let full_stake = self.split_stakes.get(&voter.nominator);
+            for &(validator, portion) in &assignment.distribution {
+                // Round down so allocations never exceed the voter's stake
+                let amount = portion.mul_floor(voter.stake);
+                // Parts of a split nominator are restated against its full stake
+                let portion = match full_stake {
+                    Some(&stake) => Perbill::from_rational(amount, stake.max(1)),
+                    None => portion,
+                };
+                backing[validator as usize] += amount;
+                backers[validator as usize] += 1;
+                *assigned[position].get_or_insert(0) += amount;
+                stake_distribution.push(StakeAllocation {
+                    nominator_id: accounts.nominator(voter.nominator).account_id.clone(),
+                    validator_id: accounts.candidate(validator).account_id.clone(),
+                    amount,
+                    proportion: portion.deconstruct() as f64 / perbill_denominator,
+                    perbill: portion.deconstruct(),
+                });
+            }
+        }
+
+        let selected_validators = solution
+            .winners
+            .iter()
+            .enumerate()
+            .map(|(rank, &(winner, _))| SelectedValidator {
+                account_id: accounts.candidate(winner).account_id.clone(),
+                total_backing_stake: backing[winner as usize],
+                nominator_count: backers[winner as usize],
+                rank: Some(rank as u32 + 1),
+            })
+            .collect();
+
+        let mut accounting = StakeAccounting {
+            assigned_stake: backing.iter().sum(),
+            ..Default::default()
+        };
+        for (voter, assigned) in self.voters.iter().zip(assigned) {
+            match assigned {
+                Some(assigned) => accounting.rounding_residue += voter.stake.saturating_sub(assigned),
+                None if voter.stake > 0 => {
+                    let reason = if voter.targets.is_empty() {
+                        UnassignedReason::NoCandidateTargets
+                    } else {
+                        UnassignedReason::NoElectedTargets
+                    };
+                    accounting.unassigned_stake += voter.stake;
+                    accounting.unassigned.push(UnassignedStake {
+                        nominator_id: accounts.nominator(voter.nominator).account_id.clone(),
+                        amount: voter.stake,
+                        reason,
+                    });
+                }
+                None => {}
+            }
+        }
+        // Excluded nominators still count towards the total
+        let total_stake = self.voters.iter().map(|voter| voter.stake).sum::<u128>()
+            + self.ineligible.iter().map(|&(_, stake, _)| stake).sum::<u128>();
+        for (nominator, stake, reason) in self.ineligible {
+            if stake > 0 {
+                accounting.unassigned_stake += stake;
+                accounting.unassigned.push(UnassignedStake {
+                    nominator_id: accounts.nominator(nominator).account_id.clone(),
+                    amount: stake,
+                    reason,
+                });
+            }
+        }
+
+        Ok(ElectionResult {
+            selected_validators,
+            stake_distribution,
+            total_stake,
+            accounting,
+            algorithm_used: algorithm,
+            execution_metadata: ExecutionMetadata {
+                block_number: config.block_number,
+                execution_timestamp: Some(chrono::Utc::now().to_rfc3339()),
+                data_source: None,
+            },
+            diagnostics: None,
+            pool_backing: None,
+            warnings: Vec::new(),
+        })
+    }
+}
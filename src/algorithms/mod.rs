@@ -1,6 +1,7 @@
 //! Election algorithm implementations
 
 pub mod trait_def;
+pub(crate) mod indexed;
 pub mod sequential_phragmen;
 pub mod parallel_phragmen;
 pub mod multi_phase;
@@ -1,6 +1,6 @@
 //! REST API request handlers
 
-use crate::api::models::{DataSource, ElectionRequest, ElectionResponse, ErrorResponse};
+use crate::api::models::{DataSource, ElectionRequest, ElectionResponse, ErrorResponse, ValidationResponse};
 use crate::diagnostics::explainer::DiagnosticsGenerator;
 use crate::engine::ElectionEngine;
 use crate::error::ElectionError;
@@ -8,6 +8,7 @@ use crate::input::rpc::RpcLoader;
 use crate::input::synthetic::SyntheticDataBuilder;
 use crate::models::election_config::ElectionConfiguration;
 use crate::models::election_data::ElectionData;
+use crate::schema::{validate_json, SchemaKind};
 use crate::types::AlgorithmType;
 use axum::extract::Path;
 use axum::http::StatusCode;
@@ -64,17 +65,32 @@ pub async fn run_election(
 
     // Load election data based on data source
     let election_data = load_election_data(&request.data_source).await
-        .map_err(|e| ApiError::Election(e))?;
+        .map_err(ApiError::Election)?;
 
     // Create election configuration
     let mut config = ElectionConfiguration::new()
         .algorithm(algorithm)
-        .active_set_size(request.active_set_size);
+        .active_set_size(request.active_set_size)
+        .strict_snapshot(request.strict_snapshot)
+        .strict(request.strict);
 
     if let Some(block) = request.block_number {
         config = config.block_number(block);
     }
 
+    if let Some(bond) = request.min_nominator_bond {
+        config = config.min_nominator_bond(bond);
+    }
+    if let Some(bond) = request.min_validator_bond {
+        config = config.min_validator_bond(bond);
+    }
+    if let Some(ref profile) = request.chain_profile {
+        config = config.chain_profile(profile.clone());
+    }
+    if let Some(format) = request.address_format {
+        config = config.address_format(format);
+    }
+
     // Apply overrides if present
     if let Some(ref overrides) = request.overrides {
         config = config.overrides(overrides.clone());
@@ -86,7 +102,7 @@ pub async fn run_election(
     // Execute election
     let engine = ElectionEngine::new();
     let result = engine.execute(&config, &election_data)
-        .map_err(|e| ApiError::Election(e))?;
+        .map_err(ApiError::Election)?;
 
     // Calculate execution time
     let execution_time_ms = start_time.elapsed().as_millis() as u64;
@@ -141,6 +157,25 @@ pub async fn get_election_diagnostics(
     Ok(Json(diagnostics_json))
 }
 
+/// Get the JSON Schema of an input or output format
+pub async fn get_schema(Path(kind): Path<String>) -> Result<Json<serde_json::Value>, ApiError> {
+    let kind = kind.parse::<SchemaKind>().map_err(ApiError::NotFound)?;
+    Ok(Json(kind.schema()))
+}
+
+/// Validate a JSON document, reporting every problem in it
+///
+/// The body is taken as text so that syntax errors are reported like any
+/// other problem.
+pub async fn validate_document(Path(kind): Path<String>, body: String) -> Result<Json<ValidationResponse>, ApiError> {
+    let kind = kind.parse::<SchemaKind>().map_err(ApiError::NotFound)?;
+    let issues = validate_json(kind, &body);
+    Ok(Json(ValidationResponse {
+        valid: issues.is_empty(),
+        issues,
+    }))
+}
+
 /// Load election data from the specified data source
 async fn load_election_data(data_source: &DataSource) -> Result<ElectionData, ElectionError> {
     match data_source {
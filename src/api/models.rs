@@ -1,13 +1,17 @@
 //! REST API request/response models
 
+use crate::models::account_id::AddressFormat;
+use crate::models::chain_profile::ChainProfile;
 use crate::models::election_data::ElectionData;
 use crate::models::election_overrides::ElectionOverrides;
 use crate::models::election_result::ElectionResult;
+use crate::schema::ValidationIssue;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// Election request model
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ElectionRequest {
     /// Election algorithm to use
     pub algorithm: String,
@@ -21,10 +25,28 @@ pub struct ElectionRequest {
     /// Optional block number for RPC snapshot
     #[serde(skip_serializing_if = "Option::is_none")]
     pub block_number: Option<u64>,
+    /// Refuse to run if the RPC snapshot is incomplete
+    #[serde(default)]
+    pub strict_snapshot: bool,
+    /// Fail instead of returning warnings when the election has to be adjusted
+    #[serde(default)]
+    pub strict: bool,
+    /// Nominators with less stake are left out of the election
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_nominator_bond: Option<u128>,
+    /// Candidates with less stake are left out of the election
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_validator_bond: Option<u128>,
+    /// Network parameters (default: none; no network limits apply)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chain_profile: Option<ChainProfile>,
+    /// How account IDs are written in the result (default: the data's format)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address_format: Option<AddressFormat>,
 }
 
 /// Data source for election data
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 #[serde(tag = "type", rename_all = "lowercase")]
 pub enum DataSource {
     /// Fetch data from RPC endpoint
@@ -49,8 +71,57 @@ pub enum DataSource {
     },
 }
 
+impl<'de> Deserialize<'de> for DataSource {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        // A derived internally tagged enum buffers its content, which cannot
+        // hold the u128 stakes of JSON election data. Read the tag from the
+        // raw JSON and deserialize the variant from it directly instead.
+        #[derive(Deserialize)]
+        struct Tag {
+            #[serde(rename = "type")]
+            kind: String,
+        }
+        #[derive(Deserialize)]
+        struct Rpc {
+            url: String,
+            #[serde(default)]
+            block_number: Option<u64>,
+        }
+        #[derive(Deserialize)]
+        struct Json {
+            data: ElectionData,
+        }
+        #[derive(Deserialize)]
+        struct Synthetic {
+            candidates: Vec<CandidateInput>,
+            nominators: Vec<NominatorInput>,
+        }
+
+        let raw = Box::<serde_json::value::RawValue>::deserialize(deserializer)?;
+        let raw = raw.get();
+        let tag: Tag = serde_json::from_str(raw).map_err(D::Error::custom)?;
+        match tag.kind.as_str() {
+            "rpc" => {
+                let Rpc { url, block_number } = serde_json::from_str(raw).map_err(D::Error::custom)?;
+                Ok(DataSource::Rpc { url, block_number })
+            }
+            "json" => {
+                let Json { data } = serde_json::from_str(raw).map_err(D::Error::custom)?;
+                Ok(DataSource::Json { data })
+            }
+            "synthetic" => {
+                let Synthetic { candidates, nominators } = serde_json::from_str(raw).map_err(D::Error::custom)?;
+                Ok(DataSource::Synthetic { candidates, nominators })
+            }
+            other => Err(D::Error::unknown_variant(other, &["rpc", "json", "synthetic"])),
+        }
+    }
+}
+
 /// Candidate input for synthetic data
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CandidateInput {
     /// Account ID
     pub account_id: String,
@@ -59,7 +130,7 @@ pub struct CandidateInput {
 }
 
 /// Nominator input for synthetic data
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct NominatorInput {
     /// Account ID
     pub account_id: String,
@@ -81,6 +152,15 @@ pub struct ElectionResponse {
     pub execution_time_ms: Option<u64>,
 }
 
+/// Response of `POST /validate/:kind`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidationResponse {
+    /// Whether the document has no problems
+    pub valid: bool,
+    /// Every problem found, with JSON pointers and positions
+    pub issues: Vec<ValidationIssue>,
+}
+
 /// Error response model
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ErrorResponse {
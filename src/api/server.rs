@@ -29,6 +29,8 @@ impl ApiServer {
             .route("/elections/run", post(crate::api::handlers::run_election))
             .route("/elections/:election_id/results", get(crate::api::handlers::get_election_results))
             .route("/elections/:election_id/diagnostics", get(crate::api::handlers::get_election_diagnostics))
+            .route("/schemas/:kind", get(crate::api::handlers::get_schema))
+            .route("/validate/:kind", post(crate::api::handlers::validate_document))
             .route("/health", get(health_check))
             .with_state(state);
 
@@ -45,6 +47,8 @@ impl ApiServer {
         eprintln!("   POST   /elections/run");
         eprintln!("   GET    /elections/:id/results");
         eprintln!("   GET    /elections/:id/diagnostics");
+        eprintln!("   GET    /schemas/:kind");
+        eprintln!("   POST   /validate/:kind");
         eprintln!("   GET    /health");
 
         axum::serve(listener, app).await
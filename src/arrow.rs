@@ -0,0 +1,383 @@
+//! Arrow record batches and Parquet files for dataframe analysis
+//!
+//! Available with the `arrow` cargo feature. Election data is exported as
+//! three tables and election results as two:
+//!
+//! | Table | Columns |
+//! |-------|---------|
+//! | `candidates` | `account_id`, `stake`, `commission_rate`, `on_chain_status`, `blocked` |
+//! | `nominators` | `account_id`, `stake`, `target_count`, `pool_id` |
+//! | `edges` | `nominator_id`, `validator_id`, `position` |
+//! | `winners` | `rank`, `account_id`, `total_backing_stake`, `nominator_count` |
+//! | `allocations` | `nominator_id`, `validator_id`, `amount`, `proportion`, `perbill` |
+//!
+//! Every table ends with the [`Provenance`] columns `block_number`, `era`,
+//! `chain` and `algorithm`, so tables of many elections can be concatenated
+//! and told apart. Amounts are in planck, as `Decimal128(38, 0)`.
+
+use crate::error::ElectionError;
+use crate::models::election_data::ElectionData;
+use crate::models::election_result::ElectionResult;
+use arrow_array::{
+    ArrayRef, BooleanArray, Decimal128Array, Float64Array, RecordBatch, StringArray, UInt32Array, UInt64Array,
+    UInt8Array,
+};
+use arrow_schema::{DataType, Field, Schema, SchemaRef};
+use parquet::arrow::ArrowWriter;
+use parquet::basic::{Compression, ZstdLevel};
+use parquet::file::properties::WriterProperties;
+use std::io::Write;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Precision of amount columns; the largest that fits `Decimal128`
+const AMOUNT_PRECISION: u8 = 38;
+
+/// Where an exported table comes from
+///
+/// Written as the trailing columns of every table. Fields that are unknown
+/// are written as nulls.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Provenance {
+    /// Block the data was taken at
+    pub block_number: Option<u64>,
+    /// Era the data belongs to
+    pub era: Option<u32>,
+    /// Chain name
+    pub chain: Option<String>,
+    /// Algorithm the result was computed with (unset for election data)
+    pub algorithm: Option<String>,
+}
+
+impl Provenance {
+    /// Provenance of election data, from its metadata
+    pub fn of_data(data: &ElectionData) -> Self {
+        let metadata = data.metadata.as_ref();
+        Self {
+            block_number: metadata.and_then(|m| m.block_number),
+            era: metadata.and_then(|m| m.era),
+            chain: metadata.and_then(|m| m.chain.clone()),
+            algorithm: None,
+        }
+    }
+
+    /// Provenance of a result computed from `data`
+    ///
+    /// The block number of the result takes precedence over that of the data.
+    pub fn of_result(result: &ElectionResult, data: &ElectionData) -> Self {
+        let data = Self::of_data(data);
+        Self {
+            block_number: result.execution_metadata.block_number.or(data.block_number),
+            algorithm: Some(result.algorithm_used.to_string()),
+            ..data
+        }
+    }
+
+    fn fields() -> [Field; 4] {
+        [
+            Field::new("block_number", DataType::UInt64, true),
+            Field::new("era", DataType::UInt32, true),
+            Field::new("chain", DataType::Utf8, true),
+            Field::new("algorithm", DataType::Utf8, true),
+        ]
+    }
+
+    fn columns(&self, rows: usize) -> [ArrayRef; 4] {
+        [
+            Arc::new(UInt64Array::from(vec![self.block_number; rows])),
+            Arc::new(UInt32Array::from(vec![self.era; rows])),
+            Arc::new(StringArray::from(vec![self.chain.as_deref(); rows])),
+            Arc::new(StringArray::from(vec![self.algorithm.as_deref(); rows])),
+        ]
+    }
+}
+
+/// Election data as Arrow record batches
+pub struct DataBatches {
+    /// One row per candidate
+    pub candidates: RecordBatch,
+    /// One row per nominator
+    pub nominators: RecordBatch,
+    /// One row per nomination, in the order of each nominator's targets
+    pub edges: RecordBatch,
+}
+
+impl DataBatches {
+    /// Convert `data`, tagging every row with `provenance`
+    pub fn new(data: &ElectionData, provenance: &Provenance) -> Result<Self, ElectionError> {
+        let candidates = &data.candidates;
+        let candidates = batch(
+            candidates_schema(),
+            vec![
+                strings(candidates.iter().map(|c| c.account_id.as_str())),
+                amounts(candidates.iter().map(|c| c.stake))?,
+                Arc::new(
+                    candidates
+                        .iter()
+                        .map(|c| c.metadata.as_ref().and_then(|m| m.commission_rate))
+                        .collect::<UInt8Array>(),
+                ),
+                Arc::new(
+                    candidates
+                        .iter()
+                        .map(|c| c.metadata.as_ref().and_then(|m| m.on_chain_status.as_deref()))
+                        .collect::<StringArray>(),
+                ),
+                Arc::new(
+                    candidates
+                        .iter()
+                        .map(|c| c.metadata.as_ref().map(|m| m.blocked))
+                        .collect::<BooleanArray>(),
+                ),
+            ],
+            provenance,
+        )?;
+
+        let nominators = &data.nominators;
+        let nominators = batch(
+            nominators_schema(),
+            vec![
+                strings(nominators.iter().map(|n| n.account_id.as_str())),
+                amounts(nominators.iter().map(|n| n.stake))?,
+                Arc::new(nominators.iter().map(|n| n.targets.len() as u32).collect::<UInt32Array>()),
+                Arc::new(
+                    nominators
+                        .iter()
+                        .map(|n| n.metadata.as_ref().and_then(|m| m.pool.as_ref()).map(|p| p.pool_id))
+                        .collect::<UInt32Array>(),
+                ),
+            ],
+            provenance,
+        )?;
+
+        let edges = || {
+            data.nominators
+                .iter()
+                .flat_map(|n| n.targets.iter().enumerate().map(move |(position, target)| (n, position, target)))
+        };
+        let edges = batch(
+            edges_schema(),
+            vec![
+                strings(edges().map(|(n, _, _)| n.account_id.as_str())),
+                strings(edges().map(|(_, _, target)| target.as_str())),
+                Arc::new(edges().map(|(_, position, _)| position as u32).collect::<UInt32Array>()),
+            ],
+            provenance,
+        )?;
+
+        Ok(Self {
+            candidates,
+            nominators,
+            edges,
+        })
+    }
+}
+
+/// Election result as Arrow record batches
+pub struct ResultBatches {
+    /// One row per selected validator
+    pub winners: RecordBatch,
+    /// One row per stake allocation
+    pub allocations: RecordBatch,
+}
+
+impl ResultBatches {
+    /// Convert `result`, tagging every row with `provenance`
+    pub fn new(result: &ElectionResult, provenance: &Provenance) -> Result<Self, ElectionError> {
+        let winners = &result.selected_validators;
+        let winners = batch(
+            winners_schema(),
+            vec![
+                Arc::new(winners.iter().map(|v| v.rank).collect::<UInt32Array>()),
+                strings(winners.iter().map(|v| v.account_id.as_str())),
+                amounts(winners.iter().map(|v| v.total_backing_stake))?,
+                Arc::new(winners.iter().map(|v| v.nominator_count).collect::<UInt32Array>()),
+            ],
+            provenance,
+        )?;
+
+        let allocations = &result.stake_distribution;
+        let allocations = batch(
+            allocations_schema(),
+            vec![
+                strings(allocations.iter().map(|a| a.nominator_id.as_str())),
+                strings(allocations.iter().map(|a| a.validator_id.as_str())),
+                amounts(allocations.iter().map(|a| a.amount))?,
+                Arc::new(allocations.iter().map(|a| a.proportion).collect::<Float64Array>()),
+                Arc::new(allocations.iter().map(|a| a.perbill).collect::<UInt32Array>()),
+            ],
+            provenance,
+        )?;
+
+        Ok(Self { winners, allocations })
+    }
+}
+
+/// Schema of the `candidates` table
+pub fn candidates_schema() -> SchemaRef {
+    schema(vec![
+        Field::new("account_id", DataType::Utf8, false),
+        amount_field("stake"),
+        Field::new("commission_rate", DataType::UInt8, true),
+        Field::new("on_chain_status", DataType::Utf8, true),
+        Field::new("blocked", DataType::Boolean, true),
+    ])
+}
+
+/// Schema of the `nominators` table
+pub fn nominators_schema() -> SchemaRef {
+    schema(vec![
+        Field::new("account_id", DataType::Utf8, false),
+        amount_field("stake"),
+        Field::new("target_count", DataType::UInt32, false),
+        Field::new("pool_id", DataType::UInt32, true),
+    ])
+}
+
+/// Schema of the `edges` table
+pub fn edges_schema() -> SchemaRef {
+    schema(vec![
+        Field::new("nominator_id", DataType::Utf8, false),
+        Field::new("validator_id", DataType::Utf8, false),
+        Field::new("position", DataType::UInt32, false),
+    ])
+}
+
+/// Schema of the `winners` table
+pub fn winners_schema() -> SchemaRef {
+    schema(vec![
+        Field::new("rank", DataType::UInt32, true),
+        Field::new("account_id", DataType::Utf8, false),
+        amount_field("total_backing_stake"),
+        Field::new("nominator_count", DataType::UInt32, false),
+    ])
+}
+
+/// Schema of the `allocations` table
+pub fn allocations_schema() -> SchemaRef {
+    schema(vec![
+        Field::new("nominator_id", DataType::Utf8, false),
+        Field::new("validator_id", DataType::Utf8, false),
+        amount_field("amount"),
+        Field::new("proportion", DataType::Float64, false),
+        Field::new("perbill", DataType::UInt32, false),
+    ])
+}
+
+/// Writer for record batches as zstd-compressed Parquet files
+///
+/// Each table is written to `<table>.parquet` in the output directory.
+pub struct ParquetWriter;
+
+impl ParquetWriter {
+    /// Create a new Parquet writer
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Write `candidates.parquet`, `nominators.parquet` and `edges.parquet` to `dir`
+    pub fn write_data_to_dir(
+        &self,
+        data: &ElectionData,
+        provenance: &Provenance,
+        dir: &Path,
+    ) -> Result<(), ElectionError> {
+        let batches = DataBatches::new(data, provenance)?;
+        create_dir(dir)?;
+        self.write_to_file(&batches.candidates, &dir.join("candidates.parquet"))?;
+        self.write_to_file(&batches.nominators, &dir.join("nominators.parquet"))?;
+        self.write_to_file(&batches.edges, &dir.join("edges.parquet"))
+    }
+
+    /// Write `winners.parquet` and `allocations.parquet` to `dir`
+    pub fn write_result_to_dir(
+        &self,
+        result: &ElectionResult,
+        provenance: &Provenance,
+        dir: &Path,
+    ) -> Result<(), ElectionError> {
+        let batches = ResultBatches::new(result, provenance)?;
+        create_dir(dir)?;
+        self.write_to_file(&batches.winners, &dir.join("winners.parquet"))?;
+        self.write_to_file(&batches.allocations, &dir.join("allocations.parquet"))
+    }
+
+    /// Write `batch` as a Parquet file at `path`
+    pub fn write_to_file(&self, batch: &RecordBatch, path: &Path) -> Result<(), ElectionError> {
+        let file = std::fs::File::create(path).map_err(|e| ElectionError::FileError {
+            message: format!("Failed to write output file: {}", e),
+            path: path.to_path_buf(),
+        })?;
+        self.write(batch, file)
+    }
+
+    /// Write `batch` as Parquet to `writer`
+    pub fn write(&self, batch: &RecordBatch, writer: impl Write + Send) -> Result<(), ElectionError> {
+        let properties = WriterProperties::builder()
+            .set_compression(Compression::ZSTD(ZstdLevel::default()))
+            .build();
+        let write_error = |e: parquet::errors::ParquetError| ElectionError::InvalidData {
+            message: format!("Failed to write Parquet: {}", e),
+        };
+        let mut writer = ArrowWriter::try_new(writer, batch.schema(), Some(properties)).map_err(write_error)?;
+        writer.write(batch).map_err(write_error)?;
+        writer.close().map_err(write_error)?;
+        Ok(())
+    }
+}
+
+impl Default for ParquetWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn schema(mut fields: Vec<Field>) -> SchemaRef {
+    fields.extend(Provenance::fields());
+    Arc::new(Schema::new(fields))
+}
+
+fn amount_field(name: &str) -> Field {
+    Field::new(name, DataType::Decimal128(AMOUNT_PRECISION, 0), false)
+}
+
+fn strings<'a>(values: impl Iterator<Item = &'a str>) -> ArrayRef {
+    Arc::new(values.map(Some).collect::<StringArray>())
+}
+
+/// Amount column; fails for amounts with more than 38 digits
+fn amounts(values: impl Iterator<Item = u128>) -> Result<ArrayRef, ElectionError> {
+    let max = 10i128.pow(AMOUNT_PRECISION as u32) - 1;
+    let values = values
+        .map(|value| {
+            i128::try_from(value)
+                .ok()
+                .filter(|value| *value <= max)
+                .ok_or_else(|| ElectionError::InvalidData {
+                    message: format!("Amount {} does not fit a Decimal128({}, 0) column", value, AMOUNT_PRECISION),
+                })
+        })
+        .collect::<Result<Vec<i128>, _>>()?;
+    let array = Decimal128Array::from(values)
+        .with_precision_and_scale(AMOUNT_PRECISION, 0)
+        .map_err(|e| ElectionError::InvalidData {
+            message: format!("Failed to build amount column: {}", e),
+        })?;
+    Ok(Arc::new(array))
+}
+
+/// Record batch of `columns` followed by the provenance columns
+fn batch(schema: SchemaRef, mut columns: Vec<ArrayRef>, provenance: &Provenance) -> Result<RecordBatch, ElectionError> {
+    let rows = columns.first().map_or(0, |column| column.len());
+    columns.extend(provenance.columns(rows));
+    RecordBatch::try_new(schema, columns).map_err(|e| ElectionError::InvalidData {
+        message: format!("Failed to build record batch: {}", e),
+    })
+}
+
+fn create_dir(dir: &Path) -> Result<(), ElectionError> {
+    std::fs::create_dir_all(dir).map_err(|e| ElectionError::FileError {
+        message: format!("Failed to create output directory: {}", e),
+        path: dir.to_path_buf(),
+    })
+}
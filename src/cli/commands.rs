@@ -1,13 +1,30 @@
 //! CLI commands implementation
 
+use crate::cli::output::{
+    format_balance, format_matrix_csv, format_matrix_table, format_scenario_report, format_sweep_csv,
+    format_validator_table, ValidatorSort,
+};
 use crate::error::ElectionError;
 use crate::engine::ElectionEngine;
+use crate::input::binary::{BinaryLoader, BinaryWriter};
+use crate::input::csv::{CsvLoader, CsvWriter};
+use crate::input::cache::StorageCache;
+use crate::models::account_id::AddressFormat;
+use crate::models::chain_profile::ChainProfile;
 use crate::models::election_config::ElectionConfiguration;
 use crate::models::election_data::ElectionData;
+use crate::models::matrix::MatrixSpec;
+use crate::models::scenario::ScenarioSet;
+use crate::schema::{validate_json, SchemaKind};
 use crate::types::AlgorithmType;
 use clap::Parser;
 use std::path::PathBuf;
 
+/// File the selected validators are written to by `run --format csv --output-file <DIR>`
+pub const VALIDATORS_CSV_FILE: &str = "selected_validators.csv";
+/// File the stake distribution is written to by `run --format csv --output-file <DIR>`
+pub const STAKE_DISTRIBUTION_CSV_FILE: &str = "stake_distribution.csv";
+
 /// Run command for executing elections
 #[derive(Parser)]
 #[command(name = "run")]
@@ -21,7 +38,7 @@ pub struct RunCommand {
     #[arg(long)]
     pub active_set_size: u32,
 
-    /// RPC URL for fetching on-chain data
+    /// RPC URL for fetching on-chain data (http(s):// or ws(s)://)
     #[arg(long, conflicts_with_all = ["input_file", "synthetic"])]
     pub rpc_url: Option<String>,
 
@@ -29,10 +46,19 @@ pub struct RunCommand {
     #[arg(long, requires = "rpc_url")]
     pub block_number: Option<u64>,
 
-    /// Input file path (JSON format)
+    /// Load the snapshot the election for this era ran on: the state at the block before
+    /// the election provider took its snapshot (requires an archive node)
+    #[arg(long, requires = "rpc_url", conflicts_with = "block_number")]
+    pub era: Option<u32>,
+
+    /// Input file path (JSON, binary snapshot, long-format CSV or a directory of CSV files)
     #[arg(long, conflicts_with_all = ["rpc_url", "synthetic"])]
     pub input_file: Option<PathBuf>,
 
+    /// Input format: json, binary or csv (default: detect from the input file)
+    #[arg(long, value_name = "FORMAT", requires = "input_file")]
+    pub input_format: Option<String>,
+
     /// Use synthetic data (requires additional flags)
     #[arg(long, conflicts_with_all = ["rpc_url", "input_file"])]
     pub synthetic: bool,
@@ -45,7 +71,7 @@ pub struct RunCommand {
     #[arg(long)]
     pub output_file: Option<PathBuf>,
 
-    /// Output format: json or human-readable
+    /// Output format: json, human-readable, csv or parquet (parquet needs the `arrow` feature)
     #[arg(long, default_value = "json")]
     pub format: String,
 
@@ -56,13 +82,95 @@ pub struct RunCommand {
     /// Override nominator stake (format: account_id=stake, can be repeated)
     #[arg(long, value_name = "ACCOUNT_ID=STAKE")]
     pub override_nominator_stake: Vec<String>,
+
+    /// Remove a candidate and its nominations (can be repeated)
+    #[arg(long, value_name = "ACCOUNT_ID")]
+    pub remove_candidate: Vec<String>,
+
+    /// Remove a nominator (can be repeated)
+    #[arg(long, value_name = "ACCOUNT_ID")]
+    pub remove_nominator: Vec<String>,
+
+    /// JSON file with overrides; the override options above are applied on top
+    #[arg(long, value_name = "FILE")]
+    pub overrides_file: Option<PathBuf>,
+
+    /// Directory for the RPC storage cache (default: ~/.cache/offline-election)
+    #[arg(long, requires = "rpc_url", conflicts_with = "no_cache")]
+    pub cache_dir: Option<PathBuf>,
+
+    /// Disable the RPC storage cache
+    #[arg(long, requires = "rpc_url")]
+    pub no_cache: bool,
+
+    /// Number of storage keys fetched per RPC request
+    #[arg(long, requires = "rpc_url", default_value_t = crate::input::rpc::DEFAULT_BATCH_SIZE)]
+    pub rpc_batch_size: usize,
+
+    /// Maximum number of RPC requests in flight at once
+    #[arg(long, requires = "rpc_url", default_value_t = crate::input::rpc::DEFAULT_CONCURRENCY)]
+    pub rpc_concurrency: usize,
+
+    /// Fallback RPC URL used when the primary endpoint fails (can be repeated)
+    #[arg(long, value_name = "URL", requires = "rpc_url")]
+    pub fallback_rpc_url: Vec<String>,
+
+    /// Send slow requests to a second endpoint after this many milliseconds
+    #[arg(long, value_name = "MS", requires = "fallback_rpc_url")]
+    pub hedge_after_ms: Option<u64>,
+
+    /// Staking layout to read: relay-chain or asset-hub (default: detect from the chain)
+    #[arg(long, value_name = "PROFILE", requires = "rpc_url")]
+    pub staking_profile: Option<String>,
+
+    /// Refuse to run if the RPC snapshot is incomplete instead of warning
+    #[arg(long)]
+    pub strict_snapshot: bool,
+
+    /// Fail instead of warning when the election has to be adjusted (implies --strict-snapshot)
+    #[arg(long)]
+    pub strict: bool,
+
+    /// Network parameters: polkadot, kusama, westend, a JSON file, or "chain" to read them over RPC
+    /// (default: none; no network limits apply)
+    #[arg(long, value_name = "PROFILE")]
+    pub chain_profile: Option<String>,
+
+    /// Leave nominators with less stake out of the election
+    #[arg(long, value_name = "STAKE")]
+    pub min_nominator_bond: Option<u128>,
+
+    /// Leave candidates with less stake out of the election
+    #[arg(long, value_name = "STAKE")]
+    pub min_validator_bond: Option<u128>,
+
+    /// Break nomination pool backing down to pool members
+    #[arg(long)]
+    pub pool_breakdown: bool,
+
+    /// Write account IDs as hex or SS58: hex, polkadot, kusama, westend, substrate or a prefix number
+    /// (default: the format used by the data)
+    #[arg(long, value_name = "FORMAT")]
+    pub address_format: Option<String>,
+
+    /// Order of the validator table in human-readable output: rank, backing or nominators
+    #[arg(long, default_value = "rank", value_name = "ORDER")]
+    pub sort_by: String,
+
+    /// Token symbol for human-readable amounts (default: from the chain profile)
+    #[arg(long, value_name = "SYMBOL")]
+    pub token_symbol: Option<String>,
+
+    /// Token decimals for human-readable amounts (default: from the chain profile)
+    #[arg(long, value_name = "DECIMALS")]
+    pub token_decimals: Option<u8>,
 }
 
 impl RunCommand {
     /// Execute the run command
     pub async fn execute(&self) -> Result<(), ElectionError> {
         // Load election data
-        let election_data = self.load_data().await?;
+        let mut election_data = self.load_data().await?;
 
         // Parse algorithm type
         let algorithm = self.algorithm.parse::<AlgorithmType>()
@@ -74,15 +182,47 @@ impl RunCommand {
         // Create election configuration
         let mut config = ElectionConfiguration::new()
             .algorithm(algorithm)
-            .active_set_size(self.active_set_size);
+            .active_set_size(self.active_set_size)
+            .strict_snapshot(self.strict_snapshot)
+            .strict(self.strict);
 
         if let Some(block) = self.block_number {
             config = config.block_number(block);
         }
 
+        if let Some(bond) = self.min_nominator_bond {
+            config = config.min_nominator_bond(bond);
+        }
+        if let Some(bond) = self.min_validator_bond {
+            config = config.min_validator_bond(bond);
+        }
+
+        if let Some(profile) = self.resolve_chain_profile().await? {
+            config = config.chain_profile(profile);
+        }
+
+        if let Some(ref format) = self.address_format {
+            let format = format.parse::<AddressFormat>()
+                .map_err(|e| ElectionError::ValidationError {
+                    message: e,
+                    field: Some("address_format".to_string()),
+                })?;
+            // Pool breakdown reads account IDs from the data directly
+            election_data.normalize_account_ids(format)?;
+            config = config.address_format(format);
+        }
+
         // Apply overrides if specified
-        if !self.override_candidate_stake.is_empty() || !self.override_nominator_stake.is_empty() {
-            let mut overrides = crate::models::election_overrides::ElectionOverrides::new();
+        if self.overrides_file.is_some()
+            || !self.override_candidate_stake.is_empty()
+            || !self.override_nominator_stake.is_empty()
+            || !self.remove_candidate.is_empty()
+            || !self.remove_nominator.is_empty()
+        {
+            let mut overrides = match self.overrides_file {
+                Some(ref path) => crate::models::election_overrides::ElectionOverrides::from_file(path)?,
+                None => crate::models::election_overrides::ElectionOverrides::new(),
+            };
             
             // Parse candidate stake overrides
             for override_str in &self.override_candidate_stake {
@@ -95,6 +235,13 @@ impl RunCommand {
                 let (account_id, stake) = self.parse_override(override_str, "nominator")?;
                 overrides.set_nominator_stake(account_id, stake)?;
             }
+
+            for account_id in &self.remove_candidate {
+                overrides.remove_candidate(account_id.clone())?;
+            }
+            for account_id in &self.remove_nominator {
+                overrides.remove_nominator(account_id.clone())?;
+            }
             
             config = config.overrides(overrides);
         }
@@ -103,20 +250,93 @@ impl RunCommand {
 
         // Execute election with diagnostics if requested
         let engine = ElectionEngine::new();
-        let result = engine.execute_with_diagnostics(&config, &election_data, self.diagnostics)?;
+        let mut result = engine.execute_with_diagnostics(&config, &election_data, self.diagnostics)?;
+        for warning in result.warnings() {
+            eprintln!("Warning: {}", warning);
+        }
+        if self.pool_breakdown {
+            result = result.with_pool_backing(&election_data);
+        }
 
         // Output results
-        self.output_result(&result)?;
+        let units = self.token_units(&config, &election_data);
+        self.output_result(&result, &election_data, units.as_ref())?;
 
         Ok(())
     }
 
+    /// Token used for human-readable amounts
+    ///
+    /// Taken from the chain profile, adjusted by `--token-symbol` and
+    /// `--token-decimals`. Without either, amounts are shown in planck.
+    fn token_units(&self, config: &ElectionConfiguration, data: &ElectionData) -> Option<ChainProfile> {
+        let profile = config.chain_profile.clone().or_else(|| {
+            data.metadata
+                .as_ref()
+                .and_then(|metadata| metadata.chain.as_deref())
+                .and_then(ChainProfile::for_chain)
+        });
+        if profile.is_none() && self.token_symbol.is_none() && self.token_decimals.is_none() {
+            return None;
+        }
+        let mut profile = profile.unwrap_or_else(|| ChainProfile::new("custom"));
+        if let Some(ref symbol) = self.token_symbol {
+            profile.token_symbol = symbol.clone();
+        }
+        if let Some(decimals) = self.token_decimals {
+            profile.token_decimals = decimals;
+        }
+        Some(profile)
+    }
+
+    /// Resolve `--chain-profile` to a preset, a file, or constants read over RPC
+    async fn resolve_chain_profile(&self) -> Result<Option<ChainProfile>, ElectionError> {
+        let Some(ref profile) = self.chain_profile else {
+            return Ok(None);
+        };
+        if profile == "chain" {
+            let rpc_url = self.rpc_url.as_ref().ok_or_else(|| ElectionError::ValidationError {
+                message: "--chain-profile chain requires --rpc-url".to_string(),
+                field: Some("chain_profile".to_string()),
+            })?;
+            let loader = crate::input::rpc::RpcLoader::with_endpoints(
+                std::iter::once(rpc_url).chain(&self.fallback_rpc_url),
+            )?;
+            return loader.load_chain_profile().await.map(Some);
+        }
+        if let Ok(preset) = profile.parse::<ChainProfile>() {
+            return Ok(Some(preset));
+        }
+        ChainProfile::from_file(profile).map(Some)
+    }
+
     /// Load election data from the specified source
     async fn load_data(&self) -> Result<ElectionData, ElectionError> {
         if let Some(ref rpc_url) = self.rpc_url {
             // Load from RPC
-            let loader = crate::input::rpc::RpcLoader::new(rpc_url)?;
-            let block_number = self.block_number.unwrap_or_else(|| {
+            let endpoints = std::iter::once(rpc_url).chain(&self.fallback_rpc_url);
+            let mut loader = crate::input::rpc::RpcLoader::with_endpoints(endpoints)?
+                .with_batch_size(self.rpc_batch_size)
+                .with_concurrency(self.rpc_concurrency);
+            if let Some(ref profile) = self.staking_profile {
+                let profile = profile.parse().map_err(|e| ElectionError::ValidationError {
+                    message: e,
+                    field: Some("staking_profile".to_string()),
+                })?;
+                loader = loader.with_profile(profile);
+            }
+            if let Some(hedge_after_ms) = self.hedge_after_ms {
+                loader = loader.with_hedge_delay(std::time::Duration::from_millis(hedge_after_ms));
+            }
+            if !self.no_cache {
+                let cache_dir = self.cache_dir.clone().unwrap_or_else(StorageCache::default_dir);
+                loader = loader.with_cache(StorageCache::new(cache_dir)?);
+            }
+            if let Some(era) = self.era {
+                return loader.load_for_era(era).await;
+            }
+
+            let block_number = self.block_number.unwrap_or({
                 // If no block number specified, use latest (None = latest)
                 0 // We'll handle this in the RPC loader
             });
@@ -128,9 +348,18 @@ impl RunCommand {
                 loader.load_at_block(block_number).await
             }
         } else if let Some(ref input_file) = self.input_file {
-            // Load from JSON file
-            let json_loader = crate::input::json::JsonLoader::new();
-            json_loader.load_from_file(input_file.clone())
+            // Load from a JSON file, binary snapshot or CSV
+            match self.input_format.as_deref() {
+                None => crate::input::load_data_file(input_file.clone()),
+                Some("json") => crate::input::json::JsonLoader::new().load_from_file(input_file.clone()),
+                Some("binary") => BinaryLoader::new().load_from_file(input_file.clone()),
+                Some("csv") if input_file.is_dir() => CsvLoader::new().load_from_dir(input_file.clone()),
+                Some("csv") => CsvLoader::new().load_from_file(input_file.clone()),
+                Some(other) => Err(ElectionError::ValidationError {
+                    message: format!("Unknown input format '{}'. Expected json, binary or csv", other),
+                    field: Some("input_format".to_string()),
+                }),
+            }
         } else if self.synthetic {
             // Create synthetic data using the builder
             // For CLI, create a simple example with a few candidates and nominators
@@ -160,12 +389,19 @@ impl RunCommand {
     }
 
     /// Output election results
-    fn output_result(&self, result: &crate::models::election_result::ElectionResult) -> Result<(), ElectionError> {
-        let output = if self.format == "human-readable" {
-            self.format_human_readable(result)?
-        } else {
-            result.to_json()?
-        };
+    fn output_result(
+        &self,
+        result: &crate::models::election_result::ElectionResult,
+        data: &ElectionData,
+        units: Option<&ChainProfile>,
+    ) -> Result<(), ElectionError> {
+        match self.format.as_str() {
+            "human-readable" => {}
+            "csv" => return self.write_csv_result(result),
+            "parquet" => return self.write_parquet_result(result, data),
+            _ => return self.write_json_result(result),
+        }
+        let output = self.format_human_readable(result, units)?;
 
         if let Some(ref output_file) = self.output_file {
             std::fs::write(output_file, output).map_err(|e| ElectionError::FileError {
@@ -179,6 +415,92 @@ impl RunCommand {
         Ok(())
     }
 
+    /// Stream the result as JSON to the output file or stdout
+    fn write_json_result(&self, result: &crate::models::election_result::ElectionResult) -> Result<(), ElectionError> {
+        use std::io::Write;
+
+        match self.output_file {
+            Some(ref output_file) => {
+                let write_error = |e: std::io::Error| ElectionError::FileError {
+                    message: format!("Failed to write output file: {}", e),
+                    path: output_file.clone(),
+                };
+                let file = std::fs::File::create(output_file).map_err(write_error)?;
+                let mut writer = std::io::BufWriter::new(file);
+                result.write_json(&mut writer)?;
+                writer.flush().map_err(write_error)
+            }
+            None => {
+                let mut writer = std::io::BufWriter::new(std::io::stdout().lock());
+                result.write_json(&mut writer)?;
+                writeln!(writer).and_then(|_| writer.flush()).map_err(|e| ElectionError::InvalidData {
+                    message: format!("Failed to write output: {}", e),
+                })
+            }
+        }
+    }
+
+    /// Write the selected validators and stake distribution as CSV
+    ///
+    /// With `--output-file`, the path is a directory that receives
+    /// `selected_validators.csv` and `stake_distribution.csv`; on stdout the
+    /// two tables are separated by a blank line.
+    fn write_csv_result(&self, result: &crate::models::election_result::ElectionResult) -> Result<(), ElectionError> {
+        use std::io::Write;
+
+        match self.output_file {
+            Some(ref output_dir) => {
+                std::fs::create_dir_all(output_dir).map_err(|e| ElectionError::FileError {
+                    message: format!("Failed to create output directory: {}", e),
+                    path: output_dir.clone(),
+                })?;
+                let create = |name: &str| {
+                    let path = output_dir.join(name);
+                    std::fs::File::create(&path)
+                        .map(std::io::BufWriter::new)
+                        .map_err(|e| ElectionError::FileError {
+                            message: format!("Failed to write output file: {}", e),
+                            path,
+                        })
+                };
+                result.write_validators_csv(create(VALIDATORS_CSV_FILE)?)?;
+                result.write_stake_distribution_csv(create(STAKE_DISTRIBUTION_CSV_FILE)?)
+            }
+            None => {
+                let mut writer = std::io::BufWriter::new(std::io::stdout().lock());
+                result.write_validators_csv(&mut writer)?;
+                writeln!(writer).map_err(|e| ElectionError::InvalidData {
+                    message: format!("Failed to write output: {}", e),
+                })?;
+                result.write_stake_distribution_csv(&mut writer)
+            }
+        }
+    }
+
+    /// Write the winners and allocations as Parquet files to the `--output-file` directory
+    fn write_parquet_result(
+        &self,
+        result: &crate::models::election_result::ElectionResult,
+        data: &ElectionData,
+    ) -> Result<(), ElectionError> {
+        let Some(ref output_dir) = self.output_file else {
+            return Err(ElectionError::ValidationError {
+                message: "--format parquet requires --output-file (a directory)".to_string(),
+                field: Some("output_file".to_string()),
+            });
+        };
+        #[cfg(feature = "arrow")]
+        {
+            let provenance = crate::arrow::Provenance::of_result(result, data);
+            crate::arrow::ParquetWriter::new().write_result_to_dir(result, &provenance, output_dir)
+        }
+        #[cfg(not(feature = "arrow"))]
+        {
+            let _ = (result, data, output_dir);
+            Err(parquet_unavailable())
+        }
+    }
+
     /// Parse an override string in format "account_id=stake"
     fn parse_override(&self, override_str: &str, override_type: &str) -> Result<(String, u128), ElectionError> {
         let parts: Vec<&str> = override_str.split('=').collect();
@@ -207,27 +529,50 @@ impl RunCommand {
     }
 
     /// Format result as human-readable text
-    fn format_human_readable(&self, result: &crate::models::election_result::ElectionResult) -> Result<String, ElectionError> {
+    fn format_human_readable(
+        &self,
+        result: &crate::models::election_result::ElectionResult,
+        units: Option<&ChainProfile>,
+    ) -> Result<String, ElectionError> {
+        let sort = self.sort_by.parse::<ValidatorSort>()
+            .map_err(|e| ElectionError::ValidationError {
+                message: e,
+                field: Some("sort_by".to_string()),
+            })?;
+
         let mut output = String::new();
         output.push_str("Election Results\n");
         output.push_str("================\n");
         output.push_str(&format!("Algorithm: {:?}\n", result.algorithm_used));
-        output.push_str(&format!("Total Stake: {}\n", result.total_stake));
+        output.push_str(&format!("Total Stake: {}\n", format_balance(result.total_stake, units)));
+        let accounting = result.accounting();
+        output.push_str(&format!("  Assigned: {}\n", format_balance(accounting.assigned_stake, units)));
+        output.push_str(&format!(
+            "  Unassigned: {} ({} nominators)\n",
+            format_balance(accounting.unassigned_stake, units),
+            accounting.unassigned.len()
+        ));
+        output.push_str(&format!("  Rounding Residue: {}\n", format_balance(accounting.rounding_residue, units)));
         output.push_str(&format!("Selected Validators: {}\n\n", result.selected_validators.len()));
 
         output.push_str("Selected Validators:\n");
-        for (idx, validator) in result.selected_validators.iter().take(10).enumerate() {
-            output.push_str(&format!(
-                "{}. {} - Stake: {}, Nominators: {}\n",
-                idx + 1,
-                validator.account_id,
-                validator.total_backing_stake,
-                validator.nominator_count
-            ));
-        }
+        output.push_str(&format_validator_table(result, units, sort));
 
-        if result.selected_validators.len() > 10 {
-            output.push_str(&format!("... and {} more\n", result.selected_validators.len() - 10));
+        if let Some(backing) = result.pool_backing() {
+            output.push_str("\nNomination Pool Backing\n");
+            output.push_str("=======================\n");
+            if backing.is_empty() {
+                output.push_str("No nomination pools in the election data\n");
+            }
+            for entry in backing {
+                output.push_str(&format!(
+                    "Pool {}: {} -> {}: {}\n",
+                    entry.pool_id,
+                    entry.member_id,
+                    entry.validator_id,
+                    format_balance(entry.amount, units)
+                ));
+            }
         }
 
         // Include diagnostics if available
@@ -242,6 +587,17 @@ impl RunCommand {
                 }
             }
 
+            if !diagnostics.exclusions.is_empty() {
+                output.push_str("\nExcluded:\n");
+                for exclusion in &diagnostics.exclusions {
+                    let role = match exclusion.role {
+                        crate::diagnostics::models::ExcludedRole::Voter => "voter",
+                        crate::diagnostics::models::ExcludedRole::Target => "target",
+                    };
+                    output.push_str(&format!("  - {} ({}): {}\n", exclusion.account_id, role, exclusion.reason));
+                }
+            }
+
             if !diagnostics.validator_explanations.is_empty() {
                 output.push_str("\nValidator Explanations:\n");
                 for explanation in &diagnostics.validator_explanations {
@@ -279,3 +635,526 @@ impl ServerCommand {
         server.start().await
     }
 }
+
+/// Cache command for inspecting and purging the RPC storage cache
+#[derive(Parser)]
+#[command(name = "cache")]
+#[command(about = "Inspect or purge the RPC storage cache")]
+pub struct CacheCommand {
+    /// Cache directory (default: ~/.cache/offline-election)
+    #[arg(long)]
+    pub cache_dir: Option<PathBuf>,
+
+    /// Remove cached data instead of listing it
+    #[arg(long)]
+    pub purge: bool,
+
+    /// Only purge data for this block hash
+    #[arg(long, requires = "purge")]
+    pub block_hash: Option<String>,
+}
+
+impl CacheCommand {
+    /// Execute the cache command
+    pub async fn execute(&self) -> Result<(), ElectionError> {
+        let cache_dir = self.cache_dir.clone().unwrap_or_else(StorageCache::default_dir);
+        let cache = StorageCache::new(cache_dir)?;
+
+        if self.purge {
+            if let Some(ref block_hash) = self.block_hash {
+                if cache.purge_block(block_hash)? {
+                    println!("Removed cached data for block {}", block_hash);
+                } else {
+                    println!("No cached data for block {}", block_hash);
+                }
+            } else {
+                let removed = cache.purge_all()?;
+                println!("Removed cached data for {} block(s) from {}", removed, cache.root().display());
+            }
+            return Ok(());
+        }
+
+        let entries = cache.inspect()?;
+        println!("Cache directory: {}", cache.root().display());
+        if entries.is_empty() {
+            println!("Cache is empty");
+            return Ok(());
+        }
+
+        for entry in &entries {
+            println!("\nBlock {} ({} bytes)", entry.block_hash, entry.size_bytes);
+            println!("  Values: {}", entry.value_count);
+            for (prefix, count, complete) in &entry.key_listings {
+                let status = if *complete { "complete" } else { "partial" };
+                println!("  Keys {}: {} ({})", prefix, count, status);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Scenario command for comparing what-if scenarios against a baseline
+#[derive(Parser)]
+#[command(name = "scenario")]
+#[command(about = "Run the scenarios in a scenario file and compare them against the baseline")]
+pub struct ScenarioCommand {
+    /// Scenario file (JSON, or TOML with a .toml extension)
+    pub file: PathBuf,
+
+    /// Output file path (default: stdout)
+    #[arg(long)]
+    pub output_file: Option<PathBuf>,
+
+    /// Output format: json or human-readable
+    #[arg(long, default_value = "human-readable")]
+    pub format: String,
+}
+
+impl ScenarioCommand {
+    /// Execute the scenario command
+    pub async fn execute(&self) -> Result<(), ElectionError> {
+        let set = ScenarioSet::from_file(&self.file)?;
+        let data = set.load_base()?;
+        let config = set.configuration()?;
+
+        let report = ElectionEngine::new().execute_scenarios(&config, &set.scenarios, &data)?;
+        for warning in report.baseline.warnings() {
+            eprintln!("Warning: baseline: {}", warning);
+        }
+        for outcome in &report.scenarios {
+            for warning in outcome.result.warnings() {
+                eprintln!("Warning: {}: {}", outcome.name, warning);
+            }
+        }
+
+        let output = if self.format == "human-readable" {
+            let units = data
+                .metadata
+                .as_ref()
+                .and_then(|metadata| metadata.chain.as_deref())
+                .and_then(ChainProfile::for_chain);
+            format_scenario_report(&report, units.as_ref())
+        } else {
+            serde_json::to_string_pretty(&report).map_err(|e| ElectionError::InvalidData {
+                message: format!("Failed to serialize scenario report: {}", e),
+            })?
+        };
+
+        if let Some(ref output_file) = self.output_file {
+            std::fs::write(output_file, output).map_err(|e| ElectionError::FileError {
+                message: format!("Failed to write output file: {}", e),
+                path: output_file.clone(),
+            })?;
+        } else {
+            println!("{}", output);
+        }
+        Ok(())
+    }
+}
+
+/// Matrix command for comparing algorithms across active set sizes
+#[derive(Parser)]
+#[command(name = "matrix")]
+#[command(about = "Run every algorithm at every active set size on the same data and compare them")]
+pub struct MatrixCommand {
+    /// Algorithms to compare, comma-separated; the first is the overlap reference
+    #[arg(
+        long,
+        value_delimiter = ',',
+        default_value = "sequential-phragmen,parallel-phragmen,multi-phase"
+    )]
+    pub algorithms: Vec<String>,
+
+    /// Active set sizes to run each algorithm at, comma-separated
+    #[arg(long, value_delimiter = ',', required = true)]
+    pub active_set_sizes: Vec<u32>,
+
+    /// Input file path (JSON, binary snapshot or CSV)
+    #[arg(long, conflicts_with = "rpc_url", required_unless_present = "rpc_url")]
+    pub input_file: Option<PathBuf>,
+
+    /// RPC URL for fetching on-chain data (http(s):// or ws(s)://)
+    #[arg(long)]
+    pub rpc_url: Option<String>,
+
+    /// Block number for RPC snapshot (default: latest)
+    #[arg(long, requires = "rpc_url")]
+    pub block_number: Option<u64>,
+
+    /// Worker threads (default: available parallelism)
+    #[arg(long)]
+    pub threads: Option<usize>,
+
+    /// Output file path (default: stdout)
+    #[arg(long)]
+    pub output_file: Option<PathBuf>,
+
+    /// Output format: text, csv or json
+    #[arg(long, default_value = "text")]
+    pub format: String,
+}
+
+impl MatrixCommand {
+    /// Execute the matrix command
+    pub async fn execute(&self) -> Result<(), ElectionError> {
+        let algorithms = self
+            .algorithms
+            .iter()
+            .map(|algorithm| {
+                algorithm.parse::<AlgorithmType>().map_err(|e| ElectionError::ValidationError {
+                    message: format!("Invalid algorithm: {}", e),
+                    field: Some("algorithms".to_string()),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut spec = MatrixSpec::new(algorithms, self.active_set_sizes.clone());
+        if let Some(threads) = self.threads {
+            spec = spec.threads(threads);
+        }
+
+        let data = load_snapshot(self.input_file.as_ref(), self.rpc_url.as_deref(), self.block_number).await?;
+
+        let config = ElectionConfiguration::new().active_set_size(spec.active_set_sizes[0]);
+        let report = ElectionEngine::new().execute_matrix(&config, &spec, &data)?;
+
+        let output = match self.format.as_str() {
+            "json" => serde_json::to_string_pretty(&report).map_err(|e| ElectionError::InvalidData {
+                message: format!("Failed to serialize matrix report: {}", e),
+            })?,
+            "csv" => format_matrix_csv(&report),
+            "text" => format_matrix_table(&report),
+            other => {
+                return Err(ElectionError::ValidationError {
+                    message: format!("Unknown format: {} (expected text, csv or json)", other),
+                    field: Some("format".to_string()),
+                })
+            }
+        };
+
+        if let Some(ref output_file) = self.output_file {
+            std::fs::write(output_file, output).map_err(|e| ElectionError::FileError {
+                message: format!("Failed to write output file: {}", e),
+                path: output_file.clone(),
+            })?;
+        } else {
+            print!("{}", output);
+        }
+        Ok(())
+    }
+}
+
+/// Active set size sweep command for charting backing against validator count
+#[derive(Parser)]
+#[command(name = "sweep")]
+#[command(about = "Run an algorithm over a range of active set sizes and report the backing at each")]
+pub struct SweepCommand {
+    /// Election algorithm to use (sequential-phragmen, parallel-phragmen, multi-phase)
+    #[arg(long, default_value = "sequential-phragmen")]
+    pub algorithm: String,
+
+    /// Smallest active set size
+    #[arg(long)]
+    pub min_size: u32,
+
+    /// Largest active set size
+    #[arg(long)]
+    pub max_size: u32,
+
+    /// Distance between sizes; the largest size is always included
+    #[arg(long, default_value_t = 1)]
+    pub step: u32,
+
+    /// Input file path (JSON, binary snapshot or CSV)
+    #[arg(long, conflicts_with = "rpc_url", required_unless_present = "rpc_url")]
+    pub input_file: Option<PathBuf>,
+
+    /// RPC URL for fetching on-chain data (http(s):// or ws(s)://)
+    #[arg(long)]
+    pub rpc_url: Option<String>,
+
+    /// Block number for RPC snapshot (default: latest)
+    #[arg(long, requires = "rpc_url")]
+    pub block_number: Option<u64>,
+
+    /// Output file path (default: stdout)
+    #[arg(long)]
+    pub output_file: Option<PathBuf>,
+
+    /// Output format: csv or json
+    #[arg(long, default_value = "csv")]
+    pub format: String,
+}
+
+impl SweepCommand {
+    /// Execute the sweep command
+    pub async fn execute(&self) -> Result<(), ElectionError> {
+        let algorithm = self.algorithm.parse::<AlgorithmType>()
+            .map_err(|e| ElectionError::ValidationError {
+                message: format!("Invalid algorithm: {}", e),
+                field: Some("algorithm".to_string()),
+            })?;
+        if self.step == 0 || self.min_size == 0 || self.min_size > self.max_size {
+            return Err(ElectionError::ValidationError {
+                message: "Sweep needs 0 < --min-size <= --max-size and a --step greater than 0".to_string(),
+                field: Some("active_set_size".to_string()),
+            });
+        }
+        let mut sizes: Vec<u32> = (self.min_size..=self.max_size).step_by(self.step as usize).collect();
+        if sizes.last() != Some(&self.max_size) {
+            sizes.push(self.max_size);
+        }
+
+        let data = load_snapshot(self.input_file.as_ref(), self.rpc_url.as_deref(), self.block_number).await?;
+        let config = ElectionConfiguration::new()
+            .algorithm(algorithm)
+            .active_set_size(self.max_size)
+            .build()?;
+        let report = ElectionEngine::new().execute_sweep(&config, &sizes, &data)?;
+
+        let output = match self.format.as_str() {
+            "json" => serde_json::to_string_pretty(&report).map_err(|e| ElectionError::InvalidData {
+                message: format!("Failed to serialize sweep report: {}", e),
+            })?,
+            "csv" => format_sweep_csv(&report),
+            other => {
+                return Err(ElectionError::ValidationError {
+                    message: format!("Unknown format: {} (expected csv or json)", other),
+                    field: Some("format".to_string()),
+                })
+            }
+        };
+
+        if let Some(ref output_file) = self.output_file {
+            std::fs::write(output_file, output).map_err(|e| ElectionError::FileError {
+                message: format!("Failed to write output file: {}", e),
+                path: output_file.clone(),
+            })?;
+        } else {
+            print!("{}", output);
+        }
+        Ok(())
+    }
+}
+
+/// Convert command for converting election data between JSON, binary snapshots, CSV and Parquet
+#[derive(Parser)]
+#[command(name = "convert")]
+#[command(about = "Convert election data between JSON, binary snapshots, CSV and Parquet")]
+pub struct ConvertCommand {
+    /// Input file: JSON, binary snapshot (detected from its first bytes), long-format CSV
+    /// or a directory of CSV files
+    pub input_file: PathBuf,
+
+    /// Output file, or directory for CSV and Parquet output
+    pub output_file: PathBuf,
+
+    /// Output format: json, binary, csv or parquet (default: binary for JSON input, json otherwise;
+    /// parquet needs the `arrow` feature)
+    #[arg(long)]
+    pub to: Option<String>,
+
+    /// Compress binary output with zstd
+    #[arg(long)]
+    pub compress: bool,
+}
+
+impl ConvertCommand {
+    /// Execute the convert command
+    pub async fn execute(&self) -> Result<(), ElectionError> {
+        let to = match self.to.as_deref() {
+            Some(to @ ("json" | "binary" | "csv" | "parquet")) => to,
+            Some(other) => {
+                return Err(ElectionError::ValidationError {
+                    message: format!("Unknown output format '{}'. Expected json, binary, csv or parquet", other),
+                    field: Some("to".to_string()),
+                })
+            }
+            None if CsvLoader::is_csv_path(&self.input_file)
+                || BinaryLoader::is_snapshot_file(&self.input_file)? =>
+            {
+                "json"
+            }
+            None => "binary",
+        };
+        if self.compress && to != "binary" {
+            return Err(ElectionError::ValidationError {
+                message: "--compress only applies to binary output".to_string(),
+                field: Some("compress".to_string()),
+            });
+        }
+
+        #[cfg(not(feature = "arrow"))]
+        if to == "parquet" {
+            return Err(parquet_unavailable());
+        }
+
+        let data = crate::input::load_data_file(self.input_file.clone())?;
+        match to {
+            "binary" => BinaryWriter::new()
+                .compressed(self.compress)
+                .write_to_file(&data, &self.output_file)?,
+            "csv" => CsvWriter::new().write_to_dir(&data, &self.output_file)?,
+            #[cfg(feature = "arrow")]
+            "parquet" => crate::arrow::ParquetWriter::new().write_data_to_dir(
+                &data,
+                &crate::arrow::Provenance::of_data(&data),
+                &self.output_file,
+            )?,
+            _ => {
+                let write_error = |e: std::io::Error| ElectionError::FileError {
+                    message: format!("Failed to write output file: {}", e),
+                    path: self.output_file.clone(),
+                };
+                let file = std::fs::File::create(&self.output_file).map_err(write_error)?;
+                let mut writer = std::io::BufWriter::new(file);
+                serde_json::to_writer_pretty(&mut writer, &data).map_err(|e| ElectionError::InvalidData {
+                    message: format!("Failed to write election data as JSON: {}", e),
+                })?;
+                std::io::Write::flush(&mut writer).map_err(write_error)?;
+            }
+        }
+
+        println!(
+            "Wrote {} candidates and {} nominators to {} ({})",
+            data.candidates.len(),
+            data.nominators.len(),
+            self.output_file.display(),
+            to
+        );
+        Ok(())
+    }
+}
+
+/// Schema command for printing the JSON Schema of an input or output format
+#[derive(Parser)]
+#[command(name = "schema")]
+#[command(about = "Print the JSON Schema of an input or output format")]
+pub struct SchemaCommand {
+    /// Format: election-data, overrides, request or result
+    #[arg(default_value = "election-data")]
+    pub kind: String,
+
+    /// Output file path (default: stdout)
+    #[arg(long)]
+    pub output_file: Option<PathBuf>,
+}
+
+impl SchemaCommand {
+    /// Execute the schema command
+    pub async fn execute(&self) -> Result<(), ElectionError> {
+        let kind = parse_schema_kind(&self.kind)?;
+        let output = serde_json::to_string_pretty(&kind.schema()).map_err(|e| ElectionError::InvalidData {
+            message: format!("Failed to serialize schema: {}", e),
+        })?;
+        if let Some(ref output_file) = self.output_file {
+            std::fs::write(output_file, output).map_err(|e| ElectionError::FileError {
+                message: format!("Failed to write output file: {}", e),
+                path: output_file.clone(),
+            })?;
+        } else {
+            println!("{}", output);
+        }
+        Ok(())
+    }
+}
+
+/// Validate command for checking a JSON file and reporting every problem in it
+#[derive(Parser)]
+#[command(name = "validate")]
+#[command(about = "Check a JSON file against its schema and report every problem")]
+pub struct ValidateCommand {
+    /// JSON file to check
+    pub file: PathBuf,
+
+    /// What the file holds: election-data, overrides, request or result
+    #[arg(long, default_value = "election-data")]
+    pub kind: String,
+
+    /// Output format: text or json
+    #[arg(long, default_value = "text")]
+    pub format: String,
+}
+
+impl ValidateCommand {
+    /// Execute the validate command
+    ///
+    /// Fails when the file has any problem, after printing all of them.
+    pub async fn execute(&self) -> Result<(), ElectionError> {
+        let kind = parse_schema_kind(&self.kind)?;
+        let text = std::fs::read_to_string(&self.file).map_err(|e| ElectionError::FileError {
+            message: format!("Failed to read file: {}", e),
+            path: self.file.clone(),
+        })?;
+        let issues = validate_json(kind, &text);
+
+        match self.format.as_str() {
+            "json" => {
+                let output = serde_json::to_string_pretty(&issues).map_err(|e| ElectionError::InvalidData {
+                    message: format!("Failed to serialize validation issues: {}", e),
+                })?;
+                println!("{}", output);
+            }
+            "text" if issues.is_empty() => println!("{} is valid {}", self.file.display(), kind),
+            "text" => {
+                for issue in &issues {
+                    println!("{}", issue);
+                }
+            }
+            other => {
+                return Err(ElectionError::ValidationError {
+                    message: format!("Unknown format: {} (expected text or json)", other),
+                    field: Some("format".to_string()),
+                })
+            }
+        }
+
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            Err(ElectionError::ValidationError {
+                message: format!("{} problem(s) found in {}", issues.len(), self.file.display()),
+                field: None,
+            })
+        }
+    }
+}
+
+fn parse_schema_kind(kind: &str) -> Result<SchemaKind, ElectionError> {
+    kind.parse().map_err(|message| ElectionError::ValidationError {
+        message,
+        field: Some("kind".to_string()),
+    })
+}
+
+/// Error for Parquet output in a build without the `arrow` feature
+#[cfg(not(feature = "arrow"))]
+fn parquet_unavailable() -> ElectionError {
+    ElectionError::ValidationError {
+        message: "Parquet output requires building with the `arrow` feature".to_string(),
+        field: Some("format".to_string()),
+    }
+}
+
+/// Load election data from a JSON file, a binary snapshot, CSV or an RPC snapshot (cached)
+async fn load_snapshot(
+    input_file: Option<&PathBuf>,
+    rpc_url: Option<&str>,
+    block_number: Option<u64>,
+) -> Result<ElectionData, ElectionError> {
+    match (input_file, rpc_url) {
+        (Some(input_file), _) => crate::input::load_data_file(input_file.clone()),
+        (None, Some(rpc_url)) => {
+            let loader = crate::input::rpc::RpcLoader::new(rpc_url)?
+                .with_cache(StorageCache::new(StorageCache::default_dir())?);
+            match block_number {
+                Some(block_number) => loader.load_at_block(block_number).await,
+                None => loader.load_latest().await,
+            }
+        }
+        (None, None) => Err(ElectionError::ValidationError {
+            message: "Must specify one of: --rpc-url or --input-file".to_string(),
+            field: None,
+        }),
+    }
+}
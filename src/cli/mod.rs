@@ -3,7 +3,13 @@
 pub mod commands;
 pub mod output;
 
-pub use commands::{RunCommand, ServerCommand};
-pub use output::format_json;
+pub use commands::{
+    CacheCommand, ConvertCommand, MatrixCommand, RunCommand, ScenarioCommand, SchemaCommand, ServerCommand, SweepCommand,
+    ValidateCommand,
+};
+pub use output::{
+    format_json, format_matrix_csv, format_matrix_table, format_scenario_report, format_sweep_csv, format_validator_table,
+    ValidatorSort,
+};
 
 
@@ -1,12 +1,267 @@
 //! CLI output formatting
-//! Will be implemented in Phase 3 (T052-T053)
 
-use crate::models::election_result::ElectionResult;
+use crate::models::chain_profile::ChainProfile;
+use crate::models::election_result::{ElectionResult, SelectedValidator};
+use crate::models::matrix::MatrixReport;
+use crate::models::scenario::ScenarioReport;
+use crate::models::sweep::SweepReport;
 
 /// Format election result as JSON
 pub fn format_json(result: &ElectionResult) -> Result<String, crate::error::ElectionError> {
-    // Implementation will be added in Phase 3 (T052-T053)
     result.to_json()
 }
 
+/// Order of the validator table in human-readable output
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ValidatorSort {
+    /// Election rank, best first
+    #[default]
+    Rank,
+    /// Total backing stake, highest first
+    Backing,
+    /// Number of nominators, most first
+    Nominators,
+}
+
+impl std::str::FromStr for ValidatorSort {
+    type Err = String;
+
+    /// Parse `rank`, `backing` or `nominators`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "rank" => Ok(ValidatorSort::Rank),
+            "backing" | "stake" => Ok(ValidatorSort::Backing),
+            "nominators" => Ok(ValidatorSort::Nominators),
+            _ => Err(format!("Unknown sort order: {} (expected rank, backing or nominators)", s)),
+        }
+    }
+}
+
+/// Format a balance in tokens when the chain's units are known, in planck otherwise
+pub fn format_balance(amount: u128, units: Option<&ChainProfile>) -> String {
+    match units {
+        Some(profile) => profile.format_balance(amount),
+        None => amount.to_string(),
+    }
+}
+
+/// Table of all selected validators with their share of the total stake
+pub fn format_validator_table(result: &ElectionResult, units: Option<&ChainProfile>, sort: ValidatorSort) -> String {
+    let mut rows: Vec<(u32, &SelectedValidator)> = result
+        .selected_validators
+        .iter()
+        .enumerate()
+        .map(|(idx, validator)| (validator.rank.unwrap_or(idx as u32 + 1), validator))
+        .collect();
+    match sort {
+        ValidatorSort::Rank => rows.sort_by_key(|(rank, _)| *rank),
+        ValidatorSort::Backing => {
+            rows.sort_by(|a, b| b.1.total_backing_stake.cmp(&a.1.total_backing_stake).then(a.0.cmp(&b.0)))
+        }
+        ValidatorSort::Nominators => {
+            rows.sort_by(|a, b| b.1.nominator_count.cmp(&a.1.nominator_count).then(a.0.cmp(&b.0)))
+        }
+    }
+
+    let cells: Vec<[String; 5]> = rows
+        .iter()
+        .map(|(rank, validator)| {
+            let share = if result.total_stake > 0 {
+                format!("{:.2}%", validator.total_backing_stake as f64 * 100.0 / result.total_stake as f64)
+            } else {
+                "-".to_string()
+            };
+            [
+                rank.to_string(),
+                validator.account_id.clone(),
+                format_balance(validator.total_backing_stake, units),
+                share,
+                validator.nominator_count.to_string(),
+            ]
+        })
+        .collect();
+
+    let header = ["Rank", "Account", "Backing", "Share", "Nominators"];
+    let mut widths = header.map(str::len);
+    for row in &cells {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut table = String::new();
+    let mut push_row = |row: [&str; 5]| {
+        let line = format!(
+            "{:>w0$}  {:<w1$}  {:>w2$}  {:>w3$}  {:>w4$}",
+            row[0],
+            row[1],
+            row[2],
+            row[3],
+            row[4],
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2],
+            w3 = widths[3],
+            w4 = widths[4],
+        );
+        table.push_str(line.trim_end());
+        table.push('\n');
+    };
+    push_row(header);
+    for row in &cells {
+        push_row([&row[0], &row[1], &row[2], &row[3], &row[4]]);
+    }
+    table
+}
+
+/// Summary of each scenario's result compared with the baseline
+pub fn format_scenario_report(report: &ScenarioReport, units: Option<&ChainProfile>) -> String {
+    let baseline = &report.baseline;
+    let min_backing = baseline.selected_validators.iter().map(|v| v.total_backing_stake).min().unwrap_or(0);
+    let total_backing: u128 = baseline.selected_validators.iter().map(|v| v.total_backing_stake).sum();
 
+    let mut output = String::new();
+    output.push_str("Baseline\n");
+    output.push_str("========\n");
+    output.push_str(&format!("Algorithm: {:?}\n", baseline.algorithm_used));
+    output.push_str(&format!("Selected Validators: {}\n", baseline.selected_validators.len()));
+    output.push_str(&format!("Minimum Backing: {}\n", format_balance(min_backing, units)));
+    output.push_str(&format!("Total Backing: {}\n", format_balance(total_backing, units)));
+
+    for outcome in &report.scenarios {
+        let comparison = &outcome.comparison;
+        output.push_str(&format!("\nScenario: {}\n", outcome.name));
+        output.push_str(&format!("{}\n", "=".repeat(outcome.name.chars().count() + 10)));
+        output.push_str(&format!("Algorithm: {:?}\n", outcome.result.algorithm_used));
+        output.push_str(&format!("Selected Validators: {}\n", outcome.result.selected_validators.len()));
+        output.push_str(&format!(
+            "Minimum Backing: {} (baseline {})\n",
+            format_balance(comparison.min_backing, units),
+            format_balance(comparison.baseline_min_backing, units)
+        ));
+        output.push_str(&format!(
+            "Total Backing: {} (baseline {})\n",
+            format_balance(comparison.total_backing, units),
+            format_balance(comparison.baseline_total_backing, units)
+        ));
+        if comparison.is_unchanged() {
+            output.push_str("No change from baseline\n");
+            continue;
+        }
+        for validator_id in &comparison.entered {
+            output.push_str(&format!("  + {}\n", validator_id));
+        }
+        for validator_id in &comparison.left {
+            output.push_str(&format!("  - {}\n", validator_id));
+        }
+        for change in &comparison.backing_changes {
+            output.push_str(&format!(
+                "  ~ {}: {} -> {}\n",
+                change.validator_id,
+                format_balance(change.baseline, units),
+                format_balance(change.backing, units)
+            ));
+        }
+    }
+    output
+}
+
+const MATRIX_HEADER: [&str; 9] = [
+    "active_set_size",
+    "algorithm",
+    "selected",
+    "min_stake",
+    "sum_stake",
+    "sum_stake_squared",
+    "overlap",
+    "runtime_ms",
+    "warnings",
+];
+
+fn matrix_rows(report: &MatrixReport) -> Vec<[String; 9]> {
+    report
+        .runs
+        .iter()
+        .map(|run| {
+            [
+                run.active_set_size.to_string(),
+                run.algorithm.to_string(),
+                run.selected.to_string(),
+                run.score.minimal_stake.to_string(),
+                run.score.sum_stake.to_string(),
+                run.score.sum_stake_squared.to_string(),
+                run.overlap.to_string(),
+                format!("{:.3}", run.runtime_ms),
+                run.warnings.to_string(),
+            ]
+        })
+        .collect()
+}
+
+/// Matrix report as CSV with a header row; amounts are in planck
+pub fn format_matrix_csv(report: &MatrixReport) -> String {
+    let mut csv = MATRIX_HEADER.join(",");
+    csv.push('\n');
+    for row in matrix_rows(report) {
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Matrix report as an aligned text table; amounts are in planck
+pub fn format_matrix_table(report: &MatrixReport) -> String {
+    let header = [
+        "Size", "Algorithm", "Selected", "Min Stake", "Sum Stake", "Sum Stake²", "Overlap", "Runtime (ms)", "Warnings",
+    ];
+    let rows = matrix_rows(report);
+    let mut widths = header.map(|h| h.chars().count());
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut table = format!("Overlap is measured against {} at the same size\n\n", report.reference);
+    let mut push_row = |row: [&str; 9]| {
+        let mut line = String::new();
+        for (idx, (cell, width)) in row.iter().zip(widths).enumerate() {
+            let padding = " ".repeat(width - cell.chars().count());
+            if idx > 0 {
+                line.push_str("  ");
+            }
+            // Left-align the algorithm name, right-align numbers
+            if idx == 1 {
+                line.push_str(cell);
+                line.push_str(&padding);
+            } else {
+                line.push_str(&padding);
+                line.push_str(cell);
+            }
+        }
+        table.push_str(line.trim_end());
+        table.push('\n');
+    };
+    push_row(header);
+    for row in &rows {
+        push_row(std::array::from_fn(|idx| row[idx].as_str()));
+    }
+    table
+}
+
+/// Sweep report as CSV with a header row; amounts are in planck
+pub fn format_sweep_csv(report: &SweepReport) -> String {
+    let mut csv = "active_set_size,selected,min_stake,average_stake,sum_stake,sum_stake_squared\n".to_string();
+    for point in &report.points {
+        csv.push_str(&format!(
+            "{},{},{},{},{},{}\n",
+            point.active_set_size,
+            point.selected,
+            point.min_stake,
+            point.average_stake,
+            point.score.sum_stake,
+            point.score.sum_stake_squared
+        ));
+    }
+    csv
+}
@@ -0,0 +1,89 @@
+//! Comparison of an election result against a baseline
+
+use crate::models::election_result::ElectionResult;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// How an election result differs from a baseline result
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResultComparison {
+    /// Validators selected in the result but not in the baseline
+    pub entered: Vec<String>,
+    /// Validators selected in the baseline but not in the result
+    pub left: Vec<String>,
+    /// Validators selected in both whose backing changed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub backing_changes: Vec<BackingChange>,
+    /// Lowest backing of a selected validator in the baseline
+    pub baseline_min_backing: u128,
+    /// Lowest backing of a selected validator in the result
+    pub min_backing: u128,
+    /// Sum of selected validators' backing in the baseline
+    pub baseline_total_backing: u128,
+    /// Sum of selected validators' backing in the result
+    pub total_backing: u128,
+}
+
+/// Backing of a validator selected in both results
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackingChange {
+    /// Account ID of the validator
+    pub validator_id: String,
+    /// Backing in the baseline
+    pub baseline: u128,
+    /// Backing in the result
+    pub backing: u128,
+}
+
+impl ResultComparison {
+    /// Compare `result` against `baseline`
+    ///
+    /// Validator lists keep the order of the result they come from.
+    pub fn new(baseline: &ElectionResult, result: &ElectionResult) -> Self {
+        let baseline_backing: HashMap<&str, u128> = baseline
+            .selected_validators
+            .iter()
+            .map(|v| (v.account_id.as_str(), v.total_backing_stake))
+            .collect();
+        let backing: HashMap<&str, u128> = result
+            .selected_validators
+            .iter()
+            .map(|v| (v.account_id.as_str(), v.total_backing_stake))
+            .collect();
+
+        let mut entered = Vec::new();
+        let mut backing_changes = Vec::new();
+        for validator in &result.selected_validators {
+            match baseline_backing.get(validator.account_id.as_str()) {
+                None => entered.push(validator.account_id.clone()),
+                Some(&before) if before != validator.total_backing_stake => backing_changes.push(BackingChange {
+                    validator_id: validator.account_id.clone(),
+                    baseline: before,
+                    backing: validator.total_backing_stake,
+                }),
+                Some(_) => {}
+            }
+        }
+        let left = baseline
+            .selected_validators
+            .iter()
+            .filter(|v| !backing.contains_key(v.account_id.as_str()))
+            .map(|v| v.account_id.clone())
+            .collect();
+
+        Self {
+            entered,
+            left,
+            backing_changes,
+            baseline_min_backing: baseline_backing.values().copied().min().unwrap_or(0),
+            min_backing: backing.values().copied().min().unwrap_or(0),
+            baseline_total_backing: baseline_backing.values().sum(),
+            total_backing: backing.values().sum(),
+        }
+    }
+
+    /// Whether the result selects exactly the baseline's validators with the same backing
+    pub fn is_unchanged(&self) -> bool {
+        self.entered.is_empty() && self.left.is_empty() && self.backing_changes.is_empty()
+    }
+}
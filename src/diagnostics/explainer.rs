@@ -115,6 +115,7 @@ impl DiagnosticsGenerator {
             stake_analysis,
             algorithm_insights: Some(algorithm_insights),
             warnings,
+            exclusions: Vec::new(),
         })
     }
 
@@ -259,11 +260,7 @@ impl DiagnosticsGenerator {
     fn generate_stake_analysis(&self, result: &ElectionResult) -> StakeAnalysis {
         let total_stake = result.total_stake;
         let validator_count = result.selected_validators.len() as u128;
-        let average_stake = if validator_count > 0 {
-            total_stake / validator_count
-        } else {
-            0
-        };
+        let average_stake = total_stake.checked_div(validator_count).unwrap_or(0);
 
         StakeAnalysis {
             total_stake,
@@ -321,7 +318,7 @@ impl DiagnosticsGenerator {
                 .collect();
             let min_stake = stakes.iter().min().copied().unwrap_or(0);
             let max_stake = stakes.iter().max().copied().unwrap_or(0);
-            let median_stake = if stakes.len() > 0 {
+            let median_stake = if !stakes.is_empty() {
                 let mut sorted = stakes.clone();
                 sorted.sort();
                 sorted[sorted.len() / 2]
@@ -1,9 +1,11 @@
 //! Diagnostic generation for election results
 
+pub mod comparison;
 pub mod explainer;
 pub mod models;
 
+pub use comparison::{BackingChange, ResultComparison};
 pub use explainer::DiagnosticsGenerator;
-pub use models::{Diagnostics, ValidatorExplanation, StakeAnalysis};
+pub use models::{Diagnostics, ExcludedRole, Exclusion, ExclusionReason, StakeAnalysis, ValidatorExplanation};
 
 
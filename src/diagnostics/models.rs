@@ -1,9 +1,10 @@
 //! Diagnostics data models
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Detailed diagnostics explaining election results
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct Diagnostics {
     /// Explanations for each validator
     pub validator_explanations: Vec<ValidatorExplanation>,
@@ -13,12 +14,15 @@ pub struct Diagnostics {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub algorithm_insights: Option<serde_json::Value>,
     /// Warnings or notable conditions
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub warnings: Vec<String>,
+    /// Voters, targets and nominations left out by eligibility rules
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclusions: Vec<Exclusion>,
 }
 
 /// Explanation for why a validator was selected or not selected
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct ValidatorExplanation {
     /// Account ID of the validator
     pub account_id: String,
@@ -32,7 +36,7 @@ pub struct ValidatorExplanation {
 }
 
 /// Analysis of stake distribution
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct StakeAnalysis {
     /// Total stake
     pub total_stake: u128,
@@ -40,6 +44,72 @@ pub struct StakeAnalysis {
     pub average_stake_per_validator: u128,
 }
 
+/// Voter, target or single nomination excluded from the election
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Exclusion {
+    /// Account ID of the excluded voter or target
+    pub account_id: String,
+    /// Whether the account was a voter or a target
+    pub role: ExcludedRole,
+    /// Why it was excluded
+    pub reason: ExclusionReason,
+}
+
+/// Role of an excluded account
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum ExcludedRole {
+    /// A nominator
+    Voter,
+    /// A validator candidate
+    Target,
+}
+
+/// Reason an account or nomination was excluded
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "rule", rename_all = "kebab-case")]
+pub enum ExclusionReason {
+    /// Nominator stake is below the minimum nominator bond
+    BelowMinNominatorBond {
+        /// Stake of the nominator
+        stake: u128,
+        /// Required minimum
+        min_bond: u128,
+    },
+    /// Candidate stake is below the minimum validator bond
+    BelowMinValidatorBond {
+        /// Stake of the candidate
+        stake: u128,
+        /// Required minimum
+        min_bond: u128,
+    },
+    /// None of the nominator's targets are eligible
+    NoEligibleTargets,
+    /// A new nomination of a validator that blocks new nominations; the
+    /// voter's other nominations still count
+    BlockedTarget {
+        /// Validator that rejected the nomination
+        validator_id: String,
+    },
+}
+
+impl std::fmt::Display for ExclusionReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExclusionReason::BelowMinNominatorBond { stake, min_bond } => {
+                write!(f, "stake {} is below the minimum nominator bond of {}", stake, min_bond)
+            }
+            ExclusionReason::BelowMinValidatorBond { stake, min_bond } => {
+                write!(f, "stake {} is below the minimum validator bond of {}", stake, min_bond)
+            }
+            ExclusionReason::NoEligibleTargets => write!(f, "none of the nominated targets are eligible"),
+            ExclusionReason::BlockedTarget { validator_id } => {
+                write!(f, "new nomination of {} rejected, the validator is blocked", validator_id)
+            }
+        }
+    }
+}
+
 impl Diagnostics {
     /// Get validator explanations
     pub fn validator_explanations(&self) -> &[ValidatorExplanation] {
@@ -50,6 +120,11 @@ impl Diagnostics {
     pub fn stake_analysis(&self) -> &StakeAnalysis {
         &self.stake_analysis
     }
+
+    /// Get accounts and nominations excluded by eligibility rules
+    pub fn exclusions(&self) -> &[Exclusion] {
+        &self.exclusions
+    }
 }
 
 
@@ -3,14 +3,29 @@
 //! The [`ElectionEngine`] is the main entry point for running election simulations.
 //! It handles algorithm selection, parameter overrides, validation, and result generation.
 
-use crate::algorithms::trait_def::ElectionAlgorithm;
-use crate::algorithms::sequential_phragmen::SequentialPhragmen;
+use crate::algorithms::indexed::{Delta, Index, Solution, Voter};
+use crate::algorithms::{MultiPhase, ParallelPhragmen, SequentialPhragmen};
+use crate::diagnostics::comparison::ResultComparison;
 use crate::diagnostics::explainer::DiagnosticsGenerator;
 use crate::error::ElectionError;
+use crate::models::account_id::AddressFormat;
+use crate::models::chain_profile::ChainProfile;
 use crate::models::election_config::ElectionConfiguration;
 use crate::models::election_data::ElectionData;
-use crate::models::election_result::ElectionResult;
+use crate::models::election_overrides::ElectionOverrides;
+use crate::models::matrix::{MatrixReport, MatrixRun, MatrixSpec};
+use crate::models::scenario::{Scenario, ScenarioOutcome, ScenarioReport};
+use crate::models::sweep::{SweepPoint, SweepReport};
+use crate::models::election_result::{ElectionResult, SelectedValidator, UnassignedReason, UnassignedStake};
+use crate::models::warning::Warning;
 use crate::types::AlgorithmType;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// Outcome of one matrix job with its runtime in milliseconds
+type TimedRun = Result<(ElectionResult, f64), ElectionError>;
 
 /// Election engine for executing elections with various algorithms
 ///
@@ -83,6 +98,190 @@ impl ElectionEngine {
         self.execute_with_diagnostics(config, data, false)
     }
 
+    /// Run a baseline election and the same election under each scenario
+    ///
+    /// `config` is the baseline configuration; each scenario runs with it,
+    /// its own overrides in place of any in `config`, and its own algorithm
+    /// if it sets one. Every scenario is compared against the baseline.
+    pub fn execute_scenarios(
+        &self,
+        config: &ElectionConfiguration,
+        scenarios: &[Scenario],
+        data: &ElectionData,
+    ) -> Result<ScenarioReport, ElectionError> {
+        let baseline = self.execute(config, data)?;
+        let mut outcomes = Vec::with_capacity(scenarios.len());
+        for scenario in scenarios {
+            let mut scenario_config = config.clone().overrides(scenario.overrides.clone());
+            if let Some(algorithm) = scenario.algorithm {
+                scenario_config = scenario_config.algorithm(algorithm);
+            }
+            let result = self.execute(&scenario_config, data).map_err(|e| ElectionError::ValidationError {
+                message: format!("Scenario '{}' failed: {}", scenario.name, e),
+                field: Some("scenarios".to_string()),
+            })?;
+            outcomes.push(ScenarioOutcome {
+                name: scenario.name.clone(),
+                comparison: ResultComparison::new(&baseline, &result),
+                result,
+            });
+        }
+        Ok(ScenarioReport { baseline, scenarios: outcomes })
+    }
+
+    /// Run every algorithm × active set size combination of `spec` on the same data
+    ///
+    /// `config` supplies everything else (overrides, bonds, chain profile);
+    /// its algorithm and active set size, including an active set size
+    /// override, are replaced by each combination's. The data is indexed
+    /// once and runs are spread over a pool of worker threads sharing it;
+    /// each run's overlap is measured against the first algorithm at the
+    /// same active set size.
+    pub fn execute_matrix(
+        &self,
+        config: &ElectionConfiguration,
+        spec: &MatrixSpec,
+        data: &ElectionData,
+    ) -> Result<MatrixReport, ElectionError> {
+        spec.validate()?;
+        let mut base_config = config.clone();
+        if let Some(ref mut overrides) = base_config.overrides {
+            overrides.active_set_size = None;
+        }
+        let (data, format, index) = self.prepare(&base_config, data)?;
+
+        let jobs: Vec<(AlgorithmType, u32)> = spec
+            .active_set_sizes
+            .iter()
+            .flat_map(|&size| spec.algorithms.iter().map(move |&algorithm| (algorithm, size)))
+            .collect();
+        let threads = spec
+            .threads
+            .unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |n| n.get()))
+            .min(jobs.len());
+
+        let next_job = AtomicUsize::new(0);
+        let outcomes: Mutex<Vec<Option<TimedRun>>> = Mutex::new(jobs.iter().map(|_| None).collect());
+        std::thread::scope(|scope| {
+            for _ in 0..threads {
+                scope.spawn(|| loop {
+                    let job = next_job.fetch_add(1, Ordering::Relaxed);
+                    let Some(&(algorithm, size)) = jobs.get(job) else {
+                        break;
+                    };
+                    let run_config = base_config.clone().algorithm(algorithm).active_set_size(size);
+                    let started = Instant::now();
+                    let outcome = self
+                        .run_indexed(&run_config, &data, &index, format, false, None)
+                        .map(|result| (result, started.elapsed().as_secs_f64() * 1000.0));
+                    outcomes.lock().unwrap_or_else(|e| e.into_inner())[job] = Some(outcome);
+                });
+            }
+        });
+
+        let outcomes = outcomes.into_inner().unwrap_or_else(|e| e.into_inner());
+        let mut results = Vec::with_capacity(jobs.len());
+        for (&(algorithm, size), outcome) in jobs.iter().zip(outcomes) {
+            let (result, runtime_ms) = outcome.expect("every matrix job runs").map_err(|e| {
+                ElectionError::ValidationError {
+                    message: format!("{} with active set size {} failed: {}", algorithm, size, e),
+                    field: Some("matrix".to_string()),
+                }
+            })?;
+            results.push((algorithm, size, result, runtime_ms));
+        }
+
+        // Jobs are grouped by size with the reference algorithm first
+        let mut runs = Vec::with_capacity(results.len());
+        for group in results.chunks(spec.algorithms.len()) {
+            let reference: HashSet<&str> = group[0]
+                .2
+                .selected_validators
+                .iter()
+                .map(|v| v.account_id.as_str())
+                .collect();
+            for (algorithm, size, result, runtime_ms) in group {
+                runs.push(MatrixRun {
+                    algorithm: *algorithm,
+                    active_set_size: *size,
+                    selected: result.selected_validators.len() as u32,
+                    score: result.score(),
+                    overlap: result
+                        .selected_validators
+                        .iter()
+                        .filter(|v| reference.contains(v.account_id.as_str()))
+                        .count() as u32,
+                    runtime_ms: *runtime_ms,
+                    warnings: result.warnings.len() as u32,
+                });
+            }
+        }
+
+        Ok(MatrixReport {
+            reference: spec.algorithms[0],
+            runs,
+        })
+    }
+
+    /// Run the configured algorithm at each of the given active set sizes
+    ///
+    /// For sequential Phragmén the largest size runs first and the smaller
+    /// sizes only consider its winners, in election order, which gives the
+    /// same results far more cheaply. Other algorithms run each size in full.
+    /// Every size runs on the same index of the data. An active set size
+    /// override in `config` is ignored.
+    pub fn execute_sweep(
+        &self,
+        config: &ElectionConfiguration,
+        active_set_sizes: &[u32],
+        data: &ElectionData,
+    ) -> Result<SweepReport, ElectionError> {
+        let mut sizes = active_set_sizes.to_vec();
+        sizes.sort_unstable();
+        sizes.dedup();
+        if sizes.is_empty() || sizes[0] == 0 {
+            return Err(ElectionError::ValidationError {
+                message: "Sweep needs at least one active set size, all greater than 0".to_string(),
+                field: Some("active_set_sizes".to_string()),
+            });
+        }
+        let mut base_config = config.clone();
+        if let Some(ref mut overrides) = base_config.overrides {
+            overrides.active_set_size = None;
+        }
+        let (data, format, index) = self.prepare(&base_config, data)?;
+
+        let reuse_winner_order = config.algorithm == AlgorithmType::SequentialPhragmen;
+        let largest = *sizes.last().expect("sizes is not empty");
+        let full_config = base_config.clone().active_set_size(largest);
+        let full = self.run_indexed(&full_config, &data, &index, format, false, None)?;
+        let winner_order: Vec<String> = if reuse_winner_order {
+            let mut winners: Vec<&SelectedValidator> = full.selected_validators.iter().collect();
+            winners.sort_by_key(|v| v.rank);
+            winners.into_iter().map(|v| v.account_id.clone()).collect()
+        } else {
+            Vec::new()
+        };
+
+        let mut points = Vec::with_capacity(sizes.len());
+        for &size in &sizes {
+            let result = if size == largest {
+                full.clone()
+            } else {
+                let size_config = base_config.clone().active_set_size(size);
+                let order = reuse_winner_order.then_some(winner_order.as_slice());
+                self.run_indexed(&size_config, &data, &index, format, false, order)?
+            };
+            points.push(SweepPoint::new(size, &result));
+        }
+
+        Ok(SweepReport {
+            algorithm: config.algorithm,
+            reused_winner_order: reuse_winner_order,
+            points,
+        })
+    }
+
     /// Execute an election with optional diagnostics generation
     ///
     /// Similar to [`execute`](Self::execute), but allows requesting diagnostics
@@ -103,107 +302,279 @@ impl ElectionEngine {
         data: &ElectionData,
         generate_diagnostics: bool,
     ) -> Result<ElectionResult, ElectionError> {
+        self.run(config, data, generate_diagnostics, None)
+    }
+
+    /// Run an election, optionally electing from a known winner order
+    ///
+    /// With `winner_order`, the winners sequential Phragmén elected at a
+    /// larger size, the algorithm only considers the first `active_set_size`
+    /// of them. Sequential Phragmén elects one candidate per round, so this
+    /// gives the same result as considering every candidate.
+    fn run(
+        &self,
+        config: &ElectionConfiguration,
+        data: &ElectionData,
+        generate_diagnostics: bool,
+        winner_order: Option<&[String]>,
+    ) -> Result<ElectionResult, ElectionError> {
+        let (data, format, index) = self.prepare(config, data)?;
+        self.run_indexed(config, &data, &index, format, generate_diagnostics, winner_order)
+    }
+
+    /// Validate and index a copy of `data` for runs with `config`
+    ///
+    /// Batch runs prepare the data once and pass it to
+    /// [`run_indexed`](Self::run_indexed) for each of their configurations.
+    fn prepare(
+        &self,
+        config: &ElectionConfiguration,
+        data: &ElectionData,
+    ) -> Result<(ElectionData, AddressFormat, Index), ElectionError> {
         // Validate election data
         data.validate()?;
 
+        // Write every account the same way so data and overrides can mix
+        // hex and SS58 addresses
+        let format = config
+            .address_format
+            .or_else(|| data.address_format())
+            .unwrap_or(AddressFormat::Hex);
+        let mut data = data.clone();
+        data.normalize_account_ids(format)?;
+        let index = Index::new(&data)?;
+        Ok((data, format, index))
+    }
+
+    /// Run an election on indexed data
+    ///
+    /// `data` must be valid, with account IDs written in `format`, and
+    /// `index` must be its index. The configuration's overrides are applied
+    /// as a delta on top of the index, so the data itself is never changed.
+    pub(crate) fn run_indexed(
+        &self,
+        config: &ElectionConfiguration,
+        data: &ElectionData,
+        index: &Index,
+        format: AddressFormat,
+        generate_diagnostics: bool,
+        winner_order: Option<&[String]>,
+    ) -> Result<ElectionResult, ElectionError> {
+        let mut warnings = Warnings::new(config.strict);
+        self.check_completeness(config, data, &mut warnings)?;
+
+        // Apply overrides if present
+        let overrides = match config.overrides {
+            Some(ref overrides) => {
+                let mut overrides = overrides.clone();
+                overrides.normalize_account_ids(format)?;
+                overrides
+            }
+            None => ElectionOverrides::new(),
+        };
+        let delta = Delta::new(data, index, &overrides, &mut warnings)?;
+        let requested_size = overrides.active_set_size.unwrap_or(config.active_set_size);
+
+        let min_validator_bond = self.min_validator_bond(config, data, &mut warnings)?;
+        let (mut election, exclusions) = delta.eligible(config, min_validator_bond)?;
+        if !exclusions.is_empty() {
+            warnings.push(Warning::IneligibleExcluded { count: exclusions.len() })?;
+        }
+
         // Auto-adjust active set size if there are fewer candidates available
-        let candidate_count = data.candidates().len();
-        let effective_active_set_size = if config.active_set_size as usize > candidate_count {
-            eprintln!(
-                "Warning: Requested {} validators but only {} candidates available. Using {} instead.",
-                config.active_set_size,
-                candidate_count,
-                candidate_count
-            );
+        let candidate_count = election.candidate_count();
+        let effective_active_set_size = if requested_size as usize > candidate_count {
+            warnings.push(Warning::ActiveSetReduced {
+                requested: requested_size,
+                available: candidate_count as u32,
+            })?;
             candidate_count as u32
         } else {
-            config.active_set_size
+            requested_size
         };
 
+        let profile = config.chain_profile.as_ref();
+        self.check_max_winners(profile, effective_active_set_size)?;
+
         // Create a modified config with the adjusted active set size
         let mut adjusted_config = config.clone();
         adjusted_config.active_set_size = effective_active_set_size;
 
-        // Select algorithm based on configuration
-        let algorithm: Box<dyn ElectionAlgorithm> = match config.algorithm {
-            AlgorithmType::SequentialPhragmen => Box::new(SequentialPhragmen),
-            AlgorithmType::ParallelPhragmen => Box::new(crate::algorithms::parallel_phragmen::ParallelPhragmen),
-            AlgorithmType::MultiPhase => Box::new(crate::algorithms::multi_phase::MultiPhase),
-        };
+        if let Some(profile) = profile {
+            election.apply_chain_limits(profile, &mut warnings)?;
+        }
+        let diagnostics_data = generate_diagnostics.then(|| election.to_data());
 
-        // Apply overrides if present
-        let mut modified_data = data.clone();
-        if let Some(ref overrides) = config.overrides {
-            self.apply_overrides(&mut modified_data, overrides)?;
+        // Weighted edges vote on their own; their allocations still name
+        // their nominators
+        election.split_weighted_edges(delta.weights());
+        let prefix = winner_order
+            .filter(|order| order.len() >= effective_active_set_size as usize)
+            .map(|order| &order[..effective_active_set_size as usize]);
+        if let Some(prefix) = prefix.filter(|_| config.algorithm == AlgorithmType::SequentialPhragmen) {
+            election.restrict_candidates(prefix);
         }
 
-        // Execute algorithm with adjusted config
-        let result = algorithm.execute(&modified_data, &adjusted_config)?;
+        // Select algorithm based on configuration
+        let solve: fn(usize, Vec<u32>, Vec<Voter>) -> Result<Solution, ElectionError> = match config.algorithm {
+            AlgorithmType::SequentialPhragmen => SequentialPhragmen::solve,
+            AlgorithmType::ParallelPhragmen => ParallelPhragmen::solve,
+            AlgorithmType::MultiPhase => MultiPhase::solve,
+        };
+        election.check_solver_stakes(&mut warnings)?;
+        let mut result = election.elect(&adjusted_config, config.algorithm, solve)?;
+        if let Some(max_backers) = profile.and_then(|p| p.max_backers_per_winner) {
+            self.trim_backers(&mut result, max_backers, &mut warnings)?;
+        }
 
         // Validate result against adjusted config
         self.validate_result(&result, &adjusted_config)?;
 
         // Generate diagnostics if requested
-        let result = if generate_diagnostics {
-            let diagnostics_gen = DiagnosticsGenerator::new();
-            match diagnostics_gen.generate(&result, &modified_data) {
-                Ok(diagnostics) => result.with_diagnostics(diagnostics),
-                Err(e) => {
-                    // Log error but don't fail the election
-                    eprintln!("Warning: Failed to generate diagnostics: {}", e);
-                    result
+        let mut result = match diagnostics_data {
+            Some(diagnostics_data) => {
+                let diagnostics_gen = DiagnosticsGenerator::new();
+                match diagnostics_gen.generate(&result, &diagnostics_data) {
+                    Ok(mut diagnostics) => {
+                        diagnostics.exclusions = exclusions;
+                        result.with_diagnostics(diagnostics)
+                    }
+                    Err(e) => {
+                        // Don't fail the election unless in strict mode
+                        warnings.push(Warning::DiagnosticsFailed { message: e.to_string() })?;
+                        result
+                    }
                 }
             }
-        } else {
-            result
+            None => result,
         };
 
+        result.warnings = warnings.into_inner();
         Ok(result)
     }
 
-    /// Apply parameter overrides to election data
-    fn apply_overrides(
+    /// Refuse an active set larger than the chain's `MaxWinners`
+    fn check_max_winners(
         &self,
-        data: &mut ElectionData,
-        overrides: &crate::models::election_overrides::ElectionOverrides,
+        profile: Option<&ChainProfile>,
+        active_set_size: u32,
     ) -> Result<(), ElectionError> {
-        // Apply candidate stake overrides
-        for (account_id, stake) in &overrides.candidate_stakes {
-            if let Some(candidate) = data.candidates.iter_mut().find(|c| c.account_id == *account_id) {
-                candidate.stake = *stake;
-            }
+        let Some(profile) = profile else {
+            return Ok(());
+        };
+        match profile.max_winners {
+            Some(max_winners) if active_set_size > max_winners => Err(ElectionError::ValidationError {
+                message: format!(
+                    "Active set size {} exceeds the maximum of {} winners on {}",
+                    active_set_size, max_winners, profile.name
+                ),
+                field: Some("active_set_size".to_string()),
+            }),
+            _ => Ok(()),
         }
+    }
 
-        // Apply nominator stake overrides
-        for (account_id, stake) in &overrides.nominator_stakes {
-            if let Some(nominator) = data.nominators.iter_mut().find(|n| n.account_id == *account_id) {
-                nominator.stake = *stake;
+    /// Refuse (in strict mode) or warn about snapshots known to be incomplete
+    fn check_completeness(
+        &self,
+        config: &ElectionConfiguration,
+        data: &ElectionData,
+        warnings: &mut Warnings,
+    ) -> Result<(), ElectionError> {
+        let Some(completeness) = data.metadata.as_ref().and_then(|m| m.completeness.as_ref()) else {
+            return Ok(());
+        };
+        if completeness.is_complete() {
+            return Ok(());
+        }
+
+        let problems = completeness.problems();
+        if config.strict_snapshot {
+            return Err(ElectionError::ValidationError {
+                message: format!(
+                    "Election data snapshot is incomplete: {}. Refusing to run in strict mode.",
+                    problems.join(", ")
+                ),
+                field: Some("metadata.completeness".to_string()),
+            });
+        }
+        warnings.push(Warning::IncompleteSnapshot { problems })
+    }
+
+    /// Minimum validator bond to apply to `data`
+    ///
+    /// Candidate stakes left at zero by a chain loader are not compared with
+    /// the bond; the bond is skipped with a warning instead.
+    fn min_validator_bond(
+        &self,
+        config: &ElectionConfiguration,
+        data: &ElectionData,
+        warnings: &mut Warnings,
+    ) -> Result<Option<u128>, ElectionError> {
+        match config.min_validator_bond {
+            Some(min_bond) if !data.candidate_stakes_known() => {
+                warnings.push(Warning::ValidatorBondUnchecked { min_bond })?;
+                Ok(None)
             }
+            min_bond => Ok(min_bond),
         }
+    }
 
-        // Apply voting edge modifications
-        for edge_mod in &overrides.voting_edges {
-            match edge_mod.action {
-                crate::models::election_overrides::EdgeAction::Add => {
-                    if let Some(nominator) = data.nominators.iter_mut().find(|n| n.account_id == edge_mod.nominator_id) {
-                        nominator.add_target(edge_mod.candidate_id.clone());
-                    }
-                }
-                crate::models::election_overrides::EdgeAction::Remove => {
-                    if let Some(nominator) = data.nominators.iter_mut().find(|n| n.account_id == edge_mod.nominator_id) {
-                        nominator.remove_target(&edge_mod.candidate_id);
-                    }
-                }
-                crate::models::election_overrides::EdgeAction::Modify => {
-                    // Modify is similar to remove + add
-                    if let Some(nominator) = data.nominators.iter_mut().find(|n| n.account_id == edge_mod.nominator_id) {
-                        nominator.remove_target(&edge_mod.candidate_id);
-                        nominator.add_target(edge_mod.candidate_id.clone());
-                    }
-                }
+    /// Keep only the `max_backers` largest backers of each winner
+    ///
+    /// Mirrors the chain's trimming of supports to `MaxBackersPerWinner`. The
+    /// trimmed stake no longer backs anyone, so it moves from the winner's
+    /// backing to the result's unassigned stake.
+    fn trim_backers(
+        &self,
+        result: &mut ElectionResult,
+        max_backers: u32,
+        warnings: &mut Warnings,
+    ) -> Result<(), ElectionError> {
+        let max_backers = max_backers as usize;
+        let mut trimmed = std::collections::HashSet::new();
+        let mut trimmed_stake = 0u128;
+        let mut backers_by_validator: HashMap<&str, Vec<usize>> = HashMap::new();
+        for (index, allocation) in result.stake_distribution.iter().enumerate() {
+            backers_by_validator.entry(&allocation.validator_id).or_default().push(index);
+        }
+        for validator in &mut result.selected_validators {
+            let Some(mut backers) = backers_by_validator.remove(validator.account_id.as_str()) else {
+                continue;
+            };
+            if backers.len() <= max_backers {
+                continue;
+            }
+
+            backers.sort_by(|&a, &b| result.stake_distribution[b].amount.cmp(&result.stake_distribution[a].amount));
+            for &index in &backers[max_backers..] {
+                let allocation = &result.stake_distribution[index];
+                validator.total_backing_stake = validator.total_backing_stake.saturating_sub(allocation.amount);
+                result.accounting.assigned_stake = result.accounting.assigned_stake.saturating_sub(allocation.amount);
+                result.accounting.unassigned_stake += allocation.amount;
+                result.accounting.unassigned.push(UnassignedStake {
+                    nominator_id: allocation.nominator_id.clone(),
+                    amount: allocation.amount,
+                    reason: UnassignedReason::BackerTrimmed { validator_id: validator.account_id.clone() },
+                });
+                trimmed_stake = trimmed_stake.saturating_add(allocation.amount);
+                trimmed.insert(index);
             }
+            validator.nominator_count = max_backers as u32;
         }
 
+        if !trimmed.is_empty() {
+            warnings.push(Warning::BackersTrimmed {
+                allocations: trimmed.len(),
+                amount: trimmed_stake,
+                max_backers: max_backers as u32,
+            })?;
+            let mut index = 0;
+            result.stake_distribution.retain(|_| {
+                index += 1;
+                !trimmed.contains(&(index - 1))
+            });
+        }
         Ok(())
     }
 
@@ -225,20 +596,91 @@ impl ElectionEngine {
             });
         }
 
-        // Check that total stake matches
+        // Check that every unit of stake is accounted for exactly
+        let accounting = &result.accounting;
         let total_allocated: u128 = result.stake_distribution.iter().map(|a| a.amount).sum();
-        if total_allocated != result.total_stake {
+        if total_allocated != accounting.assigned_stake {
             return Err(ElectionError::ValidationError {
                 message: format!(
-                    "Stake distribution total {} doesn't match total stake {}",
-                    total_allocated, result.total_stake
+                    "Stake distribution total {} doesn't match assigned stake {}",
+                    total_allocated, accounting.assigned_stake
                 ),
                 field: Some("stake_distribution".to_string()),
             });
         }
+        let total_unassigned: u128 = accounting.unassigned.iter().map(|u| u.amount).sum();
+        if total_unassigned != accounting.unassigned_stake {
+            return Err(ElectionError::ValidationError {
+                message: format!(
+                    "Unassigned stake entries total {} but unassigned stake is {}",
+                    total_unassigned, accounting.unassigned_stake
+                ),
+                field: Some("accounting.unassigned".to_string()),
+            });
+        }
+        let accounted = accounting
+            .assigned_stake
+            .checked_add(accounting.unassigned_stake)
+            .and_then(|sum| sum.checked_add(accounting.rounding_residue));
+        if accounted != Some(result.total_stake) {
+            return Err(ElectionError::ValidationError {
+                message: format!(
+                    "Total stake {} doesn't equal assigned {} + unassigned {} + rounding residue {}",
+                    result.total_stake,
+                    accounting.assigned_stake,
+                    accounting.unassigned_stake,
+                    accounting.rounding_residue
+                ),
+                field: Some("total_stake".to_string()),
+            });
+        }
+
+        // Check that backing is exactly what was allocated to each validator
+        let mut allocated_by_validator: HashMap<&str, u128> = HashMap::new();
+        for allocation in &result.stake_distribution {
+            *allocated_by_validator.entry(&allocation.validator_id).or_default() += allocation.amount;
+        }
+        for validator in &result.selected_validators {
+            let allocated = allocated_by_validator.get(validator.account_id.as_str()).copied().unwrap_or(0);
+            if allocated != validator.total_backing_stake {
+                return Err(ElectionError::ValidationError {
+                    message: format!(
+                        "Validator {} has backing {} but {} allocated",
+                        validator.account_id, validator.total_backing_stake, allocated
+                    ),
+                    field: Some("selected_validators".to_string()),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
 
+/// Warnings collected during one election
+///
+/// In strict mode the first warning is returned as an error instead.
+pub(crate) struct Warnings {
+    strict: bool,
+    warnings: Vec<Warning>,
+}
+
+impl Warnings {
+    pub(crate) fn new(strict: bool) -> Self {
+        Self { strict, warnings: Vec::new() }
+    }
+
+    pub(crate) fn push(&mut self, warning: Warning) -> Result<(), ElectionError> {
+        if self.strict {
+            return Err(warning.into_error());
+        }
+        self.warnings.push(warning);
         Ok(())
     }
+
+    pub(crate) fn into_inner(self) -> Vec<Warning> {
+        self.warnings
+    }
 }
 
 impl Default for ElectionEngine {
@@ -0,0 +1,501 @@
+//! Binary snapshot format for election data
+//!
+//! A snapshot is the magic bytes [`SNAPSHOT_MAGIC`], a SCALE-encoded
+//! [`SnapshotHeader`], and the SCALE-encoded election data, optionally
+//! zstd-compressed. Nominations are stored as candidate positions rather
+//! than account IDs, and accounts written as addresses are stored as their
+//! raw 32 bytes, which keeps snapshots small and fast to decode.
+
+use crate::error::ElectionError;
+use crate::models::account_id::{AccountId, AddressFormat};
+use crate::models::election_data::{ElectionData, ElectionMetadata, SnapshotCompleteness};
+use crate::models::nominator::{NominationPool, Nominator, NominatorMetadata, PoolMember};
+use crate::models::validator::{CandidateMetadata, ValidatorCandidate};
+use blake2::digest::consts::U32;
+use blake2::{Blake2b, Digest};
+use parity_scale_codec::{Compact, Decode, DecodeAll, Encode, IoReader};
+use std::collections::HashMap;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Bytes every binary snapshot starts with
+pub const SNAPSHOT_MAGIC: [u8; 8] = *b"NPOSSNAP";
+
+/// Snapshot format version written by [`BinaryWriter`]
+pub const SNAPSHOT_VERSION: u16 = 2;
+
+/// Header of a binary snapshot
+#[derive(Debug, Clone, PartialEq, Eq, Encode)]
+pub struct SnapshotHeader {
+    /// Format version
+    pub version: u16,
+    /// Chain the data was loaded from, if known
+    pub chain: Option<String>,
+    /// Block the data was loaded at, if known
+    pub block_number: Option<u64>,
+    /// Whether the payload is zstd-compressed
+    pub compressed: bool,
+    /// Blake2b-256 hash of the uncompressed payload
+    pub checksum: [u8; 32],
+}
+
+/// Loader for binary election data snapshots
+pub struct BinaryLoader;
+
+impl BinaryLoader {
+    /// Create a new binary loader
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Whether the file at `path` starts with the snapshot magic bytes
+    pub fn is_snapshot_file(path: &Path) -> Result<bool, ElectionError> {
+        let file = std::fs::File::open(path).map_err(|e| ElectionError::FileError {
+            message: format!("Failed to read file: {}", e),
+            path: path.to_path_buf(),
+        })?;
+        let mut magic = Vec::with_capacity(SNAPSHOT_MAGIC.len());
+        file.take(SNAPSHOT_MAGIC.len() as u64)
+            .read_to_end(&mut magic)
+            .map_err(|e| ElectionError::FileError {
+                message: format!("Failed to read file: {}", e),
+                path: path.to_path_buf(),
+            })?;
+        Ok(magic == SNAPSHOT_MAGIC)
+    }
+
+    /// Load election data from a binary snapshot file
+    pub fn load_from_file(&self, path: PathBuf) -> Result<ElectionData, ElectionError> {
+        let file = std::fs::File::open(&path).map_err(|e| ElectionError::FileError {
+            message: format!("Failed to read file: {}", e),
+            path: path.clone(),
+        })?;
+        let data = decode(BufReader::new(file)).map_err(|message| ElectionError::FileError {
+            message,
+            path: path.clone(),
+        })?;
+
+        // Validate the loaded data
+        data.validate()?;
+
+        Ok(data)
+    }
+
+    /// Load election data from a binary snapshot read from `reader`
+    pub fn load_from_reader(&self, reader: impl Read) -> Result<ElectionData, ElectionError> {
+        let data = decode(BufReader::new(reader)).map_err(|message| ElectionError::InvalidData { message })?;
+
+        // Validate the loaded data
+        data.validate()?;
+
+        Ok(data)
+    }
+
+    /// Read only the header of a binary snapshot
+    pub fn read_header(&self, reader: impl Read) -> Result<SnapshotHeader, ElectionError> {
+        read_header(&mut BufReader::new(reader)).map_err(|message| ElectionError::InvalidData { message })
+    }
+}
+
+impl Default for BinaryLoader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Writer for binary election data snapshots
+#[derive(Debug, Clone, Default)]
+pub struct BinaryWriter {
+    compress: bool,
+}
+
+impl BinaryWriter {
+    /// Create a writer for uncompressed snapshots
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Compress the payload with zstd
+    pub fn compressed(mut self, compress: bool) -> Self {
+        self.compress = compress;
+        self
+    }
+
+    /// Write `data` as a binary snapshot file
+    pub fn write_to_file(&self, data: &ElectionData, path: &Path) -> Result<(), ElectionError> {
+        let write_error = |e: std::io::Error| ElectionError::FileError {
+            message: format!("Failed to write snapshot file: {}", e),
+            path: path.to_path_buf(),
+        };
+        let file = std::fs::File::create(path).map_err(write_error)?;
+        let mut writer = BufWriter::new(file);
+        self.write(data, &mut writer)?;
+        writer.flush().map_err(write_error)
+    }
+
+    /// Write `data` as a binary snapshot to `writer`
+    ///
+    /// Nominations of accounts that are not candidates can't be stored, so
+    /// the data should be valid. A nomination written differently from its
+    /// candidate, for example hex against SS58, is read back written like
+    /// the candidate.
+    pub fn write(&self, data: &ElectionData, mut writer: impl Write) -> Result<(), ElectionError> {
+        let payload = WireData::new(data)?.encode();
+        let header = SnapshotHeader {
+            version: SNAPSHOT_VERSION,
+            chain: data.metadata.as_ref().and_then(|m| m.chain.clone()),
+            block_number: data.metadata.as_ref().and_then(|m| m.block_number),
+            compressed: self.compress,
+            checksum: checksum(&payload),
+        };
+
+        let write_error = |e: std::io::Error| ElectionError::InvalidData {
+            message: format!("Failed to write binary snapshot: {}", e),
+        };
+        writer.write_all(&SNAPSHOT_MAGIC).map_err(write_error)?;
+        writer.write_all(&header.encode()).map_err(write_error)?;
+        if self.compress {
+            zstd::stream::copy_encode(payload.as_slice(), &mut writer, 0).map_err(write_error)?;
+        } else {
+            writer.write_all(&payload).map_err(write_error)?;
+        }
+        Ok(())
+    }
+}
+
+fn checksum(payload: &[u8]) -> [u8; 32] {
+    Blake2b::<U32>::digest(payload).into()
+}
+
+fn read_header(reader: &mut impl Read) -> Result<SnapshotHeader, String> {
+    let mut magic = [0u8; SNAPSHOT_MAGIC.len()];
+    reader
+        .read_exact(&mut magic)
+        .map_err(|e| format!("Failed to read binary snapshot: {}", e))?;
+    if magic != SNAPSHOT_MAGIC {
+        return Err("Not a binary election data snapshot: missing magic bytes".to_string());
+    }
+
+    // The version comes first so later versions may change the rest of the header
+    let mut input = IoReader(reader);
+    let version = u16::decode(&mut input).map_err(|e| format!("Failed to decode snapshot header: {}", e))?;
+    if version != SNAPSHOT_VERSION {
+        return Err(format!(
+            "Unsupported binary snapshot version {}; this build reads version {}",
+            version, SNAPSHOT_VERSION
+        ));
+    }
+    let (chain, block_number, compressed, checksum) =
+        Decode::decode(&mut input).map_err(|e| format!("Failed to decode snapshot header: {}", e))?;
+    Ok(SnapshotHeader {
+        version,
+        chain,
+        block_number,
+        compressed,
+        checksum,
+    })
+}
+
+fn decode(mut reader: impl Read) -> Result<ElectionData, String> {
+    let header = read_header(&mut reader)?;
+    let mut payload = Vec::new();
+    if header.compressed {
+        zstd::stream::copy_decode(reader, &mut payload)
+            .map_err(|e| format!("Failed to decompress binary snapshot: {}", e))?;
+    } else {
+        reader
+            .read_to_end(&mut payload)
+            .map_err(|e| format!("Failed to read binary snapshot: {}", e))?;
+    }
+    if checksum(&payload) != header.checksum {
+        return Err("Binary snapshot checksum mismatch: the file is corrupt".to_string());
+    }
+
+    let wire = WireData::decode_all(&mut payload.as_slice())
+        .map_err(|e| format!("Failed to decode binary snapshot: {}", e))?;
+    wire.into_data()
+}
+
+/// SCALE layout of [`ElectionData`]
+#[derive(Encode, Decode)]
+struct WireData {
+    candidates: Vec<WireCandidate>,
+    nominators: Vec<WireNominator>,
+    metadata: Option<WireMetadata>,
+}
+
+/// Account ID: raw bytes for addresses, the string itself for labels
+///
+/// Addresses are only stored as bytes if they render back to the same
+/// string, so every ID reads back exactly as it was written.
+#[derive(Encode, Decode)]
+enum WireAccount {
+    Hex([u8; 32]),
+    Ss58([u8; 32], u16),
+    Label(String),
+}
+
+impl WireAccount {
+    fn new(id: &str) -> Self {
+        match AccountId::parse_address(id) {
+            Ok(Some((account, format))) if format.render(&account) == id => match format {
+                AddressFormat::Hex => WireAccount::Hex(*account.as_bytes()),
+                AddressFormat::Ss58(prefix) => WireAccount::Ss58(*account.as_bytes(), prefix),
+            },
+            _ => WireAccount::Label(id.to_string()),
+        }
+    }
+
+    fn into_id(self) -> String {
+        match self {
+            WireAccount::Hex(bytes) => AccountId::new(bytes).to_hex(),
+            WireAccount::Ss58(bytes, prefix) => AccountId::new(bytes).to_ss58(prefix),
+            WireAccount::Label(label) => label,
+        }
+    }
+}
+
+#[derive(Encode, Decode)]
+struct WireCandidate {
+    account_id: WireAccount,
+    stake: Compact<u128>,
+    metadata: Option<WireCandidateMetadata>,
+}
+
+#[derive(Encode, Decode)]
+struct WireCandidateMetadata {
+    commission_rate: Option<u8>,
+    on_chain_status: Option<String>,
+    blocked: bool,
+}
+
+#[derive(Encode, Decode)]
+struct WireNominator {
+    account_id: WireAccount,
+    stake: Compact<u128>,
+    /// Positions of the nominated candidates
+    targets: Vec<Compact<u32>>,
+    metadata: Option<WireNominatorMetadata>,
+}
+
+#[derive(Encode, Decode)]
+struct WireNominatorMetadata {
+    pool: Option<WirePool>,
+    /// Extra fields by name, with JSON values
+    extra: Vec<(String, String)>,
+}
+
+#[derive(Encode, Decode)]
+struct WirePool {
+    pool_id: u32,
+    members: Vec<(WireAccount, Compact<u128>)>,
+}
+
+#[derive(Encode, Decode)]
+struct WireMetadata {
+    block_number: Option<u64>,
+    chain: Option<String>,
+    era: Option<u32>,
+    completeness: Option<WireCompleteness>,
+    candidate_stakes_unknown: bool,
+}
+
+#[derive(Encode, Decode)]
+struct WireCompleteness {
+    expected_validators: Option<u32>,
+    loaded_validators: u32,
+    expected_nominators: Option<u32>,
+    loaded_nominators: u32,
+    undecodable_keys: Vec<String>,
+}
+
+impl WireData {
+    fn new(data: &ElectionData) -> Result<Self, ElectionError> {
+        let positions: HashMap<&str, u32> = data
+            .candidates
+            .iter()
+            .enumerate()
+            .map(|(position, candidate)| (candidate.account_id.as_str(), position as u32))
+            .collect();
+        // Only built if some nomination is written differently from its candidate
+        let mut canonical_positions: Option<HashMap<String, u32>> = None;
+
+        let mut nominators = Vec::with_capacity(data.nominators.len());
+        for nominator in &data.nominators {
+            let mut targets = Vec::with_capacity(nominator.targets.len());
+            for target in &nominator.targets {
+                let position = match positions.get(target.as_str()) {
+                    Some(&position) => Some(position),
+                    None => {
+                        let canonical_positions = canonical_positions.get_or_insert_with(|| {
+                            data.candidates
+                                .iter()
+                                .enumerate()
+                                .filter_map(|(position, c)| {
+                                    AccountId::canonical(&c.account_id).ok().map(|id| (id, position as u32))
+                                })
+                                .collect()
+                        });
+                        AccountId::canonical(target)
+                            .ok()
+                            .and_then(|id| canonical_positions.get(&id).copied())
+                    }
+                };
+                let position = position.ok_or_else(|| ElectionError::ValidationError {
+                    message: format!(
+                        "Nominator '{}' votes for non-existent candidate '{}'",
+                        nominator.account_id, target
+                    ),
+                    field: Some("nominators.targets".to_string()),
+                })?;
+                targets.push(Compact(position));
+            }
+
+            let metadata = match nominator.metadata {
+                Some(ref metadata) => {
+                    let mut extra = metadata
+                        .extra
+                        .iter()
+                        .map(|(key, value)| Ok((key.clone(), serde_json::to_string(value)?)))
+                        .collect::<Result<Vec<_>, serde_json::Error>>()
+                        .map_err(|e| ElectionError::InvalidData {
+                            message: format!("Failed to encode nominator metadata: {}", e),
+                        })?;
+                    extra.sort();
+                    Some(WireNominatorMetadata {
+                        pool: metadata.pool.as_ref().map(|pool| WirePool {
+                            pool_id: pool.pool_id,
+                            members: pool
+                                .members
+                                .iter()
+                                .map(|member| (WireAccount::new(&member.account_id), Compact(member.points)))
+                                .collect(),
+                        }),
+                        extra,
+                    })
+                }
+                None => None,
+            };
+            nominators.push(WireNominator {
+                account_id: WireAccount::new(&nominator.account_id),
+                stake: Compact(nominator.stake),
+                targets,
+                metadata,
+            });
+        }
+
+        Ok(Self {
+            candidates: data
+                .candidates
+                .iter()
+                .map(|candidate| WireCandidate {
+                    account_id: WireAccount::new(&candidate.account_id),
+                    stake: Compact(candidate.stake),
+                    metadata: candidate.metadata.as_ref().map(|metadata| WireCandidateMetadata {
+                        commission_rate: metadata.commission_rate,
+                        on_chain_status: metadata.on_chain_status.clone(),
+                        blocked: metadata.blocked,
+                    }),
+                })
+                .collect(),
+            nominators,
+            metadata: data.metadata.as_ref().map(|metadata| WireMetadata {
+                block_number: metadata.block_number,
+                chain: metadata.chain.clone(),
+                era: metadata.era,
+                completeness: metadata.completeness.as_ref().map(|completeness| WireCompleteness {
+                    expected_validators: completeness.expected_validators,
+                    loaded_validators: completeness.loaded_validators,
+                    expected_nominators: completeness.expected_nominators,
+                    loaded_nominators: completeness.loaded_nominators,
+                    undecodable_keys: completeness.undecodable_keys.clone(),
+                }),
+                candidate_stakes_unknown: metadata.candidate_stakes_unknown,
+            }),
+        })
+    }
+
+    fn into_data(self) -> Result<ElectionData, String> {
+        let candidates: Vec<ValidatorCandidate> = self
+            .candidates
+            .into_iter()
+            .map(|candidate| ValidatorCandidate {
+                account_id: candidate.account_id.into_id(),
+                stake: candidate.stake.0,
+                metadata: candidate.metadata.map(|metadata| CandidateMetadata {
+                    commission_rate: metadata.commission_rate,
+                    on_chain_status: metadata.on_chain_status,
+                    blocked: metadata.blocked,
+                }),
+            })
+            .collect();
+
+        let mut nominators = Vec::with_capacity(self.nominators.len());
+        for nominator in self.nominators {
+            let account_id = nominator.account_id.into_id();
+            let targets = nominator
+                .targets
+                .iter()
+                .map(|&Compact(position)| {
+                    candidates
+                        .get(position as usize)
+                        .map(|candidate| candidate.account_id.clone())
+                        .ok_or_else(|| {
+                            format!(
+                                "Nominator '{}' votes for candidate #{}, but there are only {} candidates",
+                                account_id,
+                                position,
+                                candidates.len()
+                            )
+                        })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let metadata = match nominator.metadata {
+                Some(metadata) => Some(NominatorMetadata {
+                    pool: metadata.pool.map(|pool| NominationPool {
+                        pool_id: pool.pool_id,
+                        members: pool
+                            .members
+                            .into_iter()
+                            .map(|(account_id, points)| PoolMember {
+                                account_id: account_id.into_id(),
+                                points: points.0,
+                            })
+                            .collect(),
+                    }),
+                    extra: metadata
+                        .extra
+                        .into_iter()
+                        .map(|(key, value)| Ok((key, serde_json::from_str(&value)?)))
+                        .collect::<Result<_, serde_json::Error>>()
+                        .map_err(|e| format!("Failed to decode nominator metadata: {}", e))?,
+                }),
+                None => None,
+            };
+            nominators.push(Nominator {
+                account_id,
+                stake: nominator.stake.0,
+                targets,
+                metadata,
+            });
+        }
+
+        Ok(ElectionData {
+            candidates,
+            nominators,
+            metadata: self.metadata.map(|metadata| ElectionMetadata {
+                block_number: metadata.block_number,
+                chain: metadata.chain,
+                era: metadata.era,
+                completeness: metadata.completeness.map(|completeness| SnapshotCompleteness {
+                    expected_validators: completeness.expected_validators,
+                    loaded_validators: completeness.loaded_validators,
+                    expected_nominators: completeness.expected_nominators,
+                    loaded_nominators: completeness.loaded_nominators,
+                    undecodable_keys: completeness.undecodable_keys,
+                }),
+                candidate_stakes_unknown: metadata.candidate_stakes_unknown,
+            }),
+        })
+    }
+}
@@ -0,0 +1,406 @@
+//! On-disk cache of raw chain storage, keyed by block hash
+//!
+//! State at a given block hash is immutable, so raw storage key listings and
+//! values fetched from an RPC endpoint can be reused across runs. The cache
+//! is laid out as one directory per block hash:
+//!
+//! ```text
+//! <cache_dir>/
+//!   0x<block_hash>/
+//!     keys-<prefix>.json   key listing for a storage prefix (may be partial)
+//!     values.jsonl         append-only log of fetched storage values
+//! ```
+//!
+//! Key listings record whether they are complete, so an interrupted paged
+//! fetch can resume from the last stored key. Values are appended one line at
+//! a time; a truncated trailing line left by an interrupted run is ignored,
+//! and cut off before the next value is appended.
+
+use crate::error::ElectionError;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// File name of the append-only value log inside a block directory
+const VALUES_FILE: &str = "values.jsonl";
+
+/// Storage key listing for a prefix at a block
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyListing {
+    /// Storage keys found so far, in the order returned by the node
+    pub keys: Vec<String>,
+    /// Whether every key under the prefix has been listed
+    pub complete: bool,
+}
+
+impl KeyListing {
+    /// Last key in the listing, used as the start key to resume paging
+    pub fn last_key(&self) -> Option<&String> {
+        self.keys.last()
+    }
+}
+
+/// One line of the value log
+#[derive(Debug, Serialize, Deserialize)]
+struct ValueRecord {
+    /// Storage key
+    k: String,
+    /// Hex-encoded storage value, `None` if the key has no value
+    v: Option<String>,
+}
+
+/// Summary of the cached data for a single block hash
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheEntrySummary {
+    /// Block hash the data belongs to
+    pub block_hash: String,
+    /// Key listings as (prefix, key count, complete)
+    pub key_listings: Vec<(String, usize, bool)>,
+    /// Number of cached storage values
+    pub value_count: usize,
+    /// Total size on disk in bytes
+    pub size_bytes: u64,
+}
+
+/// Values loaded for a block, plus the open log used to append new ones
+struct BlockValues {
+    values: HashMap<String, Option<String>>,
+    log: Option<File>,
+}
+
+/// On-disk content cache for raw storage fetched over RPC
+pub struct StorageCache {
+    root: PathBuf,
+    blocks: Mutex<HashMap<String, BlockValues>>,
+}
+
+impl StorageCache {
+    /// Create a cache rooted at the given directory
+    ///
+    /// The directory is created if it does not exist.
+    pub fn new(root: impl Into<PathBuf>) -> Result<Self, ElectionError> {
+        let root = root.into();
+        std::fs::create_dir_all(&root).map_err(|e| ElectionError::FileError {
+            message: format!("Failed to create cache directory: {}", e),
+            path: root.clone(),
+        })?;
+
+        Ok(Self {
+            root,
+            blocks: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Default cache directory
+    ///
+    /// Uses `$XDG_CACHE_HOME/offline-election`, falling back to
+    /// `$HOME/.cache/offline-election` and finally the system temp directory.
+    pub fn default_dir() -> PathBuf {
+        if let Some(dir) = std::env::var_os("XDG_CACHE_HOME").filter(|d| !d.is_empty()) {
+            return PathBuf::from(dir).join("offline-election");
+        }
+        if let Some(home) = std::env::var_os("HOME").filter(|h| !h.is_empty()) {
+            return PathBuf::from(home).join(".cache").join("offline-election");
+        }
+        std::env::temp_dir().join("offline-election-cache")
+    }
+
+    /// Root directory of the cache
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Get the cached key listing for a prefix, complete or partial
+    pub fn key_listing(&self, block_hash: &str, prefix: &str) -> Result<Option<KeyListing>, ElectionError> {
+        let path = self.key_listing_path(block_hash, prefix)?;
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = std::fs::read_to_string(&path).map_err(|e| ElectionError::FileError {
+            message: format!("Failed to read cached key listing: {}", e),
+            path: path.clone(),
+        })?;
+
+        // A listing that cannot be parsed is treated as missing and refetched
+        Ok(serde_json::from_str(&content).ok())
+    }
+
+    /// Store a key listing for a prefix, replacing any previous listing
+    ///
+    /// The listing is written to a temporary file and renamed into place so an
+    /// interrupted write never leaves a corrupt listing behind.
+    pub fn store_key_listing(
+        &self,
+        block_hash: &str,
+        prefix: &str,
+        listing: &KeyListing,
+    ) -> Result<(), ElectionError> {
+        let path = self.key_listing_path(block_hash, prefix)?;
+        self.ensure_block_dir(block_hash)?;
+
+        let content = serde_json::to_string(listing).map_err(|e| ElectionError::InvalidData {
+            message: format!("Failed to serialize key listing: {}", e),
+        })?;
+
+        let tmp_path = path.with_extension("json.tmp");
+        std::fs::write(&tmp_path, content).map_err(|e| ElectionError::FileError {
+            message: format!("Failed to write cached key listing: {}", e),
+            path: tmp_path.clone(),
+        })?;
+        std::fs::rename(&tmp_path, &path).map_err(|e| ElectionError::FileError {
+            message: format!("Failed to write cached key listing: {}", e),
+            path: path.clone(),
+        })
+    }
+
+    /// Get a cached storage value
+    ///
+    /// Returns `None` on a cache miss and `Some(None)` if the key is cached as
+    /// having no value.
+    pub fn value(&self, block_hash: &str, key: &str) -> Result<Option<Option<String>>, ElectionError> {
+        let mut blocks = self.blocks.lock().unwrap_or_else(|e| e.into_inner());
+        let block = self.block_values(&mut blocks, block_hash)?;
+        Ok(block.values.get(key).cloned())
+    }
+
+    /// Store a storage value
+    pub fn store_value(&self, block_hash: &str, key: &str, value: Option<&str>) -> Result<(), ElectionError> {
+        let mut blocks = self.blocks.lock().unwrap_or_else(|e| e.into_inner());
+        let log_path = self.block_dir(block_hash)?.join(VALUES_FILE);
+        let block = self.block_values(&mut blocks, block_hash)?;
+
+        if block.values.contains_key(key) {
+            return Ok(());
+        }
+
+        let record = ValueRecord {
+            k: key.to_string(),
+            v: value.map(str::to_string),
+        };
+        let mut line = serde_json::to_string(&record).map_err(|e| ElectionError::InvalidData {
+            message: format!("Failed to serialize cached value: {}", e),
+        })?;
+        line.push('\n');
+
+        if block.log.is_none() {
+            self.ensure_block_dir(block_hash)?;
+            let open_error = |e: std::io::Error| ElectionError::FileError {
+                message: format!("Failed to open cached value log: {}", e),
+                path: log_path.clone(),
+            };
+            let mut log = OpenOptions::new()
+                .create(true)
+                .read(true)
+                .append(true)
+                .open(&log_path)
+                .map_err(open_error)?;
+            truncate_to_last_record(&mut log).map_err(open_error)?;
+            block.log = Some(log);
+        }
+        let log = block.log.as_mut().expect("value log was opened above");
+        log.write_all(line.as_bytes()).map_err(|e| ElectionError::FileError {
+            message: format!("Failed to append cached value: {}", e),
+            path: log_path,
+        })?;
+        block.values.insert(record.k, record.v);
+
+        Ok(())
+    }
+
+    /// Summarize every block hash present in the cache
+    pub fn inspect(&self) -> Result<Vec<CacheEntrySummary>, ElectionError> {
+        let mut summaries = Vec::new();
+
+        for block_dir in self.block_dirs()? {
+            let block_hash = match block_dir.file_name().and_then(|n| n.to_str()) {
+                Some(name) => name.to_string(),
+                None => continue,
+            };
+
+            let mut key_listings = Vec::new();
+            let mut value_count = 0;
+            let mut size_bytes = 0;
+
+            let entries = std::fs::read_dir(&block_dir).map_err(|e| ElectionError::FileError {
+                message: format!("Failed to read cache directory: {}", e),
+                path: block_dir.clone(),
+            })?;
+            for entry in entries.flatten() {
+                let path = entry.path();
+                size_bytes += entry.metadata().map(|m| m.len()).unwrap_or(0);
+                let name = entry.file_name().to_string_lossy().to_string();
+
+                if name == VALUES_FILE {
+                    value_count = read_value_log(&path)?.len();
+                } else if let Some(prefix) = name.strip_prefix("keys-").and_then(|n| n.strip_suffix(".json")) {
+                    if let Some(listing) = self.key_listing(&block_hash, &format!("0x{}", prefix))? {
+                        key_listings.push((format!("0x{}", prefix), listing.keys.len(), listing.complete));
+                    }
+                }
+            }
+            key_listings.sort();
+
+            summaries.push(CacheEntrySummary {
+                block_hash,
+                key_listings,
+                value_count,
+                size_bytes,
+            });
+        }
+
+        summaries.sort_by(|a, b| a.block_hash.cmp(&b.block_hash));
+        Ok(summaries)
+    }
+
+    /// Remove cached data for a single block hash
+    ///
+    /// Returns `true` if anything was removed.
+    pub fn purge_block(&self, block_hash: &str) -> Result<bool, ElectionError> {
+        let dir = self.block_dir(block_hash)?;
+        self.blocks
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&normalize_block_hash(block_hash)?);
+
+        if !dir.exists() {
+            return Ok(false);
+        }
+        std::fs::remove_dir_all(&dir).map_err(|e| ElectionError::FileError {
+            message: format!("Failed to remove cache directory: {}", e),
+            path: dir.clone(),
+        })?;
+        Ok(true)
+    }
+
+    /// Remove all cached data
+    ///
+    /// Returns the number of block hashes removed.
+    pub fn purge_all(&self) -> Result<usize, ElectionError> {
+        self.blocks.lock().unwrap_or_else(|e| e.into_inner()).clear();
+
+        let dirs = self.block_dirs()?;
+        for dir in &dirs {
+            std::fs::remove_dir_all(dir).map_err(|e| ElectionError::FileError {
+                message: format!("Failed to remove cache directory: {}", e),
+                path: dir.clone(),
+            })?;
+        }
+        Ok(dirs.len())
+    }
+
+    /// Directories of all cached block hashes
+    fn block_dirs(&self) -> Result<Vec<PathBuf>, ElectionError> {
+        let entries = std::fs::read_dir(&self.root).map_err(|e| ElectionError::FileError {
+            message: format!("Failed to read cache directory: {}", e),
+            path: self.root.clone(),
+        })?;
+
+        Ok(entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.is_dir()
+                    && path
+                        .file_name()
+                        .and_then(|n| n.to_str())
+                        .map(|n| normalize_block_hash(n).is_ok())
+                        .unwrap_or(false)
+            })
+            .collect())
+    }
+
+    /// Load the cached values for a block on first access
+    fn block_values<'a>(
+        &self,
+        blocks: &'a mut HashMap<String, BlockValues>,
+        block_hash: &str,
+    ) -> Result<&'a mut BlockValues, ElectionError> {
+        let normalized = normalize_block_hash(block_hash)?;
+        if !blocks.contains_key(&normalized) {
+            let path = self.block_dir(block_hash)?.join(VALUES_FILE);
+            let values = if path.exists() { read_value_log(&path)? } else { HashMap::new() };
+            blocks.insert(normalized.clone(), BlockValues { values, log: None });
+        }
+
+        Ok(blocks.get_mut(&normalized).expect("block values were inserted above"))
+    }
+
+    fn block_dir(&self, block_hash: &str) -> Result<PathBuf, ElectionError> {
+        Ok(self.root.join(normalize_block_hash(block_hash)?))
+    }
+
+    fn ensure_block_dir(&self, block_hash: &str) -> Result<(), ElectionError> {
+        let dir = self.block_dir(block_hash)?;
+        std::fs::create_dir_all(&dir).map_err(|e| ElectionError::FileError {
+            message: format!("Failed to create cache directory: {}", e),
+            path: dir.clone(),
+        })
+    }
+
+    fn key_listing_path(&self, block_hash: &str, prefix: &str) -> Result<PathBuf, ElectionError> {
+        let prefix = normalize_hex(prefix).ok_or_else(|| ElectionError::InvalidData {
+            message: format!("Invalid storage prefix for cache: {}", prefix),
+        })?;
+        Ok(self.block_dir(block_hash)?.join(format!("keys-{}.json", prefix)))
+    }
+}
+
+/// Read every complete record from a value log, skipping a truncated tail
+fn read_value_log(path: &Path) -> Result<HashMap<String, Option<String>>, ElectionError> {
+    let file = File::open(path).map_err(|e| ElectionError::FileError {
+        message: format!("Failed to open cached value log: {}", e),
+        path: path.to_path_buf(),
+    })?;
+
+    let mut values = HashMap::new();
+    for line in BufReader::new(file).lines() {
+        let Ok(line) = line else { break };
+        if let Ok(record) = serde_json::from_str::<ValueRecord>(&line) {
+            values.insert(record.k, record.v);
+        }
+    }
+    Ok(values)
+}
+
+/// Cut a value log back to the end of its last complete line
+///
+/// Otherwise the next record would be appended to a truncated line left by an
+/// interrupted run, and be skipped along with it on every later load.
+fn truncate_to_last_record(log: &mut File) -> std::io::Result<()> {
+    let mut end = log.seek(SeekFrom::End(0))?;
+    let mut chunk = [0u8; 4096];
+    while end > 0 {
+        let start = end.saturating_sub(chunk.len() as u64);
+        let chunk = &mut chunk[..(end - start) as usize];
+        log.seek(SeekFrom::Start(start))?;
+        log.read_exact(chunk)?;
+        if let Some(newline) = chunk.iter().rposition(|&byte| byte == b'\n') {
+            return log.set_len(start + newline as u64 + 1);
+        }
+        end = start;
+    }
+    log.set_len(0)
+}
+
+/// Normalize a block hash to `0x`-prefixed lowercase hex
+///
+/// Block hashes are used as directory names, so anything other than hex is
+/// rejected.
+fn normalize_block_hash(block_hash: &str) -> Result<String, ElectionError> {
+    normalize_hex(block_hash)
+        .filter(|hex| !hex.is_empty())
+        .map(|hex| format!("0x{}", hex))
+        .ok_or_else(|| ElectionError::InvalidData {
+            message: format!("Invalid block hash for cache: {}", block_hash),
+        })
+}
+
+/// Lowercase hex digits of a value with any `0x` prefix removed
+fn normalize_hex(value: &str) -> Option<String> {
+    let hex = value.trim_start_matches("0x").to_lowercase();
+    hex.chars().all(|c| c.is_ascii_hexdigit()).then_some(hex)
+}
@@ -0,0 +1,148 @@
+//! Reading a [`ChainProfile`] from a live chain
+//!
+//! Election limits are runtime constants, found in the runtime metadata
+//! returned by `state_getMetadata`. Minimum bonds are `Staking` storage items,
+//! and the address format and token come from `system_properties`. Anything
+//! the chain does not expose is taken from the preset matching the chain name,
+//! or from [`ChainProfile::new`] for unknown chains.
+
+use crate::error::ElectionError;
+use crate::input::rpc::RpcLoader;
+use crate::models::chain_profile::ChainProfile;
+use frame_metadata::{RuntimeMetadata, RuntimeMetadataPrefixed};
+use parity_scale_codec::Decode;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Pallet constants by `(pallet, constant)` name, SCALE-encoded
+type Constants = HashMap<(String, String), Vec<u8>>;
+
+/// Collect all pallet constants from SCALE-encoded runtime metadata
+fn decode_constants(bytes: &[u8]) -> Result<Constants, String> {
+    let metadata = RuntimeMetadataPrefixed::decode(&mut &bytes[..]).map_err(|e| e.to_string())?;
+    let mut constants = HashMap::new();
+    macro_rules! collect {
+        ($metadata:expr) => {
+            for pallet in $metadata.pallets {
+                for constant in pallet.constants {
+                    constants.insert((pallet.name.clone(), constant.name), constant.value);
+                }
+            }
+        };
+    }
+    match metadata.1 {
+        RuntimeMetadata::V14(metadata) => collect!(metadata),
+        RuntimeMetadata::V15(metadata) => collect!(metadata),
+        RuntimeMetadata::V16(metadata) => collect!(metadata),
+        other => return Err(format!("unsupported metadata version {}", other.version())),
+    }
+    Ok(constants)
+}
+
+/// First of the given `u32` constants present in the metadata
+fn constant_u32(constants: &Constants, candidates: &[(&str, &str)]) -> Option<u32> {
+    candidates.iter().find_map(|(pallet, name)| {
+        let value = constants.get(&(pallet.to_string(), name.to_string()))?;
+        u32::decode(&mut value.as_slice()).ok()
+    })
+}
+
+/// A `system_properties` entry, which may be a value or a list of values
+fn property<'a>(properties: &'a Value, name: &str) -> Option<&'a Value> {
+    match properties.get(name)? {
+        Value::Array(values) => values.first(),
+        value => Some(value),
+    }
+}
+
+/// Override profile limits with the constants the runtime declares
+fn apply_constants(profile: &mut ChainProfile, constants: &Constants) {
+    if let Some(max) = constant_u32(constants, &[("Staking", "MaxNominations")]) {
+        profile.max_nominations = max;
+    }
+    if let Some(max) = constant_u32(
+        constants,
+        &[("ElectionProviderMultiPhase", "MaxWinners"), ("MultiBlockElectionVerifier", "MaxWinnersPerPage")],
+    ) {
+        profile.max_winners = Some(max);
+    }
+    if let Some(max) = constant_u32(
+        constants,
+        &[
+            ("ElectionProviderMultiPhase", "MaxBackersPerWinner"),
+            ("MultiBlockElectionVerifier", "MaxBackersPerWinner"),
+        ],
+    ) {
+        profile.max_backers_per_winner = Some(max);
+    }
+
+    // Multi-block elections snapshot a fixed number of voters per page
+    let paged_voters = constant_u32(constants, &[("MultiBlockElection", "VoterSnapshotPerBlock")])
+        .zip(constant_u32(constants, &[("MultiBlockElection", "Pages")]))
+        .map(|(per_page, pages)| per_page.saturating_mul(pages));
+    if let Some(max) = constant_u32(constants, &[("ElectionProviderMultiPhase", "MaxElectingVoters")]).or(paged_voters)
+    {
+        profile.max_electing_voters = Some(max);
+    }
+}
+
+impl RpcLoader {
+    /// Read the chain's network parameters
+    ///
+    /// Constants are read from the runtime metadata and bonds from storage at
+    /// the latest block. Values the chain does not expose are taken from the
+    /// preset for the chain name, if there is one.
+    pub async fn load_chain_profile(&self) -> Result<ChainProfile, ElectionError> {
+        let chain_name = self.chain_name().await.unwrap_or("Unknown").to_string();
+        let mut profile = ChainProfile::for_chain(&chain_name).unwrap_or_else(|| ChainProfile::new(&chain_name));
+        profile.name = chain_name;
+
+        match self.request_value("state_getMetadata").await {
+            Ok(Value::String(hex)) => {
+                let constants = self
+                    .decode_storage_hex(&hex)
+                    .and_then(|bytes| {
+                        decode_constants(&bytes).map_err(|e| ElectionError::RpcError {
+                            message: format!("Failed to decode runtime metadata: {}", e),
+                            url: self.url().to_string(),
+                        })
+                    })?;
+                apply_constants(&mut profile, &constants);
+            }
+            Ok(_) => eprintln!("  ⚠ Runtime metadata is not a hex string, using preset limits"),
+            Err(e) => eprintln!("  ⚠ Could not fetch runtime metadata, using preset limits: {}", e),
+        }
+
+        let block_hash = self.get_block_hash(self.get_latest_block_number().await?).await?;
+        let bond_keys = [
+            self.encode_storage_key("Staking", "MinNominatorBond")?,
+            self.encode_storage_key("Staking", "MinValidatorBond")?,
+        ];
+        let mut bonds = self.get_storage_values(&bond_keys, &block_hash).await?;
+        let mut read_bond = |key: &String| -> Option<u128> {
+            let bytes = self.decode_storage_hex(&bonds.remove(key).flatten()?).ok()?;
+            u128::decode(&mut bytes.as_slice()).ok()
+        };
+        if let Some(bond) = read_bond(&bond_keys[0]) {
+            profile.min_nominator_bond = bond;
+        }
+        if let Some(bond) = read_bond(&bond_keys[1]) {
+            profile.min_validator_bond = bond;
+        }
+
+        if let Ok(properties) = self.request_value("system_properties").await {
+            if let Some(prefix) = property(&properties, "ss58Format").and_then(Value::as_u64) {
+                profile.ss58_prefix = prefix as u16;
+            }
+            if let Some(decimals) = property(&properties, "tokenDecimals").and_then(Value::as_u64) {
+                profile.token_decimals = decimals as u8;
+            }
+            if let Some(symbol) = property(&properties, "tokenSymbol").and_then(Value::as_str) {
+                profile.token_symbol = symbol.to_string();
+            }
+        }
+
+        eprintln!("  ✓ Chain profile: {}", profile.name);
+        Ok(profile)
+    }
+}
@@ -0,0 +1,429 @@
+//! CSV loader and writer for election data
+//!
+//! Election data can be read from three files in one directory:
+//!
+//! - `candidates.csv` - `account_id,stake`, plus the optional columns
+//!   `commission_rate`, `on_chain_status` and `blocked`
+//! - `nominators.csv` - `account_id,stake`
+//! - `edges.csv` - `nominator_id,validator_id`, one row per nomination
+//!
+//! or from one long-format file with the columns `type,account_id,stake,validator_id`,
+//! where `type` is `candidate`, `nominator` or `edge`. Edge rows give the
+//! nominator as `account_id` and leave `stake` empty; candidate rows may also
+//! carry the optional candidate columns. Nominations keep the order of their
+//! edge rows. Nomination pool metadata has no CSV representation.
+
+use crate::error::ElectionError;
+use crate::models::election_data::ElectionData;
+use crate::models::nominator::Nominator;
+use crate::models::validator::{CandidateMetadata, ValidatorCandidate};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Candidate file of the three-file layout
+pub const CANDIDATES_FILE: &str = "candidates.csv";
+/// Nominator file of the three-file layout
+pub const NOMINATORS_FILE: &str = "nominators.csv";
+/// Nomination edge file of the three-file layout
+pub const EDGES_FILE: &str = "edges.csv";
+
+/// Row of `candidates.csv`
+#[derive(Deserialize)]
+struct CandidateRecord {
+    account_id: String,
+    stake: u128,
+    #[serde(default)]
+    commission_rate: Option<u8>,
+    #[serde(default)]
+    on_chain_status: Option<String>,
+    #[serde(default)]
+    blocked: Option<bool>,
+}
+
+/// Row of `nominators.csv`
+#[derive(Deserialize)]
+struct NominatorRecord {
+    account_id: String,
+    stake: u128,
+}
+
+/// Row of `edges.csv`
+#[derive(Deserialize)]
+struct EdgeRecord {
+    nominator_id: String,
+    validator_id: String,
+}
+
+/// Row of a long-format file
+#[derive(Deserialize)]
+struct LongRecord {
+    #[serde(rename = "type")]
+    kind: String,
+    account_id: String,
+    #[serde(default)]
+    stake: Option<u128>,
+    #[serde(default)]
+    validator_id: Option<String>,
+    #[serde(default)]
+    commission_rate: Option<u8>,
+    #[serde(default)]
+    on_chain_status: Option<String>,
+    #[serde(default)]
+    blocked: Option<bool>,
+}
+
+/// Edge with the line it was read from
+struct Edge {
+    line: u64,
+    nominator_id: String,
+    validator_id: String,
+}
+
+/// Loader for election data in CSV files
+pub struct CsvLoader;
+
+impl CsvLoader {
+    /// Create a new CSV loader
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Whether `path` is CSV input: a directory or a file with a `.csv` extension
+    pub fn is_csv_path(path: &Path) -> bool {
+        path.is_dir()
+            || path
+                .extension()
+                .is_some_and(|extension| extension.eq_ignore_ascii_case("csv"))
+    }
+
+    /// Load election data from `candidates.csv`, `nominators.csv` and `edges.csv` in `dir`
+    pub fn load_from_dir(&self, dir: PathBuf) -> Result<ElectionData, ElectionError> {
+        self.load_from_files(dir.join(CANDIDATES_FILE), dir.join(NOMINATORS_FILE), dir.join(EDGES_FILE))
+    }
+
+    /// Load election data from separate candidate, nominator and edge files
+    pub fn load_from_files(
+        &self,
+        candidates: PathBuf,
+        nominators: PathBuf,
+        edges: PathBuf,
+    ) -> Result<ElectionData, ElectionError> {
+        let candidates = read_file(&candidates)?;
+        let nominators = read_file(&nominators)?;
+        let edges = read_file(&edges)?;
+        build(candidate_rows(candidates), nominator_rows(nominators), edge_rows(edges))
+    }
+
+    /// Load election data from candidate, nominator and edge CSV read from three readers
+    pub fn load_from_readers(
+        &self,
+        candidates: impl Read,
+        nominators: impl Read,
+        edges: impl Read,
+    ) -> Result<ElectionData, ElectionError> {
+        build(
+            candidate_rows(read_records(candidates).map_err(invalid_data)?),
+            nominator_rows(read_records(nominators).map_err(invalid_data)?),
+            edge_rows(read_records(edges).map_err(invalid_data)?),
+        )
+    }
+
+    /// Load election data from a long-format CSV file
+    pub fn load_from_file(&self, path: PathBuf) -> Result<ElectionData, ElectionError> {
+        let records = read_file(&path)?;
+        let (candidates, nominators, edges) = split_long(records).map_err(|message| ElectionError::FileError {
+            message,
+            path: path.clone(),
+        })?;
+        build(candidates, nominators, edges)
+    }
+
+    /// Load election data from long-format CSV read from `reader`
+    pub fn load_from_reader(&self, reader: impl Read) -> Result<ElectionData, ElectionError> {
+        let records = read_records(reader).map_err(invalid_data)?;
+        let (candidates, nominators, edges) = split_long(records).map_err(invalid_data)?;
+        build(candidates, nominators, edges)
+    }
+}
+
+impl Default for CsvLoader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Writer for election data in the three-file CSV layout
+///
+/// Candidate metadata columns are written only when a candidate has that
+/// metadata, so data without any stays in the plain `account_id,stake` form.
+pub struct CsvWriter;
+
+impl CsvWriter {
+    /// Create a new CSV writer
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Write `candidates.csv`, `nominators.csv` and `edges.csv` to `dir`, creating it if needed
+    pub fn write_to_dir(&self, data: &ElectionData, dir: &Path) -> Result<(), ElectionError> {
+        std::fs::create_dir_all(dir).map_err(|e| ElectionError::FileError {
+            message: format!("Failed to create output directory: {}", e),
+            path: dir.to_path_buf(),
+        })?;
+        write_file(&dir.join(CANDIDATES_FILE), |file| self.write_candidates(data, file))?;
+        write_file(&dir.join(NOMINATORS_FILE), |file| self.write_nominators(data, file))?;
+        write_file(&dir.join(EDGES_FILE), |file| self.write_edges(data, file))
+    }
+
+    /// Write the candidates of `data` as CSV to `writer`
+    pub fn write_candidates(&self, data: &ElectionData, writer: impl Write) -> Result<(), ElectionError> {
+        let metadata: Vec<&CandidateMetadata> = data.candidates.iter().filter_map(|c| c.metadata.as_ref()).collect();
+        let commission = metadata.iter().any(|m| m.commission_rate.is_some());
+        let status = metadata.iter().any(|m| m.on_chain_status.is_some());
+        let blocked = metadata.iter().any(|m| m.blocked);
+
+        let mut header = vec!["account_id", "stake"];
+        header.extend(commission.then_some("commission_rate"));
+        header.extend(status.then_some("on_chain_status"));
+        header.extend(blocked.then_some("blocked"));
+        let mut rows = Vec::with_capacity(data.candidates.len());
+        for candidate in &data.candidates {
+            let metadata = candidate.metadata.clone().unwrap_or_default();
+            let mut row = vec![candidate.account_id.clone(), candidate.stake.to_string()];
+            if commission {
+                row.push(metadata.commission_rate.map(|rate| rate.to_string()).unwrap_or_default());
+            }
+            if status {
+                row.push(metadata.on_chain_status.unwrap_or_default());
+            }
+            if blocked {
+                row.push(metadata.blocked.to_string());
+            }
+            rows.push(row);
+        }
+        write_records(writer, &header, rows)
+    }
+
+    /// Write the nominators of `data` as CSV to `writer`
+    pub fn write_nominators(&self, data: &ElectionData, writer: impl Write) -> Result<(), ElectionError> {
+        let rows = data
+            .nominators
+            .iter()
+            .map(|nominator| vec![nominator.account_id.clone(), nominator.stake.to_string()]);
+        write_records(writer, &["account_id", "stake"], rows)
+    }
+
+    /// Write the nominations of `data` as CSV edges to `writer`
+    pub fn write_edges(&self, data: &ElectionData, writer: impl Write) -> Result<(), ElectionError> {
+        let rows = data.nominators.iter().flat_map(|nominator| {
+            nominator
+                .targets
+                .iter()
+                .map(|target| vec![nominator.account_id.clone(), target.clone()])
+        });
+        write_records(writer, &["nominator_id", "validator_id"], rows)
+    }
+}
+
+impl Default for CsvWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Write `header` and `rows` as CSV to `writer`
+pub(crate) fn write_records<R, F>(
+    writer: impl Write,
+    header: &[&str],
+    rows: impl IntoIterator<Item = R>,
+) -> Result<(), ElectionError>
+where
+    R: IntoIterator<Item = F>,
+    F: AsRef<[u8]>,
+{
+    let write_error = |e: ::csv::Error| ElectionError::InvalidData {
+        message: format!("Failed to write CSV: {}", e),
+    };
+    let mut writer = ::csv::Writer::from_writer(writer);
+    writer.write_record(header).map_err(write_error)?;
+    for row in rows {
+        writer.write_record(row).map_err(write_error)?;
+    }
+    writer.flush().map_err(|e| ElectionError::InvalidData {
+        message: format!("Failed to write CSV: {}", e),
+    })
+}
+
+/// Create the file at `path` and write to it through a buffer
+fn write_file(
+    path: &Path,
+    write: impl FnOnce(&mut std::io::BufWriter<std::fs::File>) -> Result<(), ElectionError>,
+) -> Result<(), ElectionError> {
+    let file = std::fs::File::create(path).map_err(|e| ElectionError::FileError {
+        message: format!("Failed to write output file: {}", e),
+        path: path.to_path_buf(),
+    })?;
+    write(&mut std::io::BufWriter::new(file))
+}
+
+/// Read every record of the CSV file at `path` with the line it starts on
+fn read_file<T: DeserializeOwned>(path: &Path) -> Result<Vec<(u64, T)>, ElectionError> {
+    let file = std::fs::File::open(path).map_err(|e| ElectionError::FileError {
+        message: format!("Failed to read file: {}", e),
+        path: path.to_path_buf(),
+    })?;
+    read_records(file).map_err(|message| ElectionError::FileError {
+        message,
+        path: path.to_path_buf(),
+    })
+}
+
+/// Read every record of the CSV read from `reader` with the line it starts on
+fn read_records<T: DeserializeOwned>(reader: impl Read) -> Result<Vec<(u64, T)>, String> {
+    let mut reader = ::csv::ReaderBuilder::new().trim(::csv::Trim::All).from_reader(reader);
+    let headers = reader
+        .headers()
+        .map_err(|e| format!("Failed to parse CSV: {}", e))?
+        .clone();
+    let mut record = ::csv::StringRecord::new();
+    let mut records = Vec::new();
+    while reader
+        .read_record(&mut record)
+        .map_err(|e| format!("Failed to parse CSV: {}", e))?
+    {
+        let line = record.position().map_or(0, |position| position.line());
+        let value = record
+            .deserialize(Some(&headers))
+            .map_err(|e| format!("Failed to parse CSV on line {}: {}", line, e))?;
+        records.push((line, value));
+    }
+    Ok(records)
+}
+
+fn invalid_data(message: String) -> ElectionError {
+    ElectionError::InvalidData { message }
+}
+
+fn candidate_rows(records: Vec<(u64, CandidateRecord)>) -> Vec<ValidatorCandidate> {
+    records
+        .into_iter()
+        .map(|(_, record)| candidate(record.account_id, record.stake, record.commission_rate, record.on_chain_status, record.blocked))
+        .collect()
+}
+
+fn nominator_rows(records: Vec<(u64, NominatorRecord)>) -> Vec<Nominator> {
+    records
+        .into_iter()
+        .map(|(_, record)| Nominator::new(record.account_id, record.stake))
+        .collect()
+}
+
+fn edge_rows(records: Vec<(u64, EdgeRecord)>) -> Vec<Edge> {
+    records
+        .into_iter()
+        .map(|(line, record)| Edge {
+            line,
+            nominator_id: record.nominator_id,
+            validator_id: record.validator_id,
+        })
+        .collect()
+}
+
+/// Candidate with metadata only when one of the optional columns is set and not `false`
+fn candidate(
+    account_id: String,
+    stake: u128,
+    commission_rate: Option<u8>,
+    on_chain_status: Option<String>,
+    blocked: Option<bool>,
+) -> ValidatorCandidate {
+    let blocked = blocked.unwrap_or(false);
+    if commission_rate.is_none() && on_chain_status.is_none() && !blocked {
+        return ValidatorCandidate::new(account_id, stake);
+    }
+    ValidatorCandidate::with_metadata(
+        account_id,
+        stake,
+        CandidateMetadata {
+            commission_rate,
+            on_chain_status,
+            blocked,
+        },
+    )
+}
+
+/// Candidates, nominators and edges read from CSV
+type Rows = (Vec<ValidatorCandidate>, Vec<Nominator>, Vec<Edge>);
+
+/// Split long-format records into candidates, nominators and edges
+fn split_long(records: Vec<(u64, LongRecord)>) -> Result<Rows, String> {
+    let mut candidates = Vec::new();
+    let mut nominators = Vec::new();
+    let mut edges = Vec::new();
+    for (line, record) in records {
+        let stake = |kind: &str| record.stake.ok_or_else(|| format!("Missing stake for {} on line {}", kind, line));
+        match record.kind.to_ascii_lowercase().as_str() {
+            "candidate" => candidates.push(candidate(
+                record.account_id.clone(),
+                stake("candidate")?,
+                record.commission_rate,
+                record.on_chain_status,
+                record.blocked,
+            )),
+            "nominator" => nominators.push(Nominator::new(record.account_id.clone(), stake("nominator")?)),
+            "edge" => edges.push(Edge {
+                line,
+                validator_id: record
+                    .validator_id
+                    .ok_or_else(|| format!("Missing validator_id for edge on line {}", line))?,
+                nominator_id: record.account_id,
+            }),
+            other => {
+                return Err(format!(
+                    "Unknown record type '{}' on line {} (expected candidate, nominator or edge)",
+                    other, line
+                ))
+            }
+        }
+    }
+    Ok((candidates, nominators, edges))
+}
+
+/// Attach edges to their nominators and validate the data
+fn build(
+    candidates: Vec<ValidatorCandidate>,
+    mut nominators: Vec<Nominator>,
+    edges: Vec<Edge>,
+) -> Result<ElectionData, ElectionError> {
+    let positions: HashMap<String, usize> = nominators
+        .iter()
+        .enumerate()
+        .map(|(position, nominator)| (nominator.account_id.clone(), position))
+        .collect();
+    for edge in edges {
+        let Some(&position) = positions.get(&edge.nominator_id) else {
+            return Err(ElectionError::ValidationError {
+                message: format!(
+                    "Edge on line {} is from unknown nominator '{}'",
+                    edge.line, edge.nominator_id
+                ),
+                field: Some("edges".to_string()),
+            });
+        };
+        nominators[position].targets.push(edge.validator_id);
+    }
+
+    let data = ElectionData {
+        candidates,
+        nominators,
+        metadata: None,
+    };
+
+    // Validate the loaded data
+    data.validate()?;
+
+    Ok(data)
+}
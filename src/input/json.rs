@@ -2,9 +2,14 @@
 
 use crate::error::ElectionError;
 use crate::models::election_data::ElectionData;
+use crate::schema::json_pointer;
+use std::io::{BufReader, Read};
 use std::path::PathBuf;
 
 /// JSON loader for loading election data from files
+///
+/// Candidates and nominators are deserialized straight from the input as it
+/// is read, so the raw JSON is never held in memory alongside the data.
 pub struct JsonLoader;
 
 impl JsonLoader {
@@ -15,14 +20,35 @@ impl JsonLoader {
 
     /// Load election data from a JSON file
     pub fn load_from_file(&self, path: PathBuf) -> Result<ElectionData, ElectionError> {
-        let content = std::fs::read_to_string(&path).map_err(|e| ElectionError::FileError {
+        let file = std::fs::File::open(&path).map_err(|e| ElectionError::FileError {
             message: format!("Failed to read file: {}", e),
             path: path.clone(),
         })?;
 
-        let data: ElectionData = serde_json::from_str(&content).map_err(|e| ElectionError::FileError {
-            message: format!("Failed to parse JSON: {}", e),
-            path: path.clone(),
+        let data = parse(BufReader::new(file)).map_err(|e| {
+            let message = if e.error.is_io() {
+                format!("Failed to read file: {}", e.error)
+            } else {
+                e.to_string()
+            };
+            ElectionError::FileError {
+                message,
+                path: path.clone(),
+            }
+        })?;
+
+        // Validate the loaded data
+        data.validate()?;
+
+        Ok(data)
+    }
+
+    /// Load election data from JSON read from `reader`
+    ///
+    /// Reads are buffered, so any reader can be passed directly.
+    pub fn load_from_reader(&self, reader: impl Read) -> Result<ElectionData, ElectionError> {
+        let data = parse(BufReader::new(reader)).map_err(|e| ElectionError::InvalidData {
+            message: e.to_string(),
         })?;
 
         // Validate the loaded data
@@ -37,3 +63,33 @@ impl Default for JsonLoader {
         Self::new()
     }
 }
+
+/// JSON parse error with the JSON pointer of the value being read
+struct ParseError {
+    pointer: String,
+    error: serde_json::Error,
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.pointer.is_empty() {
+            write!(f, "Failed to parse JSON: {}", self.error)
+        } else {
+            write!(f, "Failed to parse JSON at {}: {}", self.pointer, self.error)
+        }
+    }
+}
+
+/// Deserialize election data, tracking the path to the value being read
+fn parse(reader: impl Read) -> Result<ElectionData, ParseError> {
+    let mut deserializer = serde_json::Deserializer::from_reader(reader);
+    let data = serde_path_to_error::deserialize(&mut deserializer).map_err(|e| ParseError {
+        pointer: json_pointer(e.path()),
+        error: e.into_inner(),
+    })?;
+    deserializer.end().map_err(|error| ParseError {
+        pointer: String::new(),
+        error,
+    })?;
+    Ok(data)
+}
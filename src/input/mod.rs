@@ -3,7 +3,10 @@
 //! This module provides loaders for election data from different sources:
 //!
 //! - [`RpcLoader`] - Load data from Substrate RPC endpoints
+//! - [`StorageCache`] - Cache raw RPC storage on disk, keyed by block hash
 //! - [`JsonLoader`] - Load data from JSON files
+//! - [`BinaryLoader`] / [`BinaryWriter`] - Read and write compact binary snapshots
+//! - [`CsvLoader`] / [`CsvWriter`] - Read and write election data as CSV for spreadsheets
 //! - [`SyntheticDataBuilder`] - Create synthetic election data programmatically
 //!
 //! # Examples
@@ -20,6 +23,23 @@
 //! # }
 //! ```
 //!
+//! ## Following finalized blocks
+//!
+//! ```no_run
+//! use futures::StreamExt;
+//! use offline_election::input::RpcLoader;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let loader = RpcLoader::new("wss://rpc.polkadot.io")?;
+//! let mut heads = std::pin::pin!(loader.subscribe_finalized_heads().await?);
+//! while let Some(head) = heads.next().await {
+//!     let head = head?;
+//!     println!("Finalized block {} ({})", head.number, head.hash);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
 //! ## Loading from JSON
 //!
 //! ```no_run
@@ -48,7 +68,13 @@
 //! # }
 //! ```
 
+pub mod binary;
+pub mod cache;
+pub mod csv;
 pub mod rpc;
+mod chain_constants;
+mod nomination_pools;
+mod staking_async;
 pub mod json;
 pub mod synthetic;
 
@@ -56,17 +82,50 @@ pub mod synthetic;
 ///
 /// Connects to a Substrate RPC endpoint and fetches validator candidates,
 /// nominators, and stake information at a specific block.
-pub use rpc::RpcLoader;
+pub use rpc::{FinalizedHead, RpcLoader, StakingProfile};
+
+/// On-disk cache of raw storage fetched over RPC
+///
+/// Stores storage key listings and values per block hash so repeated
+/// fetches against the same block are served from disk.
+pub use cache::StorageCache;
 
 /// JSON loader for reading election data from files
 ///
 /// Loads election data from JSON files that match the `ElectionData` schema.
 pub use json::JsonLoader;
 
+/// Loader and writer for binary election data snapshots
+///
+/// Snapshots are SCALE-encoded with a versioned, checksummed header and are
+/// optionally zstd-compressed.
+pub use binary::{BinaryLoader, BinaryWriter, SnapshotHeader};
+
+/// Loader and writer for election data in CSV files
+///
+/// Reads candidates, nominators and nomination edges from three files in a
+/// directory or from one long-format file.
+pub use self::csv::{CsvLoader, CsvWriter};
+
 /// Builder for creating synthetic election data
 ///
 /// Allows creating election data with arbitrary account IDs that don't
 /// need to exist on-chain. Useful for testing and "what-if" scenarios.
 pub use synthetic::SyntheticDataBuilder;
 
-
+/// Load election data from a JSON file, a binary snapshot or CSV
+///
+/// A directory is read as the three-file CSV layout. Files are read as
+/// binary snapshots when they start with the snapshot magic bytes, as
+/// long-format CSV when they have a `.csv` extension, and as JSON otherwise.
+pub fn load_data_file(path: std::path::PathBuf) -> Result<crate::models::ElectionData, crate::error::ElectionError> {
+    if path.is_dir() {
+        CsvLoader::new().load_from_dir(path)
+    } else if BinaryLoader::is_snapshot_file(&path)? {
+        BinaryLoader::new().load_from_file(path)
+    } else if CsvLoader::is_csv_path(&path) {
+        CsvLoader::new().load_from_file(path)
+    } else {
+        JsonLoader::new().load_from_file(path)
+    }
+}
@@ -0,0 +1,108 @@
+//! Nomination pools for [`RpcLoader`]
+//!
+//! A pool votes through its bonded account, which appears in
+//! `Staking::Nominators` (or the election snapshot) like any other nominator.
+//! This module finds those accounts from `NominationPools::BondedPools` and
+//! attaches the pool's members from `NominationPools::PoolMembers`, so results
+//! can be attributed to members.
+
+use crate::error::ElectionError;
+use crate::input::rpc::{LoadReport, RpcLoader};
+use crate::input::staking_async::account_from_key;
+use crate::models::nominator::{NominationPool, Nominator, PoolMember};
+use parity_scale_codec::Decode;
+use std::collections::HashMap;
+
+const POOLS_PALLET: &str = "NominationPools";
+
+/// `PalletId` of the nomination pools pallet
+const POOLS_PALLET_ID: &[u8; 8] = b"py/nopls";
+
+/// Leading fields of `PoolMember`, which is all the loader needs
+#[derive(Decode)]
+struct PoolMemberPrefix {
+    pool_id: u32,
+    points: u128,
+}
+
+/// Bonded account of a pool
+///
+/// Derived like `PalletId::into_sub_account_truncating((AccountType::Bonded, pool_id))`:
+/// `"modl" ++ pallet id ++ SCALE((0u8, pool_id))`, zero-padded to 32 bytes.
+fn bonded_account(pool_id: u32) -> String {
+    let mut account = [0u8; 32];
+    account[..4].copy_from_slice(b"modl");
+    account[4..12].copy_from_slice(POOLS_PALLET_ID);
+    // account[12] is AccountType::Bonded, encoded as 0
+    account[13..17].copy_from_slice(&pool_id.to_le_bytes());
+    format!("0x{}", hex::encode(account))
+}
+
+/// Pool id at the end of a `Twox64Concat`-hashed `BondedPools` key
+fn pool_id_from_key(key: &str) -> Option<u32> {
+    let bytes = hex::decode(key.trim_start_matches("0x")).ok()?;
+    let id = bytes.get(bytes.len().checked_sub(4)?..)?;
+    Some(u32::from_le_bytes(id.try_into().ok()?))
+}
+
+impl RpcLoader {
+    /// Mark pool bonded accounts among `nominators` and attach their members
+    ///
+    /// Returns the number of pools found voting. Chains without the pools
+    /// pallet have no `BondedPools` keys and are left untouched.
+    pub(super) async fn attach_nomination_pools(
+        &self,
+        nominators: &mut [Nominator],
+        block_hash: &str,
+        report: &mut LoadReport,
+    ) -> Result<usize, ElectionError> {
+        let pool_keys = self
+            .list_storage_keys(&self.encode_storage_key(POOLS_PALLET, "BondedPools")?, block_hash)
+            .await?;
+        let mut pools: HashMap<u32, NominationPool> = pool_keys
+            .iter()
+            .filter_map(|key| pool_id_from_key(key))
+            .map(|pool_id| (pool_id, NominationPool { pool_id, members: Vec::new() }))
+            .collect();
+        if pools.is_empty() {
+            return Ok(0);
+        }
+
+        let member_keys = self
+            .list_storage_keys(&self.encode_storage_key(POOLS_PALLET, "PoolMembers")?, block_hash)
+            .await?;
+        let mut values = self.get_storage_values(&member_keys, block_hash).await?;
+        let mut undecodable = Vec::new();
+        for key in member_keys {
+            let member = values
+                .remove(&key)
+                .flatten()
+                .and_then(|hex| self.decode_storage_hex(&hex).ok())
+                .and_then(|bytes| PoolMemberPrefix::decode(&mut bytes.as_slice()).ok());
+            let (Some(account_id), Some(member)) = (account_from_key(&key), member) else {
+                undecodable.push(key);
+                continue;
+            };
+            if let Some(pool) = pools.get_mut(&member.pool_id) {
+                pool.members.push(PoolMember { account_id, points: member.points });
+            }
+        }
+        if !undecodable.is_empty() {
+            eprintln!("    ⚠ {} pool members could not be decoded", undecodable.len());
+        }
+        report.undecodable_keys.extend(undecodable);
+
+        let mut by_account: HashMap<String, NominationPool> = pools
+            .into_values()
+            .map(|pool| (bonded_account(pool.pool_id), pool))
+            .collect();
+        let mut voting = 0;
+        for nominator in nominators.iter_mut() {
+            if let Some(pool) = by_account.remove(&nominator.account_id) {
+                nominator.metadata.get_or_insert_with(Default::default).pool = Some(pool);
+                voting += 1;
+            }
+        }
+        Ok(voting)
+    }
+}
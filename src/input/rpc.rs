@@ -1,49 +1,383 @@
 //! RPC input loader for fetching election data from Substrate RPC endpoints
 
 use crate::error::ElectionError;
-use crate::models::election_data::{ElectionData, ElectionMetadata};
+use crate::input::cache::{KeyListing, StorageCache};
+use crate::input::staking_async::account_from_key;
+use crate::models::election_data::{ElectionData, ElectionMetadata, SnapshotCompleteness};
 use crate::models::nominator::Nominator;
 use crate::models::validator::ValidatorCandidate;
-use jsonrpsee::core::client::ClientT;
+use jsonrpsee::core::client::{ClientT, Subscription, SubscriptionClientT};
+use jsonrpsee::core::params::ArrayParams;
+use jsonrpsee::core::traits::ToRpcParams;
 use jsonrpsee::http_client::{HttpClient, HttpClientBuilder};
+use jsonrpsee::ws_client::{WsClient, WsClientBuilder};
+use serde::de::DeserializeOwned;
+use futures::{Stream, StreamExt};
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::HashMap;
 use std::hash::Hasher;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
 use twox_hash::XxHash64;
 
 /// Maximum number of retry attempts for transient errors
 const MAX_RETRIES: u32 = 5;
 /// Initial delay in seconds before first retry
 const INITIAL_RETRY_DELAY_SECS: u64 = 2;
+/// Default number of storage keys per `state_queryStorageAt` request
+pub const DEFAULT_BATCH_SIZE: usize = 256;
+/// Default number of storage requests in flight at once
+pub const DEFAULT_CONCURRENCY: usize = 8;
+/// Default timeout in seconds for fetching all nominators
+pub const DEFAULT_NOMINATORS_TIMEOUT_SECS: u64 = 600;
+
+/// Number of keys requested per `state_getKeysPaged` call
+const KEYS_PAGE_SIZE: u32 = 1000;
+/// Timeout for individual RPC requests
+const REQUEST_TIMEOUT_SECS: u64 = 30;
+/// Pallet taking the election snapshot on relay chains
+const ELECTION_PROVIDER_PALLET: &str = "ElectionProviderMultiPhase";
+
+/// A single RPC endpoint in the loader's pool
+struct RpcEndpoint {
+    url: String,
+    transport: RpcTransport,
+}
+
+/// Transport used to reach an endpoint, chosen from the URL scheme
+enum RpcTransport {
+    Http(Box<HttpClient>),
+    /// One WebSocket connection shared by all requests, opened on first use
+    /// and reopened if the node drops it
+    Ws(tokio::sync::Mutex<Option<Arc<WsClient>>>),
+}
+
+impl RpcEndpoint {
+    /// Set up an endpoint for an `http(s)://` or `ws(s)://` URL
+    fn new(url: String) -> Result<Self, ElectionError> {
+        let lower = url.to_lowercase();
+        let transport = if lower.starts_with("ws://") || lower.starts_with("wss://") {
+            RpcTransport::Ws(tokio::sync::Mutex::new(None))
+        } else {
+            // Configure timeouts to prevent hanging
+            let client = HttpClientBuilder::default()
+                .request_timeout(std::time::Duration::from_secs(REQUEST_TIMEOUT_SECS))
+                .build(&url)
+                .map_err(|e| ElectionError::RpcError {
+                    message: format!("Failed to create RPC client: {}", e),
+                    url: url.clone(),
+                })?;
+            RpcTransport::Http(Box::new(client))
+        };
+        Ok(Self { url, transport })
+    }
+
+    fn is_websocket(&self) -> bool {
+        matches!(self.transport, RpcTransport::Ws(_))
+    }
+
+    /// Get the WebSocket connection, connecting if there is none yet
+    async fn ws_client(
+        &self,
+        connection: &tokio::sync::Mutex<Option<Arc<WsClient>>>,
+    ) -> Result<Arc<WsClient>, jsonrpsee::core::Error> {
+        // Holding the lock while connecting makes concurrent requests wait for
+        // the same connection instead of each opening their own
+        let mut connection = connection.lock().await;
+        if let Some(client) = connection.as_ref().filter(|client| client.is_connected()) {
+            return Ok(client.clone());
+        }
+
+        let client = Arc::new(
+            WsClientBuilder::default()
+                .request_timeout(std::time::Duration::from_secs(REQUEST_TIMEOUT_SECS))
+                .connection_timeout(std::time::Duration::from_secs(REQUEST_TIMEOUT_SECS))
+                .build(&self.url)
+                .await?,
+        );
+        *connection = Some(client.clone());
+        Ok(client)
+    }
+
+    /// Send a JSON-RPC request over the endpoint's transport
+    async fn request<R, P>(&self, method: &str, params: P) -> Result<R, jsonrpsee::core::Error>
+    where
+        R: DeserializeOwned,
+        P: ToRpcParams + Send,
+    {
+        match &self.transport {
+            RpcTransport::Http(client) => client.request(method, params).await,
+            RpcTransport::Ws(connection) => self.ws_client(connection).await?.request(method, params).await,
+        }
+    }
+
+    /// Subscribe to notifications, which requires a WebSocket endpoint
+    async fn subscribe<N: DeserializeOwned>(
+        &self,
+        method: &str,
+        unsubscribe_method: &str,
+    ) -> Result<Subscription<N>, ElectionError> {
+        let RpcTransport::Ws(connection) = &self.transport else {
+            return Err(ElectionError::RpcError {
+                message: format!("{} requires a ws:// or wss:// endpoint", method),
+                url: self.url.clone(),
+            });
+        };
+        let client = self.ws_client(connection).await.map_err(|e| ElectionError::RpcError {
+            message: format!("Failed to connect: {}", e),
+            url: self.url.clone(),
+        })?;
+        client
+            .subscribe(method, ArrayParams::new(), unsubscribe_method)
+            .await
+            .map_err(|e| ElectionError::RpcError {
+                message: format!("Failed to subscribe with {}: {}", method, e),
+                url: self.url.clone(),
+            })
+    }
+}
+
+/// Staking pallet layout the loader reads election data from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StakingProfile {
+    /// Relay-chain `pallet-staking`, with the active set in `Session::Validators`
+    RelayChain,
+    /// Asset Hub `pallet-staking-async`, with the paged `MultiBlockElection` snapshot
+    AssetHub,
+}
+
+impl StakingProfile {
+    /// Profile implied by a `system_chain` name, if the name is conclusive
+    ///
+    /// Relay chain names are not conclusive, since relay chains hand staking
+    /// over to Asset Hub at some point in their history.
+    pub fn from_chain_name(name: &str) -> Option<Self> {
+        let name = name.to_lowercase();
+        ["asset hub", "asset-hub", "assethub", "statemint", "statemine", "westmint"]
+            .iter()
+            .any(|marker| name.contains(marker))
+            .then_some(StakingProfile::AssetHub)
+    }
+}
+
+impl std::str::FromStr for StakingProfile {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "relay-chain" | "relay" => Ok(StakingProfile::RelayChain),
+            "asset-hub" | "staking-async" => Ok(StakingProfile::AssetHub),
+            _ => Err(format!("Unknown staking profile: {}", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for StakingProfile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StakingProfile::RelayChain => write!(f, "relay-chain"),
+            StakingProfile::AssetHub => write!(f, "asset-hub"),
+        }
+    }
+}
+
+/// Finalized block announced by `chain_subscribeFinalizedHeads`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FinalizedHead {
+    /// Block number
+    pub number: u64,
+    /// Block hash
+    pub hash: String,
+    /// Hash of the parent block
+    pub parent_hash: String,
+}
+
+/// What one load found besides the election data, for its completeness report
+///
+/// Every load owns its report, so concurrent loads on one loader never mix
+/// up their undecodable keys.
+#[derive(Debug, Default)]
+pub(super) struct LoadReport {
+    /// Nominators decoded, including those without targets
+    pub nominators: usize,
+    /// Whether voters came from an election snapshot rather than `Staking::Nominators`
+    ///
+    /// Snapshot voters include validators voting for themselves and are
+    /// capped by the snapshot size, so `CounterForNominators` does not count them.
+    pub voters_from_snapshot: bool,
+    /// Storage keys whose entries could not be fetched or decoded
+    pub undecodable_keys: Vec<String>,
+}
 
 /// RPC loader for fetching election data from Substrate nodes
+///
+/// A loader can be backed by a pool of endpoints serving the same chain.
+/// Requests go to the active endpoint; retryable errors fail over to the next
+/// endpoint, and slow requests can optionally be hedged against a second one.
+///
+/// Both `http(s)://` and `ws(s)://` URLs are supported. WebSocket endpoints
+/// keep a single connection open for all requests of a fetch.
 pub struct RpcLoader {
-    client: HttpClient,
-    url: String,
+    endpoints: Vec<RpcEndpoint>,
+    /// Index of the endpoint requests are currently sent to
+    active: AtomicUsize,
+    /// Endpoints that may serve requests for the block being loaded
+    healthy: Vec<AtomicBool>,
+    /// Send a duplicate request to another endpoint after this delay
+    hedge_delay: Option<std::time::Duration>,
+    cache: Option<StorageCache>,
+    batch_size: usize,
+    concurrency: usize,
+    nominators_timeout: std::time::Duration,
+    /// Cleared once the endpoint rejects `state_queryStorageAt`
+    batch_queries_supported: AtomicBool,
+    /// Staking layout, detected on first use unless set explicitly
+    profile: tokio::sync::OnceCell<StakingProfile>,
+    /// `system_chain` name, fetched on first use
+    chain_name: tokio::sync::OnceCell<Option<String>>,
 }
 
 impl RpcLoader {
     /// Create a new RPC loader
     pub fn new(url: impl Into<String>) -> Result<Self, ElectionError> {
-        let url_str = url.into();
-        // Configure timeouts to prevent hanging
-        // 30 seconds for request timeout
-        let client = HttpClientBuilder::default()
-            .request_timeout(std::time::Duration::from_secs(30))
-            .build(&url_str)
-            .map_err(|e| ElectionError::RpcError {
-                message: format!("Failed to create RPC client: {}", e),
-                url: url_str.clone(),
-            })?;
+        Self::with_endpoints([url])
+    }
+
+    /// Create an RPC loader backed by a pool of endpoints for the same chain
+    ///
+    /// The first endpoint is used until it fails with a retryable error, after
+    /// which requests fail over to the next endpoint in the pool.
+    pub fn with_endpoints<I, S>(urls: I) -> Result<Self, ElectionError>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut endpoints = Vec::new();
+        for url in urls {
+            endpoints.push(RpcEndpoint::new(url.into())?);
+        }
+
+        if endpoints.is_empty() {
+            return Err(ElectionError::ValidationError {
+                message: "At least one RPC endpoint is required".to_string(),
+                field: Some("rpc_url".to_string()),
+            });
+        }
 
         Ok(Self {
-            client,
-            url: url_str,
+            healthy: endpoints.iter().map(|_| AtomicBool::new(true)).collect(),
+            endpoints,
+            active: AtomicUsize::new(0),
+            hedge_delay: None,
+            cache: None,
+            batch_size: DEFAULT_BATCH_SIZE,
+            concurrency: DEFAULT_CONCURRENCY,
+            nominators_timeout: std::time::Duration::from_secs(DEFAULT_NOMINATORS_TIMEOUT_SECS),
+            batch_queries_supported: AtomicBool::new(true),
+            profile: tokio::sync::OnceCell::new(),
+            chain_name: tokio::sync::OnceCell::new(),
         })
     }
 
+    /// Hedge requests that take longer than `delay`
+    ///
+    /// When a request has not completed after `delay`, the same request is sent
+    /// to another endpoint in the pool and whichever succeeds first is used.
+    /// Has no effect with a single endpoint.
+    pub fn with_hedge_delay(mut self, delay: std::time::Duration) -> Self {
+        self.hedge_delay = Some(delay);
+        self
+    }
+
+    /// URLs of all endpoints in the pool
+    pub fn endpoint_urls(&self) -> Vec<&str> {
+        self.endpoints.iter().map(|e| e.url.as_str()).collect()
+    }
+
+    /// Index of the endpoint requests are currently sent to
+    fn active_endpoint(&self) -> usize {
+        self.active.load(Ordering::Relaxed)
+    }
+
+    /// The active endpoint
+    fn endpoint(&self) -> &RpcEndpoint {
+        &self.endpoints[self.active_endpoint()]
+    }
+
+    /// URL of the active endpoint
+    pub(super) fn url(&self) -> &str {
+        &self.endpoints[self.active_endpoint()].url
+    }
+
+    /// Switch away from a failing endpoint to the next healthy one
+    ///
+    /// Returns the endpoint now in use, or `None` if there is no other healthy
+    /// endpoint to fail over to.
+    fn fail_over(&self, from: usize) -> Option<usize> {
+        let count = self.endpoints.len();
+        let next = (1..count)
+            .map(|offset| (from + offset) % count)
+            .find(|&index| self.healthy[index].load(Ordering::Relaxed))?;
+
+        // Another request may already have failed over; keep its choice
+        match self.active.compare_exchange(from, next, Ordering::Relaxed, Ordering::Relaxed) {
+            Ok(_) => Some(next),
+            Err(current) => Some(current),
+        }
+    }
+
+    /// Next healthy endpoint after `from` to hedge a request against
+    fn hedge_endpoint(&self, from: usize) -> Option<usize> {
+        let count = self.endpoints.len();
+        (1..count)
+            .map(|offset| (from + offset) % count)
+            .find(|&index| self.healthy[index].load(Ordering::Relaxed))
+    }
+
+    /// Set the number of storage keys fetched per `state_queryStorageAt` request
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Set the maximum number of storage requests in flight at once
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Set the timeout for fetching all nominators and their ledgers
+    pub fn with_nominators_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.nominators_timeout = timeout;
+        self
+    }
+
+    /// Read staking data with the given layout instead of detecting it
+    pub fn with_profile(mut self, profile: StakingProfile) -> Self {
+        self.profile = tokio::sync::OnceCell::new_with(Some(profile));
+        self
+    }
+
+    /// Serve storage key listings and values from an on-disk cache
+    ///
+    /// Storage fetched at a block hash is stored in the cache and reused by
+    /// later runs against the same block. Interrupted key listings are resumed
+    /// from the last cached key.
+    pub fn with_cache(mut self, cache: StorageCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Get the storage cache, if one is configured
+    pub fn cache(&self) -> Option<&StorageCache> {
+        self.cache.as_ref()
+    }
+
     /// Get suggested alternative RPC endpoints based on current URL
     fn get_alternative_endpoints(&self) -> Vec<&str> {
-        let url_lower = self.url.to_lowercase();
+        let url_lower = self.endpoints[0].url.to_lowercase();
         if url_lower.contains("polkadot") {
             vec![
                 "https://rpc.polkadot.io",
@@ -100,13 +434,21 @@ impl RpcLoader {
     }
 
     /// Retry an RPC call with exponential backoff for transient errors
-    async fn retry_rpc_call<F, Fut, T>(&self, mut f: F) -> Result<T, ElectionError>
+    ///
+    /// `f` is called with the index of the endpoint to send the request to.
+    /// Retryable errors fail over to the next healthy endpoint in the pool
+    /// immediately; the backoff delay only applies once every endpoint has
+    /// been tried.
+    async fn retry_rpc_call<F, Fut, T>(&self, f: F) -> Result<T, ElectionError>
     where
-        F: FnMut() -> Fut,
+        F: Fn(usize) -> Fut,
         Fut: std::future::Future<Output = Result<T, ElectionError>>,
     {
+        let mut failovers_since_backoff = 0;
+        let mut backoff_round = 0;
         for attempt in 0..=MAX_RETRIES {
-            match f().await {
+            let endpoint = self.active_endpoint();
+            match self.call_with_hedge(&f, endpoint).await {
                 Ok(result) => return Ok(result),
                 Err(e) => {
                     // Check if error is retryable
@@ -119,9 +461,11 @@ impl RpcLoader {
                     if attempt >= MAX_RETRIES {
                         return Err(match &e {
                             ElectionError::RpcError { message, url } => {
+                                let pool = self.endpoint_urls();
                                 let alternatives = self.get_alternative_endpoints();
                                 let alternatives_list = alternatives
                                     .iter()
+                                    .filter(|alt| !pool.contains(alt))
                                     .map(|alt| format!("  - {}", alt))
                                     .collect::<Vec<_>>()
                                     .join("\n");
@@ -129,7 +473,7 @@ impl RpcLoader {
                                 ElectionError::RpcError {
                                     message: format!(
                                         "{}\n\n\
-                                        All {} retry attempts failed. The RPC endpoint appears to be unavailable.\n\n\
+                                        All {} retry attempts failed across {} endpoint(s). The RPC endpoint appears to be unavailable.\n\n\
                                         Suggested alternative endpoints:\n{}\n\n\
                                         Other options:\n\
                                         - Use --input-file with JSON data instead\n\
@@ -137,6 +481,7 @@ impl RpcLoader {
                                         - Check the endpoint status page",
                                         message,
                                         MAX_RETRIES + 1,
+                                        self.endpoints.len(),
                                         alternatives_list
                                     ),
                                     url: url.clone(),
@@ -145,18 +490,37 @@ impl RpcLoader {
                             _ => e,
                         });
                     }
+
+                    // Fail over to another endpoint before backing off
+                    if failovers_since_backoff + 1 < self.endpoints.len() {
+                        if let Some(next) = self.fail_over(endpoint) {
+                            failovers_since_backoff += 1;
+                            eprintln!(
+                                "  ⚠ RPC error on {} (attempt {}/{}), failing over to {}...",
+                                self.endpoints[endpoint].url,
+                                attempt + 1,
+                                MAX_RETRIES + 1,
+                                self.endpoints[next].url
+                            );
+                            std::io::Write::flush(&mut std::io::stderr()).ok();
+                            continue;
+                        }
+                    }
+                    failovers_since_backoff = 0;
                     
                     // Calculate exponential backoff delay with cap at 30 seconds
                     let delay_secs = std::cmp::min(
-                        INITIAL_RETRY_DELAY_SECS * (1u64 << attempt),
+                        INITIAL_RETRY_DELAY_SECS * (1u64 << backoff_round),
                         30
                     );
+                    backoff_round += 1;
                     eprintln!("  ⚠ RPC error (attempt {}/{}), retrying in {} seconds...", 
                              attempt + 1, MAX_RETRIES + 1, delay_secs);
                     std::io::Write::flush(&mut std::io::stderr()).ok();
                     
-                    // Wait before retrying
+                    // Wait before retrying, moving on to the next endpoint if there is one
                     tokio::time::sleep(std::time::Duration::from_secs(delay_secs)).await;
+                    self.fail_over(endpoint);
                 }
             }
         }
@@ -164,10 +528,102 @@ impl RpcLoader {
         // This should never be reached, but handle it just in case
         Err(ElectionError::RpcError {
             message: "Unknown error during retry".to_string(),
-            url: self.url.clone(),
+            url: self.url().to_string(),
         })
     }
 
+    /// Run a request on an endpoint, hedging it on another endpoint if slow
+    ///
+    /// Without a hedge delay (or without a second healthy endpoint) this just
+    /// awaits the request. Otherwise, once the delay elapses the request is
+    /// also sent to another endpoint; the first success wins, and if one copy
+    /// fails the other is awaited.
+    async fn call_with_hedge<F, Fut, T>(&self, f: &F, endpoint: usize) -> Result<T, ElectionError>
+    where
+        F: Fn(usize) -> Fut,
+        Fut: std::future::Future<Output = Result<T, ElectionError>>,
+    {
+        let (Some(delay), Some(backup_endpoint)) = (self.hedge_delay, self.hedge_endpoint(endpoint)) else {
+            return f(endpoint).await;
+        };
+
+        let mut primary = std::pin::pin!(f(endpoint));
+        tokio::select! {
+            result = &mut primary => return result,
+            _ = tokio::time::sleep(delay) => {}
+        }
+
+        let mut backup = std::pin::pin!(f(backup_endpoint));
+        tokio::select! {
+            result = &mut primary => match result {
+                Ok(value) => Ok(value),
+                Err(_) => backup.await,
+            },
+            result = &mut backup => match result {
+                Ok(value) => {
+                    // The backup answered first, prefer it for later requests
+                    self.active
+                        .compare_exchange(endpoint, backup_endpoint, Ordering::Relaxed, Ordering::Relaxed)
+                        .ok();
+                    Ok(value)
+                }
+                Err(_) => primary.await,
+            },
+        }
+    }
+
+    /// Check that every endpoint in the pool agrees on the hash of a block
+    ///
+    /// Endpoints that report a different hash (for example, because they
+    /// follow another fork or have pruned the block) or cannot be reached are
+    /// excluded from the pool while loading this block, so data from them is
+    /// never mixed with data from the reference endpoint.
+    async fn verify_block_hash(&self, block_number: u64, block_hash: &str) {
+        if self.endpoints.len() < 2 {
+            return;
+        }
+
+        let reference = self.active_endpoint();
+        let checks = self.endpoints.iter().enumerate().map(|(index, endpoint)| async move {
+            if index == reference {
+                return (index, Ok(Value::String(block_hash.to_string())));
+            }
+            let response: Result<Value, _> = endpoint
+                .request("chain_getBlockHash", (format!("0x{:x}", block_number),))
+                .await;
+            (index, response)
+        });
+
+        let mut agreeing = 0;
+        for (index, response) in futures::future::join_all(checks).await {
+            let agrees = match response {
+                Ok(hash) if hash.as_str().is_some_and(|h| h.eq_ignore_ascii_case(block_hash)) => true,
+                Ok(hash) => {
+                    eprintln!(
+                        "  ⚠ Excluding {}: reports hash {} for block {} (expected {})",
+                        self.endpoints[index].url, hash, block_number, block_hash
+                    );
+                    false
+                }
+                Err(e) => {
+                    eprintln!(
+                        "  ⚠ Excluding {}: could not verify hash of block {}: {}",
+                        self.endpoints[index].url, block_number, e
+                    );
+                    false
+                }
+            };
+            self.healthy[index].store(agrees, Ordering::Relaxed);
+            agreeing += usize::from(agrees);
+        }
+
+        eprintln!(
+            "  ✓ {}/{} endpoints agree on block hash",
+            agreeing,
+            self.endpoints.len()
+        );
+    }
+
     /// Load election data at a specific block number
     pub async fn load_at_block(&self, block_number: u64) -> Result<ElectionData, ElectionError> {
         eprintln!("Fetching data from block {}...", block_number);
@@ -192,11 +648,14 @@ impl RpcLoader {
                 Note: Historical block queries require archive nodes, not regular RPC endpoints.",
                 block_number
             ),
-            url: self.url.clone(),
+            url: self.url().to_string(),
         })??;
         
         eprintln!("  ✓ Block hash: {}", block_hash);
         std::io::Write::flush(&mut std::io::stderr()).ok();
+        self.verify_block_hash(block_number, &block_hash).await;
+
+        let mut report = LoadReport::default();
 
         // Fetch validator candidates
         eprintln!("  → Fetching validators (this may take up to 30 seconds)...");
@@ -204,7 +663,7 @@ impl RpcLoader {
         
         let candidates = tokio::time::timeout(
             std::time::Duration::from_secs(30),
-            self.fetch_validators(&block_hash)
+            self.fetch_validators(&block_hash, &mut report)
         ).await.map_err(|_| ElectionError::RpcError {
             message: format!(
                 "Timeout after 30 seconds while fetching validators.\n\
@@ -212,29 +671,33 @@ impl RpcLoader {
                 The RPC endpoint may be slow or unresponsive.",
                 block_hash
             ),
-            url: self.url.clone(),
+            url: self.url().to_string(),
         })??;
         
         eprintln!("  ✓ Found {} validators", candidates.len());
         std::io::Write::flush(&mut std::io::stderr()).ok();
 
         // Fetch nominators and their votes
-        eprintln!("  → Fetching nominators (this may take a while, timeout: 60 seconds)...");
+        eprintln!(
+            "  → Fetching nominators (this may take a while, timeout: {} seconds)...",
+            self.nominators_timeout.as_secs()
+        );
         std::io::Write::flush(&mut std::io::stderr()).ok();
         
-        let nominators = tokio::time::timeout(
-            std::time::Duration::from_secs(60),
-            self.fetch_nominators(&block_hash)
+        let mut nominators = tokio::time::timeout(
+            self.nominators_timeout,
+            self.fetch_nominators(&block_hash, &mut report)
         ).await.unwrap_or_else(|_| {
             Err(ElectionError::RpcError {
                 message: format!(
-                    "Timeout after 60 seconds while fetching nominators.\n\
+                    "Timeout after {} seconds while fetching nominators.\n\
                     Block hash: {}\n\
                     This usually means the RPC endpoint doesn't support storage queries or is very slow.\n\
                     Proceeding with zero nominators - election will use only validator self-stakes.",
+                    self.nominators_timeout.as_secs(),
                     block_hash
                 ),
-                url: self.url.clone(),
+                url: self.url().to_string(),
             })
         }).unwrap_or_else(|e| {
             eprintln!("  ⚠ Warning: Could not fetch nominators from RPC: {}", e);
@@ -246,16 +709,220 @@ impl RpcLoader {
         eprintln!("  ✓ Found {} nominators", nominators.len());
         std::io::Write::flush(&mut std::io::stderr()).ok();
 
+        match self.attach_nomination_pools(&mut nominators, &block_hash, &mut report).await {
+            Ok(0) => {}
+            Ok(pools) => eprintln!("  ✓ Found {} nomination pools voting", pools),
+            Err(e) => eprintln!("  ⚠ Warning: Could not fetch nomination pools: {}", e),
+        }
+
+        let completeness = self.snapshot_completeness(&block_hash, report).await;
+
         Ok(ElectionData {
             candidates,
             nominators,
             metadata: Some(ElectionMetadata {
                 block_number: Some(block_number),
-                chain: None,
+                chain: self.chain_name().await.map(str::to_string),
+                era: None,
+                completeness: Some(completeness),
+                candidate_stakes_unknown: true,
             }),
         })
     }
 
+    /// Load election data for an era
+    ///
+    /// On relay chains, era `n` starts at session `ErasStartSessionIndex[n]`,
+    /// and its validators are elected when the session before that one begins.
+    /// This looks up the start session and binary-searches block headers for
+    /// the block where `Session::CurrentIndex` reaches the election session.
+    /// The election does not run on the state at that block, though: the
+    /// `ElectionProviderMultiPhase` pallet takes its snapshot when the signed
+    /// phase of the round starts, earlier in the session. That block is found
+    /// from `ElectionProviderMultiPhase::Round` and `CurrentPhase`, and data is
+    /// loaded at the block before it, i.e. the state the snapshot was taken
+    /// from. Chains without the pallet elect from the live state, so data is
+    /// loaded at the block before the election session instead.
+    ///
+    /// On Asset Hub, `Staking::CurrentEra` reaches the era when the era is
+    /// planned, just before its election starts. From that block, the search
+    /// continues for the first block holding the complete `MultiBlockElection`
+    /// snapshot of the round, and the snapshot is loaded there. If the round
+    /// stored no snapshot, the live state before the era was planned is loaded.
+    ///
+    /// The era must have started and still be within the chain's history
+    /// depth. Loading historical blocks requires an archive node.
+    pub async fn load_for_era(&self, era: u32) -> Result<ElectionData, ElectionError> {
+        eprintln!("Locating election block for era {}...", era);
+        std::io::Write::flush(&mut std::io::stderr()).ok();
+
+        let latest_block = self.get_latest_block_number().await?;
+        let latest_hash = self.get_block_hash(latest_block).await?;
+
+        let active_era_key = self.encode_storage_key("Staking", "ActiveEra")?;
+        let active_era = self
+            .read_storage_u32(&active_era_key, &latest_hash)
+            .await?
+            .ok_or_else(|| ElectionError::RpcError {
+                message: "Staking::ActiveEra is not set on this chain".to_string(),
+                url: self.url().to_string(),
+            })?;
+        if era > active_era {
+            return Err(ElectionError::ValidationError {
+                message: format!("Era {} has not started yet (active era is {})", era, active_era),
+                field: Some("era".to_string()),
+            });
+        }
+
+        let block_number = match self.staking_profile(&latest_hash).await? {
+            StakingProfile::RelayChain => {
+                let start_session_key = format!(
+                    "{}{}",
+                    self.encode_storage_key("Staking", "ErasStartSessionIndex")?,
+                    hex::encode(twox_64_concat(&era.to_le_bytes()))
+                );
+                let start_session = self
+                    .read_storage_u32(&start_session_key, &latest_hash)
+                    .await?
+                    .ok_or_else(|| ElectionError::ValidationError {
+                        message: format!(
+                            "No start session recorded for era {} (active era is {}); it may be older than the chain's history depth",
+                            era, active_era
+                        ),
+                        field: Some("era".to_string()),
+                    })?;
+                let election_session = start_session.checked_sub(1).ok_or_else(|| ElectionError::ValidationError {
+                    message: format!("Era {} starts at genesis and has no election", era),
+                    field: Some("era".to_string()),
+                })?;
+                eprintln!(
+                    "  ✓ Era {} starts at session {}, elected at the start of session {}",
+                    era, start_session, election_session
+                );
+                let session_key = self.encode_storage_key("Session", "CurrentIndex")?;
+                let election_block = self
+                    .find_first_block("Session::CurrentIndex", election_session as u64, latest_block, |block| {
+                        self.storage_u32_at(&session_key, block)
+                    })
+                    .await?;
+                // The state the snapshot was taken from, or the election read
+                let election_block = match self.find_snapshot_block(election_block.saturating_sub(1)).await? {
+                    Some(snapshot_block) => snapshot_block,
+                    None => election_block,
+                };
+                election_block.saturating_sub(1)
+            }
+            StakingProfile::AssetHub => {
+                if era == 0 {
+                    return Err(ElectionError::ValidationError {
+                        message: "Era 0 starts at genesis and has no election".to_string(),
+                        field: Some("era".to_string()),
+                    });
+                }
+                let era_key = self.encode_storage_key("Staking", "CurrentEra")?;
+                let plan_block = self
+                    .find_first_block("Staking::CurrentEra", era as u64, latest_block, |block| {
+                        self.storage_u32_at(&era_key, block)
+                    })
+                    .await?;
+                match self.find_asset_hub_snapshot_block(plan_block, latest_block).await? {
+                    Some(snapshot_block) => snapshot_block,
+                    None => plan_block.saturating_sub(1),
+                }
+            }
+        };
+
+        eprintln!("  ✓ Loading state the election for era {} ran on at block {}", era, block_number);
+        std::io::Write::flush(&mut std::io::stderr()).ok();
+
+        let mut data = self.load_at_block(block_number).await?;
+        if let Some(metadata) = data.metadata.as_mut() {
+            metadata.era = Some(era);
+        }
+        Ok(data)
+    }
+
+    /// Find the block at which the election snapshot read before `elect_block` was taken
+    ///
+    /// Returns `None` if the chain has no `ElectionProviderMultiPhase` pallet
+    /// or no snapshot was stored at `elect_block`. The phase moves from `Off`
+    /// to `Signed` (or straight to `Unsigned`) when the snapshot is taken and
+    /// back to `Off` once the round is elected, when `Round` increments, so
+    /// `(Round, phase is not Off)` never decreases over time.
+    async fn find_snapshot_block(&self, elect_block: u64) -> Result<Option<u64>, ElectionError> {
+        let round_key = self.encode_storage_key(ELECTION_PROVIDER_PALLET, "Round")?;
+        let phase_key = self.encode_storage_key(ELECTION_PROVIDER_PALLET, "CurrentPhase")?;
+        let progress_at = |block_number: u64| {
+            let (round_key, phase_key) = (&round_key, &phase_key);
+            async move {
+                let block_hash = self.get_block_hash(block_number).await?;
+                let round = self.read_storage_u32(round_key, &block_hash).await?;
+                // Phase::Off is variant 0 and the default when unset
+                let phase = self.get_storage_hex(phase_key, &block_hash).await?;
+                let in_round = phase.is_some_and(|hex| !hex.trim_start_matches("0x").starts_with("00"));
+                Ok::<_, ElectionError>(round.map(|round| round as u64 * 2 + u64::from(in_round)))
+            }
+        };
+
+        let Some(progress) = progress_at(elect_block).await? else {
+            eprintln!("  → No ElectionProviderMultiPhase pallet, the election reads the live staking state");
+            return Ok(None);
+        };
+        if progress % 2 == 0 {
+            eprintln!("  ⚠ Warning: No election snapshot stored at block {}, using the live staking state", elect_block);
+            return Ok(None);
+        }
+        let block = self
+            .find_first_block("the election snapshot", progress, elect_block, |block| async move {
+                Ok(progress_at(block).await?.unwrap_or(0))
+            })
+            .await?;
+        Ok(Some(block))
+    }
+
+    /// Read a `u32` storage value at a block number, 0 when unset
+    pub(super) async fn storage_u32_at(&self, key: &str, block_number: u64) -> Result<u64, ElectionError> {
+        let block_hash = self.get_block_hash(block_number).await?;
+        Ok(self.read_storage_u32(key, &block_hash).await?.unwrap_or(0) as u64)
+    }
+
+    /// Find the first block at which `value_at` is at least `target`
+    ///
+    /// The value must never decrease over time, like a session or era index.
+    pub(super) async fn find_first_block<F, Fut>(
+        &self,
+        what: &str,
+        target: u64,
+        latest_block: u64,
+        value_at: F,
+    ) -> Result<u64, ElectionError>
+    where
+        F: Fn(u64) -> Fut,
+        Fut: std::future::Future<Output = Result<u64, ElectionError>>,
+    {
+        if value_at(latest_block).await? < target {
+            return Err(ElectionError::RpcError {
+                message: format!("{} has not reached {} by block {}", what, target, latest_block),
+                url: self.url().to_string(),
+            });
+        }
+
+        // Invariant: the target is reached at `high` but not before `low`
+        let (mut low, mut high) = (0, latest_block);
+        let mut steps = 0;
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if value_at(mid).await? >= target {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+            steps += 1;
+        }
+        eprintln!("  ✓ {} reaches {} at block {} ({} lookups)", what, target, low, steps);
+        Ok(low)
+    }
+
     /// Load election data from the latest block
     pub async fn load_latest(&self) -> Result<ElectionData, ElectionError> {
         eprintln!("Fetching data from latest block...");
@@ -269,27 +936,28 @@ impl RpcLoader {
             std::time::Duration::from_secs(30),
             self.get_block_hash(0)
         ).await.map_err(|_| ElectionError::RpcError {
-            message: format!(
-                "Timeout after 30 seconds while getting latest block hash.\n\
+            message: "Timeout after 30 seconds while getting latest block hash.\n\
                 The RPC endpoint may be slow or unresponsive.\n\
                 Please try:\n\
                 - Using a different RPC endpoint\n\
                 - Using --input-file with JSON data instead\n\
-                - Checking your network connection"
-            ),
-            url: self.url.clone(),
+                - Checking your network connection".to_string(),
+            url: self.url().to_string(),
         })??;
         
         eprintln!("  ✓ Block hash: {}", block_hash);
         std::io::Write::flush(&mut std::io::stderr()).ok();
+        self.verify_block_hash(0, &block_hash).await;
         
+        let mut report = LoadReport::default();
+
         // Fetch validator candidates
         eprintln!("  → Fetching validators (this may take up to 30 seconds)...");
         std::io::Write::flush(&mut std::io::stderr()).ok();
         
         let candidates = tokio::time::timeout(
             std::time::Duration::from_secs(30),
-            self.fetch_validators(&block_hash)
+            self.fetch_validators(&block_hash, &mut report)
         ).await.map_err(|_| ElectionError::RpcError {
             message: format!(
                 "Timeout after 30 seconds while fetching validators.\n\
@@ -297,29 +965,33 @@ impl RpcLoader {
                 The RPC endpoint may be slow or unresponsive.",
                 block_hash
             ),
-            url: self.url.clone(),
+            url: self.url().to_string(),
         })??;
         
         eprintln!("  ✓ Found {} validators", candidates.len());
         std::io::Write::flush(&mut std::io::stderr()).ok();
 
         // Fetch nominators and their votes
-        eprintln!("  → Fetching nominators (this may take a while, timeout: 60 seconds)...");
+        eprintln!(
+            "  → Fetching nominators (this may take a while, timeout: {} seconds)...",
+            self.nominators_timeout.as_secs()
+        );
         std::io::Write::flush(&mut std::io::stderr()).ok();
         
-        let nominators = tokio::time::timeout(
-            std::time::Duration::from_secs(60),
-            self.fetch_nominators(&block_hash)
+        let mut nominators = tokio::time::timeout(
+            self.nominators_timeout,
+            self.fetch_nominators(&block_hash, &mut report)
         ).await.unwrap_or_else(|_| {
             Err(ElectionError::RpcError {
                 message: format!(
-                    "Timeout after 60 seconds while fetching nominators.\n\
+                    "Timeout after {} seconds while fetching nominators.\n\
                     Block hash: {}\n\
                     This usually means the RPC endpoint doesn't support storage queries or is very slow.\n\
                     Proceeding with zero nominators - election will use only validator self-stakes.",
+                    self.nominators_timeout.as_secs(),
                     block_hash
                 ),
-                url: self.url.clone(),
+                url: self.url().to_string(),
             })
         }).unwrap_or_else(|e| {
             eprintln!("  ⚠ Warning: Could not fetch nominators from RPC: {}", e);
@@ -331,6 +1003,14 @@ impl RpcLoader {
         eprintln!("  ✓ Found {} nominators", nominators.len());
         std::io::Write::flush(&mut std::io::stderr()).ok();
 
+        match self.attach_nomination_pools(&mut nominators, &block_hash, &mut report).await {
+            Ok(0) => {}
+            Ok(pools) => eprintln!("  ✓ Found {} nomination pools voting", pools),
+            Err(e) => eprintln!("  ⚠ Warning: Could not fetch nomination pools: {}", e),
+        }
+
+        let completeness = self.snapshot_completeness(&block_hash, report).await;
+
         // Get latest block number
         let latest_block = self.get_latest_block_number().await?;
 
@@ -339,21 +1019,216 @@ impl RpcLoader {
             nominators,
             metadata: Some(ElectionMetadata {
                 block_number: Some(latest_block),
-                chain: None,
+                chain: self.chain_name().await.map(str::to_string),
+                era: None,
+                completeness: Some(completeness),
+                candidate_stakes_unknown: true,
             }),
         })
     }
 
+    /// Get the staking layout, detecting it on first use
+    ///
+    /// Detection uses the `system_chain` name, falling back to probing for
+    /// storage of the `MultiBlockElection` pallet at the given block.
+    async fn staking_profile(&self, block_hash: &str) -> Result<StakingProfile, ElectionError> {
+        self.profile
+            .get_or_try_init(|| async {
+                let profile = match self.chain_name().await.and_then(StakingProfile::from_chain_name) {
+                    Some(profile) => profile,
+                    None => {
+                        let prefix = format!("0x{}", hex::encode(twox_128_hash(b"MultiBlockElection")));
+                        let keys: Vec<String> = self
+                            .retry_rpc_call(|endpoint| {
+                                let prefix = &prefix;
+                                async move {
+                                    let endpoint = &self.endpoints[endpoint];
+                                    endpoint
+                                        .request("state_getKeysPaged", (prefix, 1, None::<String>, block_hash))
+                                        .await
+                                        .map_err(|e| ElectionError::RpcError {
+                                            message: format!("Failed to query storage keys: {}", e),
+                                            url: endpoint.url.clone(),
+                                        })
+                                }
+                            })
+                            .await
+                            .unwrap_or_default();
+                        if keys.is_empty() {
+                            StakingProfile::RelayChain
+                        } else {
+                            StakingProfile::AssetHub
+                        }
+                    }
+                };
+                eprintln!("  ✓ Staking profile: {}", profile);
+                Ok(profile)
+            })
+            .await
+            .copied()
+    }
+
+    /// Name of the chain as reported by `system_chain`, if the node reports one
+    pub(super) async fn chain_name(&self) -> Option<&str> {
+        self.chain_name
+            .get_or_init(|| async {
+                self.request_value("system_chain")
+                    .await
+                    .ok()
+                    .and_then(|value| value.as_str().map(String::from))
+            })
+            .await
+            .as_deref()
+    }
+
+    /// Make a request without parameters, failing over like storage queries
+    pub(super) async fn request_value(&self, method: &str) -> Result<Value, ElectionError> {
+        self.retry_rpc_call(|endpoint| async move {
+            let endpoint = &self.endpoints[endpoint];
+            endpoint
+                .request(method, ArrayParams::new())
+                .await
+                .map_err(|e| ElectionError::RpcError {
+                    message: format!("{} failed: {}", method, e),
+                    url: endpoint.url.clone(),
+                })
+        })
+        .await
+    }
+
+    /// Compare decoded counts with the chain's `CounterFor*` storage items
+    ///
+    /// `CounterForValidators` counts every validator intention, so it is
+    /// compared with the `Staking::Validators` entries at the block rather
+    /// than with the candidates, which on relay chains are the active set.
+    /// The validator counter is skipped if those entries cannot be listed,
+    /// and the nominator counter if voters came from an election snapshot.
+    async fn snapshot_completeness(&self, block_hash: &str, mut report: LoadReport) -> SnapshotCompleteness {
+        let validators = self.count_validator_intentions(block_hash, &mut report).await;
+        let expected_validators = match validators {
+            Some(_) => self.read_counter("CounterForValidators", block_hash).await,
+            None => None,
+        };
+        let expected_nominators = if report.voters_from_snapshot {
+            None
+        } else {
+            self.read_counter("CounterForNominators", block_hash).await
+        };
+        let completeness = SnapshotCompleteness {
+            expected_validators,
+            loaded_validators: validators.unwrap_or(0) as u32,
+            expected_nominators,
+            loaded_nominators: report.nominators as u32,
+            undecodable_keys: report.undecodable_keys,
+        };
+
+        if completeness.is_complete() {
+            eprintln!("  ✓ Snapshot complete");
+        } else {
+            eprintln!("  ⚠ Snapshot incomplete: {}", completeness.problems().join(", "));
+        }
+        std::io::Write::flush(&mut std::io::stderr()).ok();
+        completeness
+    }
+
+    /// Number of `Staking::Validators` entries whose account decodes
+    async fn count_validator_intentions(&self, block_hash: &str, report: &mut LoadReport) -> Option<usize> {
+        let prefix = self.encode_storage_key("Staking", "Validators").ok()?;
+        let keys = match self.list_storage_keys(&prefix, block_hash).await {
+            Ok(keys) => keys,
+            Err(e) => {
+                eprintln!("  ⚠ Warning: Could not list Staking::Validators: {}", e);
+                return None;
+            }
+        };
+        let mut decoded = 0;
+        for key in keys {
+            match account_from_key(&key) {
+                Some(_) => decoded += 1,
+                None => report.undecodable_keys.push(key),
+            }
+        }
+        Some(decoded)
+    }
+
+    /// Read a `Staking` counter storage item (`u32`), if the chain has it
+    async fn read_counter(&self, item: &str, block_hash: &str) -> Option<u32> {
+        let key = self.encode_storage_key("Staking", item).ok()?;
+        match self.read_storage_u32(&key, block_hash).await {
+            Ok(value) => value,
+            Err(e) => {
+                eprintln!("  ⚠ Warning: Could not read Staking::{}: {}", item, e);
+                None
+            }
+        }
+    }
+
+    /// Read a storage value starting with a SCALE-encoded `u32`
+    pub(super) async fn read_storage_u32(&self, key: &str, block_hash: &str) -> Result<Option<u32>, ElectionError> {
+        let Some(hex) = self.get_storage_hex(key, block_hash).await? else {
+            return Ok(None);
+        };
+        let bytes = self.decode_storage_hex(&hex)?;
+        let value = bytes.get(..4).ok_or_else(|| ElectionError::RpcError {
+            message: format!("Storage value for {} is too short for a u32", key),
+            url: self.url().to_string(),
+        })?;
+        Ok(Some(u32::from_le_bytes([value[0], value[1], value[2], value[3]])))
+    }
+
+    /// Subscribe to finalized blocks
+    ///
+    /// Requires a `ws://` or `wss://` endpoint in the pool. Each announced
+    /// header is resolved to its block hash, so the block can be passed
+    /// straight to [`RpcLoader::load_at_block`]. The stream ends when the
+    /// connection is closed.
+    pub async fn subscribe_finalized_heads(
+        &self,
+    ) -> Result<impl Stream<Item = Result<FinalizedHead, ElectionError>> + '_, ElectionError> {
+        let endpoint = self
+            .endpoints
+            .iter()
+            .find(|endpoint| endpoint.is_websocket())
+            .unwrap_or(self.endpoint());
+        let subscription: Subscription<Value> = endpoint
+            .subscribe("chain_subscribeFinalizedHeads", "chain_unsubscribeFinalizedHeads")
+            .await?;
+
+        Ok(subscription.then(move |header| async move {
+            let header = header.map_err(|e| ElectionError::RpcError {
+                message: format!("Finalized heads subscription failed: {}", e),
+                url: endpoint.url.clone(),
+            })?;
+            let field = |name: &str| {
+                header.get(name).and_then(|v| v.as_str()).ok_or_else(|| ElectionError::RpcError {
+                    message: format!("Invalid header notification: missing {}", name),
+                    url: endpoint.url.clone(),
+                })
+            };
+
+            let number = u64::from_str_radix(field("number")?.trim_start_matches("0x"), 16).map_err(|e| {
+                ElectionError::RpcError {
+                    message: format!("Failed to parse block number: {}", e),
+                    url: endpoint.url.clone(),
+                }
+            })?;
+            let parent_hash = field("parentHash")?.to_string();
+            let hash = self.get_block_hash(number).await?;
+
+            Ok(FinalizedHead { number, hash, parent_hash })
+        }))
+    }
+
     /// Get the latest block number
-    async fn get_latest_block_number(&self) -> Result<u64, ElectionError> {
-        self.retry_rpc_call(|| async {
-            let response: Value = self
-                .client
+    pub(super) async fn get_latest_block_number(&self) -> Result<u64, ElectionError> {
+        self.retry_rpc_call(|endpoint| async move {
+            let endpoint = &self.endpoints[endpoint];
+            let response: Value = endpoint
                 .request("chain_getHeader", Vec::<String>::new())
                 .await
                 .map_err(|e| ElectionError::RpcError {
                     message: format!("Failed to get latest header: {}", e),
-                    url: self.url.clone(),
+                    url: endpoint.url.clone(),
                 })?;
 
             let number = response
@@ -361,24 +1236,24 @@ impl RpcLoader {
                 .and_then(|n| n.as_str())
                 .ok_or_else(|| ElectionError::RpcError {
                     message: "Invalid header response".to_string(),
-                    url: self.url.clone(),
+                    url: endpoint.url.clone(),
                 })?;
 
             // Parse hex number
             let number = number.trim_start_matches("0x");
             u64::from_str_radix(number, 16).map_err(|e| ElectionError::RpcError {
                 message: format!("Failed to parse block number: {}", e),
-                url: self.url.clone(),
+                url: endpoint.url.clone(),
             })
         })
         .await
     }
 
     /// Get block hash for a given block number
-    async fn get_block_hash(&self, block_number: u64) -> Result<String, ElectionError> {
-        self.retry_rpc_call(|| async {
-            let response: Value = self
-                .client
+    pub(super) async fn get_block_hash(&self, block_number: u64) -> Result<String, ElectionError> {
+        self.retry_rpc_call(|endpoint| async move {
+            let endpoint = &self.endpoints[endpoint];
+            let response: Value = endpoint
                 .request(
                     "chain_getBlockHash",
                     (format!("0x{:x}", block_number),),
@@ -386,12 +1261,12 @@ impl RpcLoader {
                 .await
                 .map_err(|e| ElectionError::RpcError {
                     message: format!("Failed to get block hash: {}", e),
-                    url: self.url.clone(),
+                    url: endpoint.url.clone(),
                 })?;
 
             let hash = response.as_str().ok_or_else(|| ElectionError::RpcError {
                 message: "Invalid block hash response".to_string(),
-                url: self.url.clone(),
+                url: endpoint.url.clone(),
             })?;
 
             Ok(hash.to_string())
@@ -400,22 +1275,27 @@ impl RpcLoader {
     }
 
     /// Fetch validator candidates from chain
-    async fn fetch_validators(&self, block_hash: &str) -> Result<Vec<ValidatorCandidate>, ElectionError> {
+    async fn fetch_validators(
+        &self,
+        block_hash: &str,
+        report: &mut LoadReport,
+    ) -> Result<Vec<ValidatorCandidate>, ElectionError> {
+        if self.staking_profile(block_hash).await? == StakingProfile::AssetHub {
+            return self.fetch_asset_hub_candidates(block_hash, report).await;
+        }
+
         // Try Session::Validators() first (active validator set)
         // Storage key: TwoX128("Session") + TwoX128("Validators")
         let session_key = self.encode_storage_key("Session", "Validators")?;
         
-        let response: Value = self
-            .client
-            .request(
-                "state_getStorage",
-                (session_key.clone(), block_hash),
-            )
+        let response = self
+            .get_storage_hex(&session_key, block_hash)
             .await
             .map_err(|e| ElectionError::RpcError {
                 message: format!("Failed to query Session::Validators storage: {}", e),
-                url: self.url.clone(),
-            })?;
+                url: self.url().to_string(),
+            })?
+            .map_or(Value::Null, Value::String);
 
         // If Session::Validators returns data, decode it
         if !response.is_null() {
@@ -426,17 +1306,14 @@ impl RpcLoader {
         // Note: Staking::Validators might not exist in all chains, but Session::Validators should
         let staking_key = self.encode_storage_key("Staking", "Validators")?;
         
-        let response: Value = self
-            .client
-            .request(
-                "state_getStorage",
-                (staking_key.clone(), block_hash),
-            )
+        let response = self
+            .get_storage_hex(&staking_key, block_hash)
             .await
             .map_err(|e| ElectionError::RpcError {
                 message: format!("Failed to query Staking::Validators storage: {}", e),
-                url: self.url.clone(),
-            })?;
+                url: self.url().to_string(),
+            })?
+            .map_or(Value::Null, Value::String);
 
         if !response.is_null() {
             return self.decode_validators_from_storage(&response, block_hash).await;
@@ -454,11 +1331,19 @@ impl RpcLoader {
         
         // Try state_queryStorageAt first
         let response: Result<Value, _> = self
-            .client
-            .request(
-                "state_queryStorageAt",
-                (vec![session_prefix.clone()], block_hash),
-            )
+            .retry_rpc_call(|endpoint| {
+                let session_prefix = &session_prefix;
+                async move {
+                    let endpoint = &self.endpoints[endpoint];
+                    endpoint
+                        .request("state_queryStorageAt", (vec![session_prefix], block_hash))
+                        .await
+                        .map_err(|e| ElectionError::RpcError {
+                            message: format!("Failed to query storage: {}", e),
+                            url: endpoint.url.clone(),
+                        })
+                }
+            })
             .await;
 
         if let Ok(value) = response {
@@ -500,7 +1385,7 @@ impl RpcLoader {
                 - Verify the block number is valid for this chain",
                 block_hash
             ),
-            url: self.url.clone(),
+            url: self.url().to_string(),
         })
     }
 
@@ -509,7 +1394,7 @@ impl RpcLoader {
         // Get the hex string from the storage value
         let hex_str = storage_value.as_str().ok_or_else(|| ElectionError::RpcError {
             message: "Storage value is not a string".to_string(),
-            url: self.url.clone(),
+            url: self.url().to_string(),
         })?;
 
         // Remove 0x prefix if present
@@ -518,7 +1403,7 @@ impl RpcLoader {
         // Decode hex to bytes
         let bytes = hex::decode(hex_str).map_err(|e| ElectionError::RpcError {
             message: format!("Failed to decode hex: {}", e),
-            url: self.url.clone(),
+            url: self.url().to_string(),
         })?;
 
         // Decode SCALE-encoded Vec<AccountId>
@@ -548,7 +1433,7 @@ impl RpcLoader {
                     expected_size,
                     bytes.len()
                 ),
-                url: self.url.clone(),
+                url: self.url().to_string(),
             });
         }
 
@@ -573,7 +1458,7 @@ impl RpcLoader {
                     "No validators found in storage. Block hash: {}",
                 block_hash
             ),
-            url: self.url.clone(),
+            url: self.url().to_string(),
         })
         } else {
             Ok(validators)
@@ -585,7 +1470,7 @@ impl RpcLoader {
         if data.is_empty() {
             return Err(ElectionError::RpcError {
                 message: "Empty data for compact decoding".to_string(),
-                url: self.url.clone(),
+                url: self.url().to_string(),
             });
         }
 
@@ -602,7 +1487,7 @@ impl RpcLoader {
                 if data.len() < 2 {
                     return Err(ElectionError::RpcError {
                         message: "Insufficient data for two-byte compact".to_string(),
-                        url: self.url.clone(),
+                        url: self.url().to_string(),
                     });
                 }
                 let value = ((first_byte >> 2) as u32) | ((data[1] as u32) << 6);
@@ -613,7 +1498,7 @@ impl RpcLoader {
                 if data.len() < 4 {
                     return Err(ElectionError::RpcError {
                         message: "Insufficient data for four-byte compact".to_string(),
-                        url: self.url.clone(),
+                        url: self.url().to_string(),
                     });
                 }
                 let value = ((first_byte >> 2) as u32)
@@ -628,7 +1513,7 @@ impl RpcLoader {
                 if data.len() < 1 + len {
                     return Err(ElectionError::RpcError {
                         message: format!("Insufficient data for {}-byte compact", len),
-                        url: self.url.clone(),
+                        url: self.url().to_string(),
                     });
                 }
                 // Read little-endian u32 from following bytes
@@ -642,7 +1527,7 @@ impl RpcLoader {
     }
 
     /// Encode a storage key using Substrate's TwoX128 hashing (twox_128)
-    fn encode_storage_key(&self, pallet: &str, storage_item: &str) -> Result<String, ElectionError> {
+    pub(super) fn encode_storage_key(&self, pallet: &str, storage_item: &str) -> Result<String, ElectionError> {
         let mut key_bytes = Vec::with_capacity(32);
         key_bytes.extend_from_slice(&twox_128_hash(pallet.as_bytes()));
         key_bytes.extend_from_slice(&twox_128_hash(storage_item.as_bytes()));
@@ -651,7 +1536,11 @@ impl RpcLoader {
     }
 
     /// Fetch nominators and their votes from chain
-    async fn fetch_nominators(&self, block_hash: &str) -> Result<Vec<Nominator>, ElectionError> {
+    async fn fetch_nominators(&self, block_hash: &str, report: &mut LoadReport) -> Result<Vec<Nominator>, ElectionError> {
+        if self.staking_profile(block_hash).await? == StakingProfile::AssetHub {
+            return self.fetch_asset_hub_nominators(block_hash, report).await;
+        }
+
         // Staking::Nominators is a StorageMap<AccountId, Nominations>
         // Staking::Ledger is a StorageMap<AccountId, StakingLedger>
         // We need to fetch all entries from both maps and combine them
@@ -663,156 +1552,20 @@ impl RpcLoader {
         let ledger_prefix = self.encode_storage_key("Staking", "Ledger")?;
         
         // Fetch all storage keys with the Nominators prefix
-        let nominator_keys_result = self.get_storage_keys(&nominators_prefix, block_hash).await;
-        let nominator_keys = match nominator_keys_result {
-            Ok(keys) => {
-                if keys.is_empty() {
-                    // Try pagination method if regular method returns empty
-                    return self.fetch_nominators_with_pagination(&nominators_prefix, &ledger_prefix, block_hash).await;
-                }
-                keys
-            }
-            Err(_e) => {
-                // Try alternative RPC method: state_getKeysPaged
-                return self.fetch_nominators_with_pagination(&nominators_prefix, &ledger_prefix, block_hash).await;
-            }
+        let nominator_keys = match self.get_storage_keys(&nominators_prefix, block_hash).await {
+            Ok(keys) if !keys.is_empty() => keys,
+            // Try pagination method if the regular method fails or returns nothing
+            _ => return self.fetch_nominators_with_pagination(&nominators_prefix, &ledger_prefix, block_hash, report).await,
         };
         
         // Fetch all storage keys with the Ledger prefix
-        let ledger_keys_result = self.get_storage_keys(&ledger_prefix, block_hash).await;
-        let ledger_keys = match ledger_keys_result {
+        let ledger_keys = match self.get_storage_keys(&ledger_prefix, block_hash).await {
             Ok(keys) => keys,
-            Err(_e) => {
-                // If Ledger keys fail, try pagination method
-                return self.fetch_nominators_with_pagination(&nominators_prefix, &ledger_prefix, block_hash).await;
-            }
+            // If Ledger keys fail, try pagination method
+            Err(_e) => return self.fetch_nominators_with_pagination(&nominators_prefix, &ledger_prefix, block_hash, report).await,
         };
         
-        // Build a map of AccountId -> Nominator (initially with empty targets)
-        let mut nominators_map: std::collections::HashMap<String, Nominator> = std::collections::HashMap::new();
-        
-        let mut nominator_keys_processed = 0;
-        let mut ledger_keys_processed = 0;
-        let mut decode_errors = Vec::new();
-        
-        // Process Nominators storage entries to get targets
-        for key in nominator_keys {
-            nominator_keys_processed += 1;
-            // Extract AccountId from storage key
-            // Format: prefix (32 bytes) + blake2_128(AccountId) (16 bytes) + AccountId (32 bytes)
-            let account_id = match self.decode_account_id_from_key(&key, &nominators_prefix, true) {
-                Ok(id) => id,
-                Err(e) => {
-                    decode_errors.push(format!("Failed to decode AccountId from Nominators key: {}", e));
-                    continue;
-                }
-            };
-            
-            // Fetch the storage value for this key
-            let value = match self.get_storage_value(&key, block_hash).await {
-                Ok(v) => v,
-                Err(e) => {
-                    decode_errors.push(format!("Failed to get storage value for Nominators key: {}", e));
-                    continue;
-                }
-            };
-            
-            if let Some(nominations_bytes) = value {
-                // Decode Nominations struct to get targets
-                match self.decode_nominations_targets(&nominations_bytes) {
-                    Ok(targets) => {
-                        // Create or update nominator with targets
-                        let nominator = nominators_map.entry(account_id.clone()).or_insert_with(|| {
-                            Nominator::new(account_id, 0)
-                        });
-                        nominator.targets = targets;
-                    }
-                    Err(e) => {
-                        decode_errors.push(format!("Failed to decode Nominations targets: {}", e));
-                    }
-                }
-            }
-        }
-        
-        // Process Ledger storage entries to get stakes
-        for key in ledger_keys {
-            ledger_keys_processed += 1;
-            // Extract AccountId from storage key
-            // Format: prefix (32 bytes) + twox64(AccountId) (8 bytes) + AccountId (32 bytes)
-            let account_id = match self.decode_account_id_from_key(&key, &ledger_prefix, false) {
-                Ok(id) => id,
-                Err(e) => {
-                    decode_errors.push(format!("Failed to decode AccountId from Ledger key: {}", e));
-                    continue;
-                }
-            };
-            
-            // Fetch the storage value for this key
-            let value = match self.get_storage_value(&key, block_hash).await {
-                Ok(v) => v,
-                Err(e) => {
-                    decode_errors.push(format!("Failed to get storage value for Ledger key: {}", e));
-                    continue;
-                }
-            };
-            
-            if let Some(ledger_bytes) = value {
-                // Decode StakingLedger to get total stake
-                match self.decode_staking_ledger_stake(&ledger_bytes) {
-                    Ok(stake) => {
-                        // Create or update nominator with stake
-                        let nominator = nominators_map.entry(account_id.clone()).or_insert_with(|| {
-                            Nominator::new(account_id, 0)
-                        });
-                        nominator.stake = stake;
-                    }
-                    Err(e) => {
-                        decode_errors.push(format!("Failed to decode StakingLedger stake: {}", e));
-                    }
-                }
-            }
-        }
-        
-        // Convert HashMap to Vec
-        let mut nominators: Vec<Nominator> = nominators_map.into_values().collect();
-        
-        // Build diagnostic message
-        let mut diag_msg = format!(
-            "Nominator fetch diagnostics:\n\
-            - Nominator keys found: {}\n\
-            - Ledger keys found: {}\n\
-            - Nominators processed: {}\n",
-            nominator_keys_processed,
-            ledger_keys_processed,
-            nominators.len()
-        );
-        
-        if !decode_errors.is_empty() {
-            diag_msg.push_str(&format!("\nDecode errors (showing first 5):\n"));
-            for err in decode_errors.iter().take(5) {
-                diag_msg.push_str(&format!("  - {}\n", err));
-            }
-        }
-        
-        // Filter out nominators with no targets (they're not actually nominating)
-        let before_filter = nominators.len();
-        nominators.retain(|n| !n.targets.is_empty());
-        let after_filter = nominators.len();
-        
-        diag_msg.push_str(&format!(
-            "- Nominators before filtering (no targets): {}\n\
-            - Nominators after filtering: {}",
-            before_filter,
-            after_filter
-        ));
-        
-        if nominators.is_empty() {
-            // Return empty list instead of error - election can run without nominators
-            // This allows the tool to work even if RPC doesn't support these methods
-            return Ok(Vec::new());
-        }
-        
-        Ok(nominators)
+        self.process_nominator_keys(nominator_keys, ledger_keys, &nominators_prefix, &ledger_prefix, block_hash, report).await
     }
     
     /// Alternative method using state_queryStorage (more reliable on some endpoints)
@@ -843,6 +1596,7 @@ impl RpcLoader {
         nominators_prefix: &str,
         ledger_prefix: &str,
         block_hash: &str,
+        report: &mut LoadReport,
     ) -> Result<Vec<Nominator>, ElectionError> {
         // Store lengths before processing
         let nominator_keys_count = nominator_keys.len();
@@ -851,44 +1605,75 @@ impl RpcLoader {
         // Build a map of AccountId -> Nominator
         let mut nominators_map: std::collections::HashMap<String, Nominator> = std::collections::HashMap::new();
         let mut decode_errors = Vec::new();
+        let mut undecodable_keys = Vec::new();
         let mut nominators_processed = 0;
         let mut ledgers_processed = 0;
         let mut targets_decoded = 0;
         let mut stakes_decoded = 0;
         
-        // Process Nominators storage entries
+        // Decode account IDs from the keys, skipping keys that are exactly the
+        // prefix (some RPCs return the prefix itself)
+        let mut nominator_entries = Vec::with_capacity(nominator_keys.len());
         for key in &nominator_keys {
-            // Skip keys that are exactly the prefix (some RPCs return the prefix itself)
-            let key_normalized = key.trim_start_matches("0x");
-            let prefix_normalized = nominators_prefix.trim_start_matches("0x");
-            if key_normalized == prefix_normalized {
-                decode_errors.push(format!("Skipping key that is exactly the prefix (not a valid entry)"));
+            if key.trim_start_matches("0x") == nominators_prefix.trim_start_matches("0x") {
+                decode_errors.push("Skipping key that is exactly the prefix (not a valid entry)".to_string());
                 continue;
             }
             
             nominators_processed += 1;
-            let account_id = match self.decode_account_id_from_key(key, nominators_prefix, true) {
-                Ok(id) => id,
+            match self.decode_account_id_from_key(key, nominators_prefix, true) {
+                Ok(account_id) => nominator_entries.push((key.clone(), account_id)),
                 Err(e) => {
                     decode_errors.push(format!("Failed to decode AccountId from Nominators key: {}", e));
-                    continue;
+                    undecodable_keys.push(key.clone());
                 }
-            };
+            }
+        }
+        
+        let mut ledger_entries = Vec::with_capacity(ledger_keys.len());
+        for key in &ledger_keys {
+            if key.trim_start_matches("0x") == ledger_prefix.trim_start_matches("0x") {
+                decode_errors.push("Skipping key that is exactly the prefix (not a valid entry)".to_string());
+                continue;
+            }
             
-            let nominations_bytes = match self.get_storage_value(key, block_hash).await {
-                Ok(Some(bytes)) => bytes,
-                Ok(None) => {
-                    decode_errors.push(format!("Nominators storage value is null for key"));
-                    continue;
-                }
+            ledgers_processed += 1;
+            match self.decode_account_id_from_key(key, ledger_prefix, false) {
+                Ok(account_id) => ledger_entries.push((key.clone(), account_id)),
                 Err(e) => {
+                    decode_errors.push(format!("Failed to decode AccountId from Ledger key: {}", e));
+                    undecodable_keys.push(key.clone());
+                }
+            }
+        }
+        
+        // Fetch Nominators and Ledger values together in concurrent batches
+        let all_keys: Vec<String> = nominator_entries
+            .iter()
+            .chain(ledger_entries.iter())
+            .map(|(key, _)| key.clone())
+            .collect();
+        let mut values = self.get_storage_values(&all_keys, block_hash).await?;
+        
+        // Process Nominators storage entries
+        for (key, account_id) in nominator_entries {
+            let nominations_bytes = match values.remove(&key).flatten().map(|hex| self.decode_storage_hex(&hex)) {
+                Some(Ok(bytes)) => bytes,
+                Some(Err(e)) => {
                     decode_errors.push(format!("Failed to get storage value for Nominators key: {}", e));
+                    undecodable_keys.push(key);
+                    continue;
+                }
+                None => {
+                    decode_errors.push("Nominators storage value is null for key".to_string());
+                    undecodable_keys.push(key);
                     continue;
                 }
             };
             
             match self.decode_nominations_targets(&nominations_bytes) {
                 Ok(targets) => {
+                    report.nominators += 1;
                     if !targets.is_empty() {
                         targets_decoded += 1;
                         let nominator = nominators_map.entry(account_id.clone()).or_insert_with(|| {
@@ -899,37 +1684,23 @@ impl RpcLoader {
                 }
                 Err(e) => {
                     decode_errors.push(format!("Failed to decode Nominations targets: {}", e));
+                    undecodable_keys.push(key);
                 }
             }
         }
         
         // Process Ledger storage entries
-        for key in &ledger_keys {
-            // Skip keys that are exactly the prefix (some RPCs return the prefix itself)
-            let key_normalized = key.trim_start_matches("0x");
-            let prefix_normalized = ledger_prefix.trim_start_matches("0x");
-            if key_normalized == prefix_normalized {
-                decode_errors.push(format!("Skipping key that is exactly the prefix (not a valid entry)"));
-                continue;
-            }
-            
-            ledgers_processed += 1;
-            let account_id = match self.decode_account_id_from_key(key, ledger_prefix, false) {
-                Ok(id) => id,
-                Err(e) => {
-                    decode_errors.push(format!("Failed to decode AccountId from Ledger key: {}", e));
+        for (key, account_id) in ledger_entries {
+            let ledger_bytes = match values.remove(&key).flatten().map(|hex| self.decode_storage_hex(&hex)) {
+                Some(Ok(bytes)) => bytes,
+                Some(Err(e)) => {
+                    decode_errors.push(format!("Failed to get storage value for Ledger key: {}", e));
+                    undecodable_keys.push(key);
                     continue;
                 }
-            };
-            
-            let ledger_bytes = match self.get_storage_value(key, block_hash).await {
-                Ok(Some(bytes)) => bytes,
-                Ok(None) => {
-                    decode_errors.push(format!("Ledger storage value is null for key"));
-                    continue;
-                }
-                Err(e) => {
-                    decode_errors.push(format!("Failed to get storage value for Ledger key: {}", e));
+                None => {
+                    decode_errors.push("Ledger storage value is null for key".to_string());
+                    undecodable_keys.push(key);
                     continue;
                 }
             };
@@ -937,17 +1708,20 @@ impl RpcLoader {
             match self.decode_staking_ledger_stake(&ledger_bytes) {
                 Ok(stake) => {
                     stakes_decoded += 1;
-                        let nominator = nominators_map.entry(account_id.clone()).or_insert_with(|| {
-                            Nominator::new(account_id, 0)
-                        });
-                        nominator.stake = stake;
-                    }
+                    let nominator = nominators_map.entry(account_id.clone()).or_insert_with(|| {
+                        Nominator::new(account_id, 0)
+                    });
+                    nominator.stake = stake;
+                }
                 Err(e) => {
                     decode_errors.push(format!("Failed to decode StakingLedger stake: {}", e));
+                    undecodable_keys.push(key);
                 }
             }
         }
         
+        report.undecodable_keys.extend(undecodable_keys);
+
         let mut nominators: Vec<Nominator> = nominators_map.into_values().collect();
         let before_filter = nominators.len();
         nominators.retain(|n| !n.targets.is_empty());
@@ -998,7 +1772,7 @@ impl RpcLoader {
             
             return Err(ElectionError::RpcError {
                 message: error_msg,
-                url: self.url.clone(),
+                url: self.url().to_string(),
             });
         }
         
@@ -1006,238 +1780,42 @@ impl RpcLoader {
     }
     
     /// Alternative method using pagination if state_getKeys doesn't work
+    ///
+    /// Keys are listed with [`Self::list_storage_keys`], so each page fails
+    /// over, retries and hedges like other storage reads, and a listing cached
+    /// by an earlier, interrupted run is resumed.
     async fn fetch_nominators_with_pagination(
         &self,
         nominators_prefix: &str,
         ledger_prefix: &str,
         block_hash: &str,
+        report: &mut LoadReport,
     ) -> Result<Vec<Nominator>, ElectionError> {
-        // Try state_getKeysPaged with pagination
-        // Note: Parameter order may vary by RPC implementation
-        let mut nominator_keys = Vec::new();
-        let mut ledger_keys = Vec::new();
-        let mut nominator_start_key: Option<String> = None;
-        let mut ledger_start_key: Option<String> = None;
-        let page_size = 1000u32;
-        
-        // Try different parameter orders for state_getKeysPaged
-        // Some implementations use: (prefix, count, start_key, at)
-        // Others use: (prefix, count, at, start_key)
-        
-        // Fetch Nominator keys with pagination - try first parameter order
-        let mut page_count = 0;
-        loop {
-            page_count += 1;
-            if page_count > 1 {
-                eprintln!("    → Fetching nominator keys page {}...", page_count);
-            }
-            
-            // Add timeout wrapper for individual requests
-            let response = tokio::time::timeout(
-                std::time::Duration::from_secs(30),
-                self.client.request(
-                    "state_getKeysPaged",
-                    (
-                        nominators_prefix,
-                        page_size,
-                        nominator_start_key.as_ref(),
-                        Some(block_hash),
-                    ),
-                )
-            ).await;
-            
-            // If that fails, try alternative parameter order
-            let response: Result<Value, _> = match response {
-                Ok(Ok(v)) => Ok(v),
-                Ok(Err(_)) => {
-                    // Try alternative parameter order
-                    let alt_response = tokio::time::timeout(
-                        std::time::Duration::from_secs(30),
-                        self.client.request(
-                            "state_getKeysPaged",
-                            (
-                                nominators_prefix,
-                                page_size,
-                                Some(block_hash),
-                                nominator_start_key.as_ref(),
-                            ),
-                        )
-                    ).await;
-                    
-                    match alt_response {
-                        Ok(Ok(v)) => Ok(v),
-                        Ok(Err(e)) => Err(e),
-                        Err(_) => {
-                            return Err(ElectionError::RpcError {
-                                message: format!(
-                                    "Request timeout after 30 seconds while fetching nominator keys.\n\
-                                    This usually means the RPC endpoint is slow or doesn't support this method.\n\
-                                    Block hash: {}\n\
-                                    Please try using --input-file with JSON data instead.",
-                                    block_hash
-                                ),
-                                url: self.url.clone(),
-                            });
-                        }
-                    }
-                }
-                Err(_) => {
-                    return Err(ElectionError::RpcError {
-                        message: format!(
-                            "Request timeout after 30 seconds while fetching nominator keys.\n\
-                            This usually means the RPC endpoint is slow or doesn't support this method.\n\
-                            Block hash: {}\n\
-                            Please try using --input-file with JSON data instead.",
-                            block_hash
-                        ),
-                        url: self.url.clone(),
-                    });
-                }
-            };
-            
-            match response {
-                Ok(value) => {
-                    if let Some(keys_array) = value.as_array() {
-                        if keys_array.is_empty() {
-                            break;
-                        }
-                        let prefix_normalized = nominators_prefix.trim_start_matches("0x");
-                        for key in keys_array {
-                            if let Some(key_str) = key.as_str() {
-                                // Filter out keys that are exactly the prefix
-                                let key_normalized = key_str.trim_start_matches("0x");
-                                if key_normalized != prefix_normalized {
-                                nominator_keys.push(key_str.to_string());
-                                }
-                            }
-                        }
-                        // Set start key for next page
-                        if let Some(last_key) = keys_array.last().and_then(|k| k.as_str()) {
-                            nominator_start_key = Some(last_key.to_string());
-                        } else {
-                            break;
-                        }
-                        // If we got fewer than page_size, we're done
-                        if keys_array.len() < page_size as usize {
-                            break;
-                        }
-                    } else {
-                        break;
-                    }
-                }
-                Err(_) => {
-                    // If pagination doesn't work, return error with helpful message
-                    return Err(ElectionError::RpcError {
-            message: format!(
-                            "Failed to fetch nominator storage keys.\n\
-                            Tried both state_getKeys and state_getKeysPaged.\n\
-                Block hash: {}\n\
-                            \n\
-                            This RPC endpoint might not support these methods or historical state queries.\n\
-                            Solutions:\n\
-                            - Use an archive node endpoint (see RPC_ARCHIVE_NODES.md for list)\n\
-                            - Use --input-file with JSON data instead",
-                block_hash
-            ),
-                        url: self.url.clone(),
-                    });
-                }
-            }
-        }
-        
-        // Fetch Ledger keys with pagination - try first parameter order
-        let mut ledger_page_count = 0;
-        loop {
-            ledger_page_count += 1;
-            if ledger_page_count > 1 {
-                eprintln!("    → Fetching ledger keys page {}...", ledger_page_count);
-            }
-            
-            // Add timeout wrapper for individual requests
-            let response = tokio::time::timeout(
-                std::time::Duration::from_secs(30),
-                self.client.request(
-                    "state_getKeysPaged",
-                    (
-                        ledger_prefix,
-                        page_size,
-                        ledger_start_key.as_ref(),
-                        Some(block_hash),
-                    ),
-                )
-            ).await;
-            
-            let response: Result<Value, _> = match response {
-                Ok(Ok(v)) => Ok(v),
-                Ok(Err(e)) => Err(e),
-                Err(_) => {
-                    return Err(ElectionError::RpcError {
-                        message: format!(
-                            "Request timeout after 30 seconds while fetching ledger keys.\n\
-                            This usually means the RPC endpoint is slow or doesn't support this method.\n\
-                            Block hash: {}\n\
-                            Please try using --input-file with JSON data instead.",
-                            block_hash
-                        ),
-                        url: self.url.clone(),
-                    });
-                }
-            };
-            
-            // If that fails, try alternative parameter order
-            let response = match response {
-                Ok(v) => Ok(v),
-                Err(_) => {
-                    self.client
-                        .request(
-                            "state_getKeysPaged",
-                            (
-                                ledger_prefix,
-                                page_size,
-                                Some(block_hash),
-                                ledger_start_key.as_ref(),
-                            ),
-                        )
-                        .await
-                }
-            };
-            
-            match response {
-                Ok(value) => {
-                    if let Some(keys_array) = value.as_array() {
-                        if keys_array.is_empty() {
-                            break;
-                        }
-                        let prefix_normalized = ledger_prefix.trim_start_matches("0x");
-                        for key in keys_array {
-                            if let Some(key_str) = key.as_str() {
-                                // Filter out keys that are exactly the prefix
-                                let key_normalized = key_str.trim_start_matches("0x");
-                                if key_normalized != prefix_normalized {
-                                    ledger_keys.push(key_str.to_string());
-                                }
-                            }
-                        }
-                        // Set start key for next page
-                        if let Some(last_key) = keys_array.last().and_then(|k| k.as_str()) {
-                            ledger_start_key = Some(last_key.to_string());
-                        } else {
-                            break;
-                        }
-                        // If we got fewer than page_size, we're done
-                        if keys_array.len() < page_size as usize {
-                            break;
-                        }
-                    } else {
-                        break;
-                    }
-                }
-                Err(_) => {
-                    break;
-                }
+        let nominator_keys = self
+            .list_storage_keys(nominators_prefix, block_hash)
+            .await
+            .map_err(|e| ElectionError::RpcError {
+                message: format!(
+                    "Failed to fetch nominator storage keys: {}\n\
+                    Tried both state_getKeys and state_getKeysPaged.\n\
+                    Block hash: {}\n\
+                    \n\
+                    This RPC endpoint might not support these methods or historical state queries.\n\
+                    Solutions:\n\
+                    - Use an archive node endpoint (see RPC_ARCHIVE_NODES.md for list)\n\
+                    - Use --input-file with JSON data instead",
+                    e, block_hash
+                ),
+                url: self.url().to_string(),
+            })?;
+        let ledger_keys = match self.list_storage_keys(ledger_prefix, block_hash).await {
+            Ok(keys) => keys,
+            Err(e) => {
+                eprintln!("    ⚠ Warning: Could not list ledger keys: {}", e);
+                Vec::new()
             }
-        }
-        
+        };
+
         // If we found no valid keys with pagination (only prefix keys were returned), 
         // try query_storage method as fallback
         if nominator_keys.is_empty() && ledger_keys.is_empty() {
@@ -1246,7 +1824,9 @@ impl RpcLoader {
         }
         
         // Process the keys using the shared processing logic
-        let result = self.process_nominator_keys(nominator_keys, ledger_keys, nominators_prefix, ledger_prefix, block_hash).await;
+        let result = self
+            .process_nominator_keys(nominator_keys, ledger_keys, nominators_prefix, ledger_prefix, block_hash, report)
+            .await;
         
         // If processing failed, try query_storage as final fallback
         match result {
@@ -1263,33 +1843,66 @@ impl RpcLoader {
         }
     }
     
+    /// List all storage keys under a prefix with `state_getKeysPaged`
+    ///
+    /// Each page is cached as it arrives, so an interrupted listing resumes
+    /// from the last cached key.
+    pub(super) async fn list_storage_keys(&self, prefix: &str, block_hash: &str) -> Result<Vec<String>, ElectionError> {
+        let KeyListing { mut keys, mut complete } = self.cached_key_listing(prefix, block_hash);
+        let prefix_normalized = prefix.trim_start_matches("0x");
+        while !complete {
+            let start_key = keys.last().cloned();
+            let page: Vec<String> = self
+                .retry_rpc_call(|endpoint| {
+                    let start_key = &start_key;
+                    async move {
+                        let endpoint = &self.endpoints[endpoint];
+                        endpoint
+                            .request("state_getKeysPaged", (prefix, KEYS_PAGE_SIZE, start_key, block_hash))
+                            .await
+                            .map_err(|e| ElectionError::RpcError {
+                                message: format!("Failed to query storage keys: {}", e),
+                                url: endpoint.url.clone(),
+                            })
+                    }
+                })
+                .await?;
+
+            complete = page.len() < KEYS_PAGE_SIZE as usize;
+            // Some RPCs return the prefix itself as a key
+            keys.extend(page.into_iter().filter(|key| key.trim_start_matches("0x") != prefix_normalized));
+            self.cache_key_listing(prefix, block_hash, &keys, complete);
+        }
+        Ok(keys)
+    }
+
     /// Get all storage keys with a given prefix
     async fn get_storage_keys(&self, prefix: &str, block_hash: &str) -> Result<Vec<String>, ElectionError> {
+        let cached = self.cached_key_listing(prefix, block_hash);
+        if cached.complete {
+            return Ok(cached.keys);
+        }
+
         // Use state_getKeys RPC method to get all keys with the prefix
         // Note: Some RPC endpoints use state_getKeysPaged instead
-        let response: Result<Value, _> = self
-            .client
-            .request(
-                "state_getKeys",
-                (prefix, block_hash),
-            )
-            .await;
-        
-        let value = match response {
-            Ok(v) => v,
-            Err(e) => {
-                // If state_getKeys fails, the error will be caught by caller
-                return Err(ElectionError::RpcError {
-                    message: format!("Failed to query storage keys: {}", e),
-                    url: self.url.clone(),
-                });
-            }
-        };
+        // If state_getKeys fails, the error will be caught by caller
+        let value: Value = self
+            .retry_rpc_call(|endpoint| async move {
+                let endpoint = &self.endpoints[endpoint];
+                endpoint
+                    .request("state_getKeys", (prefix, block_hash))
+                    .await
+                    .map_err(|e| ElectionError::RpcError {
+                        message: format!("Failed to query storage keys: {}", e),
+                        url: endpoint.url.clone(),
+                    })
+            })
+            .await?;
         
         // Parse the response - should be an array of hex strings
         let keys_array = value.as_array().ok_or_else(|| ElectionError::RpcError {
             message: "Invalid storage keys response (not an array)".to_string(),
-            url: self.url.clone(),
+            url: self.url().to_string(),
         })?;
         
         let prefix_normalized = prefix.trim_start_matches("0x");
@@ -1304,39 +1917,246 @@ impl RpcLoader {
             }
         }
         
+        self.cache_key_listing(prefix, block_hash, &result, true);
         Ok(result)
     }
-    
-    /// Get storage value for a given key
-    async fn get_storage_value(&self, key: &str, block_hash: &str) -> Result<Option<Vec<u8>>, ElectionError> {
-        let response: Value = self
-            .client
-            .request(
-                "state_getStorage",
-                (key, block_hash),
-            )
-            .await
-            .map_err(|e| ElectionError::RpcError {
-                message: format!("Failed to query storage value: {}", e),
-                url: self.url.clone(),
-            })?;
-        
-        if response.is_null() {
-            return Ok(None);
+
+    /// Get the cached key listing for a prefix, or an empty partial listing
+    fn cached_key_listing(&self, prefix: &str, block_hash: &str) -> KeyListing {
+        let Some(cache) = &self.cache else {
+            return KeyListing::default();
+        };
+        match cache.key_listing(block_hash, prefix) {
+            Ok(listing) => listing.unwrap_or_default(),
+            Err(e) => {
+                eprintln!("  ⚠ Warning: Ignoring unreadable cached key listing: {}", e);
+                KeyListing::default()
+            }
         }
-        
-        let hex_str = response.as_str().ok_or_else(|| ElectionError::RpcError {
-            message: "Storage value is not a string".to_string(),
-            url: self.url.clone(),
-        })?;
-        
-        let hex_str = hex_str.trim_start_matches("0x");
-        let bytes = hex::decode(hex_str).map_err(|e| ElectionError::RpcError {
+    }
+
+    /// Store a key listing in the cache, if one is configured
+    ///
+    /// Cache write failures are reported but never fail the fetch.
+    fn cache_key_listing(&self, prefix: &str, block_hash: &str, keys: &[String], complete: bool) {
+        if let Some(cache) = &self.cache {
+            let listing = KeyListing {
+                keys: keys.to_vec(),
+                complete,
+            };
+            if let Err(e) = cache.store_key_listing(block_hash, prefix, &listing) {
+                eprintln!("  ⚠ Warning: Failed to cache storage keys: {}", e);
+            }
+        }
+    }
+
+    /// Get the hex-encoded storage value for a key, serving it from the cache when possible
+    pub(super) async fn get_storage_hex(&self, key: &str, block_hash: &str) -> Result<Option<String>, ElectionError> {
+        if let Some(cache) = &self.cache {
+            if let Ok(Some(cached)) = cache.value(block_hash, key) {
+                return Ok(cached);
+            }
+        }
+
+        let value = self
+            .retry_rpc_call(|endpoint| async move {
+                let endpoint = &self.endpoints[endpoint];
+                let response: Value = endpoint
+                    .request(
+                        "state_getStorage",
+                        (key, block_hash),
+                    )
+                    .await
+                    .map_err(|e| ElectionError::RpcError {
+                        message: format!("Failed to query storage value: {}", e),
+                        url: endpoint.url.clone(),
+                    })?;
+
+                if response.is_null() {
+                    return Ok(None);
+                }
+                let hex_str = response.as_str().ok_or_else(|| ElectionError::RpcError {
+                    message: "Storage value is not a string".to_string(),
+                    url: endpoint.url.clone(),
+                })?;
+                Ok(Some(hex_str.to_string()))
+            })
+            .await?;
+
+        if let Some(cache) = &self.cache {
+            if let Err(e) = cache.store_value(block_hash, key, value.as_deref()) {
+                eprintln!("  ⚠ Warning: Failed to cache storage value: {}", e);
+            }
+        }
+
+        Ok(value)
+    }
+    
+    /// Decode a hex-encoded storage value into bytes
+    pub(super) fn decode_storage_hex(&self, hex_str: &str) -> Result<Vec<u8>, ElectionError> {
+        hex::decode(hex_str.trim_start_matches("0x")).map_err(|e| ElectionError::RpcError {
             message: format!("Failed to decode hex: {}", e),
-            url: self.url.clone(),
-        })?;
-        
-        Ok(Some(bytes))
+            url: self.url().to_string(),
+        })
+    }
+
+    /// Get hex-encoded storage values for many keys
+    ///
+    /// Cached values are served from disk. The remaining keys are split into
+    /// batches of `batch_size` and fetched with `state_queryStorageAt`, keeping
+    /// at most `concurrency` requests in flight. Batches are only issued as
+    /// earlier ones complete, so a slow endpoint applies backpressure instead
+    /// of queueing thousands of requests.
+    pub(super) async fn get_storage_values(
+        &self,
+        keys: &[String],
+        block_hash: &str,
+    ) -> Result<HashMap<String, Option<String>>, ElectionError> {
+        let started = std::time::Instant::now();
+        let mut values = HashMap::with_capacity(keys.len());
+        let mut missing = Vec::new();
+
+        for key in keys {
+            match self.cache.as_ref().and_then(|cache| cache.value(block_hash, key).ok().flatten()) {
+                Some(value) => {
+                    values.insert(key.clone(), value);
+                }
+                None => missing.push(key.clone()),
+            }
+        }
+
+        let from_cache = values.len();
+        let batch_count = (missing.len() + self.batch_size - 1) / self.batch_size;
+        if from_cache > 0 {
+            eprintln!("    → {} of {} storage values served from cache", from_cache, keys.len());
+        }
+
+        // Futures do nothing until polled, so building them all up front is cheap
+        let requests: Vec<_> = missing
+            .chunks(self.batch_size)
+            .map(|batch| self.query_storage_batch(batch, block_hash))
+            .collect();
+        let mut batches = futures::stream::iter(requests).buffer_unordered(self.concurrency);
+
+        let mut batches_done = 0;
+        let mut fetched = 0;
+        while let Some(batch) = batches.next().await {
+            for (key, value) in batch? {
+                if let Some(cache) = &self.cache {
+                    if let Err(e) = cache.store_value(block_hash, &key, value.as_deref()) {
+                        eprintln!("  ⚠ Warning: Failed to cache storage value: {}", e);
+                    }
+                }
+                values.insert(key, value);
+                fetched += 1;
+            }
+
+            batches_done += 1;
+            if batches_done % 20 == 0 && batches_done < batch_count {
+                eprintln!(
+                    "    → Fetched {}/{} storage values ({:.0} keys/s)",
+                    fetched,
+                    missing.len(),
+                    fetched as f64 / started.elapsed().as_secs_f64().max(f64::EPSILON)
+                );
+            }
+        }
+
+        let elapsed = started.elapsed().as_secs_f64();
+        if fetched > 0 {
+            eprintln!(
+                "    ✓ Fetched {} storage values in {} batches over {:.1}s ({:.0} keys/s, concurrency {})",
+                fetched,
+                batch_count,
+                elapsed,
+                fetched as f64 / elapsed.max(f64::EPSILON),
+                self.concurrency
+            );
+        }
+
+        Ok(values)
+    }
+
+    /// Fetch one batch of storage values with `state_queryStorageAt`
+    ///
+    /// Falls back to one `state_getStorage` call per key if the endpoint does
+    /// not support `state_queryStorageAt`. Keys missing from the response
+    /// (pruned or lagging nodes may answer partially) are re-queried with
+    /// `state_getStorage`, so only values the node returned are reported as
+    /// absent and cached.
+    async fn query_storage_batch(
+        &self,
+        keys: &[String],
+        block_hash: &str,
+    ) -> Result<Vec<(String, Option<String>)>, ElectionError> {
+        if !self.batch_queries_supported.load(Ordering::Relaxed) {
+            return self.query_storage_individually(keys, block_hash).await;
+        }
+
+        let response = self
+            .retry_rpc_call(|endpoint| async move {
+                let endpoint = &self.endpoints[endpoint];
+                endpoint
+                    .request::<Value, _>("state_queryStorageAt", (keys, block_hash))
+                    .await
+                    .map_err(|e| ElectionError::RpcError {
+                        message: format!("Failed to query storage batch: {}", e),
+                        url: endpoint.url.clone(),
+                    })
+            })
+            .await;
+
+        let response = match response {
+            Ok(response) => response,
+            Err(e) if self.is_retryable_error(&e) => return Err(e),
+            Err(_) => {
+                // Endpoint doesn't support batched queries, fetch keys one by one
+                if self.batch_queries_supported.swap(false, Ordering::Relaxed) {
+                    eprintln!("    ⚠ state_queryStorageAt not supported, falling back to state_getStorage per key");
+                }
+                return self.query_storage_individually(keys, block_hash).await;
+            }
+        };
+
+        // state_queryStorageAt returns an array of StorageChangeSet:
+        // [{ "block": hash, "changes": [[key, value | null], ...] }]
+        let mut changes: HashMap<String, Option<String>> = HashMap::with_capacity(keys.len());
+        for change_set in response.as_array().into_iter().flatten() {
+            for change in change_set.get("changes").and_then(|c| c.as_array()).into_iter().flatten() {
+                if let Some([key, value]) = change.as_array().map(Vec::as_slice) {
+                    if let Some(key) = key.as_str() {
+                        changes.insert(key.to_lowercase(), value.as_str().map(str::to_string));
+                    }
+                }
+            }
+        }
+
+        let mut values = Vec::with_capacity(keys.len());
+        let mut unanswered = Vec::new();
+        for key in keys {
+            match changes.remove(&key.to_lowercase()) {
+                Some(value) => values.push((key.clone(), value)),
+                None => unanswered.push(key.clone()),
+            }
+        }
+        if !unanswered.is_empty() {
+            values.extend(self.query_storage_individually(&unanswered, block_hash).await?);
+        }
+        Ok(values)
+    }
+
+    /// Fetch a batch of storage values with one `state_getStorage` call per key
+    async fn query_storage_individually(
+        &self,
+        keys: &[String],
+        block_hash: &str,
+    ) -> Result<Vec<(String, Option<String>)>, ElectionError> {
+        let mut values = Vec::with_capacity(keys.len());
+        for key in keys {
+            let value = self.get_storage_hex(key, block_hash).await?;
+            values.push((key.clone(), value));
+        }
+        Ok(values)
     }
     
     /// Decode AccountId from a storage key
@@ -1354,19 +2174,19 @@ impl RpcLoader {
                     "Storage key is exactly the prefix (not a valid entry). Key length: {} bytes",
                     key_normalized.len() / 2
                 ),
-                url: self.url.clone(),
+                url: self.url().to_string(),
             });
         }
         
         // Decode hex strings
         let key_bytes = hex::decode(key_normalized).map_err(|e| ElectionError::RpcError {
             message: format!("Failed to decode key hex: {}", e),
-            url: self.url.clone(),
+            url: self.url().to_string(),
         })?;
         
         let prefix_bytes = hex::decode(prefix_normalized).map_err(|e| ElectionError::RpcError {
             message: format!("Failed to decode prefix hex: {}", e),
-            url: self.url.clone(),
+            url: self.url().to_string(),
         })?;
         
         // Ensure the key starts with the prefix
@@ -1377,14 +2197,14 @@ impl RpcLoader {
                     key_bytes.len(),
                     prefix_bytes.len()
                 ),
-                url: self.url.clone(),
+                url: self.url().to_string(),
             });
         }
         
         if &key_bytes[..prefix_bytes.len()] != prefix_bytes.as_slice() {
             return Err(ElectionError::RpcError {
                 message: "Storage key does not start with expected prefix".to_string(),
-                url: self.url.clone(),
+                url: self.url().to_string(),
             });
         }
         
@@ -1401,7 +2221,7 @@ impl RpcLoader {
                     hash_length,
                     key_bytes.len()
                 ),
-                url: self.url.clone(),
+                url: self.url().to_string(),
             });
         }
         
@@ -1427,27 +2247,30 @@ impl RpcLoader {
         // For simplicity, assume targets is the first field
         // If not, we may need to skip some bytes based on the actual structure
         
-        // Try to find the Vec<AccountId> encoding
-        // Look for compact length encoding
-        if let Ok((len, len_bytes)) = self.decode_compact_u32(&bytes[offset..]) {
-            offset += len_bytes;
-            
-            // Each AccountId is 32 bytes
-            let account_id_size = 32;
-            let expected_size = offset + (len as usize * account_id_size);
-            
-            if bytes.len() >= expected_size {
-                // Decode each AccountId
-                for i in 0..len {
-                    let start = offset + (i as usize * account_id_size);
-                    let end = start + account_id_size;
-                    if end <= bytes.len() {
-                        let account_id_bytes = &bytes[start..end];
-                        let account_id_hex = format!("0x{}", hex::encode(account_id_bytes));
-                        targets.push(account_id_hex);
-                    }
-                }
-            }
+        // Decode the Vec<AccountId> length prefix
+        let (len, len_bytes) = self.decode_compact_u32(&bytes[offset..])?;
+        offset += len_bytes;
+
+        // Each AccountId is 32 bytes
+        let account_id_size = 32;
+        let expected_size = offset + (len as usize * account_id_size);
+        if bytes.len() < expected_size {
+            return Err(ElectionError::RpcError {
+                message: format!(
+                    "Nominations data too short for {} targets. Expected at least {} bytes, got {}",
+                    len,
+                    expected_size,
+                    bytes.len()
+                ),
+                url: self.url().to_string(),
+            });
+        }
+
+        // Decode each AccountId
+        for i in 0..len {
+            let start = offset + (i as usize * account_id_size);
+            let end = start + account_id_size;
+            targets.push(format!("0x{}", hex::encode(&bytes[start..end])));
         }
         
         Ok(targets)
@@ -1460,7 +2283,7 @@ impl RpcLoader {
         if bytes.len() < 32 {
             return Err(ElectionError::RpcError {
                 message: "StakingLedger data too short".to_string(),
-                url: self.url.clone(),
+                url: self.url().to_string(),
             });
         }
         
@@ -1476,7 +2299,7 @@ impl RpcLoader {
             // Some chains might have different structures
             return Err(ElectionError::RpcError {
                 message: "StakingLedger data incomplete".to_string(),
-                url: self.url.clone(),
+                url: self.url().to_string(),
             });
         }
         
@@ -1490,6 +2313,15 @@ impl RpcLoader {
     }
 }
 
+/// Twox64Concat map key hasher: `twox64(data) ++ data`
+pub(super) fn twox_64_concat(data: &[u8]) -> Vec<u8> {
+    let mut hasher = XxHash64::with_seed(0);
+    hasher.write(data);
+    let mut result = hasher.finish().to_le_bytes().to_vec();
+    result.extend_from_slice(data);
+    result
+}
+
 /// Re-implementation of Substrate's twox_128 hashing combinator.
 /// Uses two XxHash64 computations with seeds 0 and 1 and concatenates the outputs.
 fn twox_128_hash(data: &[u8]) -> [u8; 16] {
@@ -0,0 +1,272 @@
+//! Asset Hub (`pallet-staking-async`) data source for [`RpcLoader`]
+//!
+//! Polkadot and Kusama staking lives on Asset Hub, where elections are run by
+//! the multi-block election provider (`MultiBlockElection`). While an election
+//! is in progress, it keeps a paged snapshot of the electable targets
+//! (`PagedTargetSnapshot`) and of the voters with their vote weights
+//! (`PagedVoterSnapshot`). That snapshot is exactly what the election runs on,
+//! so it is preferred. Outside the election window the live `Staking` maps are
+//! read instead.
+//!
+//! Unlike the relay-chain layout, there is no `Session::Validators` to read the
+//! candidates from: they come from the snapshot or from `Staking::Validators`.
+
+use crate::error::ElectionError;
+use crate::input::rpc::{LoadReport, RpcLoader};
+use crate::models::nominator::Nominator;
+use crate::models::validator::ValidatorCandidate;
+use parity_scale_codec::Decode;
+
+/// Pallet running elections on Asset Hub
+const ELECTION_PALLET: &str = "MultiBlockElection";
+
+/// `AccountId32`
+type AccountId = [u8; 32];
+
+/// Voter in `PagedVoterSnapshot`: `(who, vote weight, targets)`
+type SnapshotVoter = (AccountId, u64, Vec<AccountId>);
+
+/// Leading fields of `StakingLedger`, which is all the loader needs
+#[derive(Decode)]
+struct LedgerPrefix {
+    stash: AccountId,
+    #[codec(compact)]
+    total: u128,
+}
+
+fn account_hex(account: &AccountId) -> String {
+    format!("0x{}", hex::encode(account))
+}
+
+/// Account at the end of a `*Concat`-hashed map key
+pub(super) fn account_from_key(key: &str) -> Option<String> {
+    let bytes = hex::decode(key.trim_start_matches("0x")).ok()?;
+    let account = bytes.len().checked_sub(32).map(|start| &bytes[start..])?;
+    Some(format!("0x{}", hex::encode(account)))
+}
+
+impl RpcLoader {
+    /// Fetch electable targets on Asset Hub
+    pub(super) async fn fetch_asset_hub_candidates(
+        &self,
+        block_hash: &str,
+        report: &mut LoadReport,
+    ) -> Result<Vec<ValidatorCandidate>, ElectionError> {
+        let pages = self.snapshot_pages("PagedTargetSnapshot", block_hash, report).await?;
+        if !pages.is_empty() {
+            let mut candidates = Vec::new();
+            for (key, value) in pages {
+                match Vec::<AccountId>::decode(&mut value.as_slice()) {
+                    Ok(targets) => candidates.extend(
+                        targets.iter().map(|target| ValidatorCandidate::new(account_hex(target), 0)),
+                    ),
+                    Err(e) => {
+                        eprintln!("    ⚠ Failed to decode target snapshot page: {}", e);
+                        report.undecodable_keys.push(key);
+                    }
+                }
+            }
+            eprintln!("    → Read {} targets from the election snapshot", candidates.len());
+            return Ok(candidates);
+        }
+
+        // No election in progress, list validator intentions instead
+        let validators_prefix = self.encode_storage_key("Staking", "Validators")?;
+        let keys = self.list_storage_keys(&validators_prefix, block_hash).await?;
+        let mut candidates = Vec::with_capacity(keys.len());
+        for key in keys {
+            match account_from_key(&key) {
+                Some(account) => candidates.push(ValidatorCandidate::new(account, 0)),
+                None => report.undecodable_keys.push(key),
+            }
+        }
+
+        if candidates.is_empty() {
+            return Err(ElectionError::RpcError {
+                message: format!(
+                    "No validators found in Staking::Validators or the election snapshot. Block hash: {}",
+                    block_hash
+                ),
+                url: self.url().to_string(),
+            });
+        }
+        Ok(candidates)
+    }
+
+    /// Fetch voters on Asset Hub
+    ///
+    /// Snapshot voters carry the vote weight the election uses as their stake,
+    /// and include validators voting for themselves.
+    pub(super) async fn fetch_asset_hub_nominators(
+        &self,
+        block_hash: &str,
+        report: &mut LoadReport,
+    ) -> Result<Vec<Nominator>, ElectionError> {
+        let pages = self.snapshot_pages("PagedVoterSnapshot", block_hash, report).await?;
+        if !pages.is_empty() {
+            let mut nominators = Vec::new();
+            for (key, value) in pages {
+                match Vec::<SnapshotVoter>::decode(&mut value.as_slice()) {
+                    Ok(voters) => nominators.extend(voters.into_iter().map(|(who, weight, targets)| {
+                        let mut nominator = Nominator::new(account_hex(&who), weight as u128);
+                        nominator.targets = targets.iter().map(account_hex).collect();
+                        nominator
+                    })),
+                    Err(e) => {
+                        eprintln!("    ⚠ Failed to decode voter snapshot page: {}", e);
+                        report.undecodable_keys.push(key);
+                    }
+                }
+            }
+            eprintln!("    → Read {} voters from the election snapshot", nominators.len());
+            report.nominators = nominators.len();
+            report.voters_from_snapshot = true;
+            return Ok(nominators);
+        }
+
+        self.fetch_live_asset_hub_nominators(block_hash, report).await
+    }
+
+    /// Read nominators from the live `Staking::Nominators` and `Staking::Ledger` maps
+    ///
+    /// Ledgers are matched to nominators by the stash recorded in the ledger
+    /// itself, so the (deprecated) controller account never matters.
+    async fn fetch_live_asset_hub_nominators(
+        &self,
+        block_hash: &str,
+        report: &mut LoadReport,
+    ) -> Result<Vec<Nominator>, ElectionError> {
+        let nominator_keys = self
+            .list_storage_keys(&self.encode_storage_key("Staking", "Nominators")?, block_hash)
+            .await?;
+        let ledger_keys = self
+            .list_storage_keys(&self.encode_storage_key("Staking", "Ledger")?, block_hash)
+            .await?;
+
+        let all_keys: Vec<String> = nominator_keys.iter().chain(&ledger_keys).cloned().collect();
+        let mut values = self.get_storage_values(&all_keys, block_hash).await?;
+        let mut undecodable = Vec::new();
+
+        let mut stakes = std::collections::HashMap::with_capacity(ledger_keys.len());
+        for key in ledger_keys {
+            let ledger = values
+                .remove(&key)
+                .flatten()
+                .and_then(|hex| self.decode_storage_hex(&hex).ok())
+                .and_then(|bytes| LedgerPrefix::decode(&mut bytes.as_slice()).ok());
+            match ledger {
+                Some(ledger) => {
+                    stakes.insert(account_hex(&ledger.stash), ledger.total);
+                }
+                None => undecodable.push(key),
+            }
+        }
+
+        let mut nominators = Vec::with_capacity(nominator_keys.len());
+        for key in nominator_keys {
+            // Nominations { targets, submitted_in, suppressed }, targets first
+            let targets = values
+                .remove(&key)
+                .flatten()
+                .and_then(|hex| self.decode_storage_hex(&hex).ok())
+                .and_then(|bytes| Vec::<AccountId>::decode(&mut bytes.as_slice()).ok());
+            let (Some(account), Some(targets)) = (account_from_key(&key), targets) else {
+                undecodable.push(key);
+                continue;
+            };
+            report.nominators += 1;
+            if targets.is_empty() {
+                continue;
+            }
+
+            let mut nominator = Nominator::new(account.clone(), stakes.get(&account).copied().unwrap_or(0));
+            nominator.targets = targets.iter().map(account_hex).collect();
+            nominators.push(nominator);
+        }
+
+        if !undecodable.is_empty() {
+            eprintln!("    ⚠ {} staking entries could not be decoded", undecodable.len());
+        }
+        report.undecodable_keys.extend(undecodable);
+        Ok(nominators)
+    }
+
+    /// Fetch all pages of a `MultiBlockElection` snapshot for the current round
+    ///
+    /// Returns `(key, value)` pairs, empty when no snapshot exists.
+    async fn snapshot_pages(
+        &self,
+        item: &str,
+        block_hash: &str,
+        report: &mut LoadReport,
+    ) -> Result<Vec<(String, Vec<u8>)>, ElectionError> {
+        let round_key = self.encode_storage_key(ELECTION_PALLET, "Round")?;
+        let round = self.read_storage_u32(&round_key, block_hash).await?.unwrap_or(0);
+
+        // Pages are keyed by (round, page), both Twox64Concat
+        let round_prefix = format!(
+            "{}{}",
+            self.encode_storage_key(ELECTION_PALLET, item)?,
+            hex::encode(super::rpc::twox_64_concat(&round.to_le_bytes()))
+        );
+        let keys = self.list_storage_keys(&round_prefix, block_hash).await?;
+        if keys.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut values = self.get_storage_values(&keys, block_hash).await?;
+        let mut pages = Vec::with_capacity(keys.len());
+        for key in keys {
+            match values.remove(&key).flatten().map(|hex| self.decode_storage_hex(&hex)) {
+                Some(Ok(bytes)) => pages.push((key, bytes)),
+                _ => report.undecodable_keys.push(key),
+            }
+        }
+        Ok(pages)
+    }
+
+    /// Find the first block from `plan_block` that holds the complete election snapshot
+    ///
+    /// The target snapshot is taken first, then voter pages from the most
+    /// significant page down to page 0, so the snapshot is complete once page 0
+    /// of the round's `PagedVoterSnapshot` is stored. It stays until the round
+    /// is elected and `Round` increments, so `(Round, page 0 stored)` never
+    /// decreases over time. Returns `None` if the round of `plan_block` never
+    /// stored a complete snapshot.
+    pub(super) async fn find_asset_hub_snapshot_block(
+        &self,
+        plan_block: u64,
+        latest_block: u64,
+    ) -> Result<Option<u64>, ElectionError> {
+        let round_key = self.encode_storage_key(ELECTION_PALLET, "Round")?;
+        let voter_prefix = self.encode_storage_key(ELECTION_PALLET, "PagedVoterSnapshot")?;
+        let progress_at = |block_number: u64| {
+            let (round_key, voter_prefix) = (&round_key, &voter_prefix);
+            async move {
+                let block_hash = self.get_block_hash(block_number).await?;
+                let round = self.read_storage_u32(round_key, &block_hash).await?.unwrap_or(0);
+                let last_page_key = format!(
+                    "{}{}{}",
+                    voter_prefix,
+                    hex::encode(super::rpc::twox_64_concat(&round.to_le_bytes())),
+                    hex::encode(super::rpc::twox_64_concat(&0u32.to_le_bytes()))
+                );
+                let complete = self.get_storage_hex(&last_page_key, &block_hash).await?.is_some();
+                Ok::<_, ElectionError>(round as u64 * 2 + u64::from(complete))
+            }
+        };
+
+        let progress = progress_at(plan_block).await?;
+        let block = if progress % 2 == 1 {
+            plan_block
+        } else {
+            self.find_first_block("the election snapshot", progress + 1, latest_block, progress_at)
+                .await?
+        };
+        if progress_at(block).await? != progress | 1 {
+            eprintln!("  ⚠ Warning: No election snapshot was stored for round {}, using the live staking state", progress / 2);
+            return Ok(None);
+        }
+        Ok(Some(block))
+    }
+}
@@ -39,15 +39,21 @@
 //! - [`algorithms`] - Election algorithm implementations
 //! - [`diagnostics`] - Result analysis and explanations
 //! - [`error`] - Error types
+//! - [`schema`] - JSON Schemas of the input and output formats and validation against them
+//! - `arrow` - Arrow record batches and Parquet files (with the `arrow` feature)
 
 pub mod algorithms;
 pub mod api;
+#[cfg(feature = "arrow")]
+pub mod arrow;
 pub mod cli;
 pub mod diagnostics;
 pub mod engine;
 pub mod error;
 pub mod input;
 pub mod models;
+pub mod schema;
+pub mod session;
 pub mod types;
 
 // Re-export commonly used types
@@ -58,6 +64,12 @@ pub mod types;
 /// and call [`execute`](ElectionEngine::execute) with a configuration and data.
 pub use engine::ElectionEngine;
 
+/// Election data prepared for repeated runs
+///
+/// Indexes the data once so that what-if runs with different overrides skip
+/// the copying and lookups [`ElectionEngine::execute`] does on every call.
+pub use session::ElectionSession;
+
 /// Error type for election operations
 ///
 /// All operations return `Result<T, ElectionError>` to handle validation errors,
@@ -82,6 +94,18 @@ pub use error::ElectionError;
 /// ```
 pub use models::election_config::ElectionConfiguration;
 
+/// Network parameters of a chain
+///
+/// Election limits, minimum bonds, address format and token of a network.
+/// Presets exist for Polkadot, Kusama and Westend.
+pub use models::chain_profile::ChainProfile;
+
+/// Account ID parsed from hex or SS58
+///
+/// Normalized to 32 bytes; [`AddressFormat`] renders it as hex or SS58 with
+/// any network prefix.
+pub use models::account_id::{AccountId, AddressFormat};
+
 /// Election data containing candidates and nominators
 ///
 /// Contains all validator candidates, nominators, and their voting preferences.
@@ -115,6 +139,12 @@ pub use models::election_result::ElectionResult;
 /// without modifying the original data source.
 pub use models::election_overrides::ElectionOverrides;
 
+/// What-if scenarios run against a base dataset
+///
+/// A scenario set names override sets to compare against the unmodified
+/// baseline; the report holds each result and its differences.
+pub use models::scenario::{ScenarioReport, ScenarioSet};
+
 /// Nominator model
 ///
 /// Represents an account that stakes tokens and votes for validator candidates.
@@ -1,7 +1,10 @@
 //! CLI binary entry point for the Offline NPoS Election Tool
 
 use clap::Parser;
-use offline_election::cli::commands::{RunCommand, ServerCommand};
+use offline_election::cli::commands::{
+    CacheCommand, ConvertCommand, MatrixCommand, RunCommand, ScenarioCommand, SchemaCommand, ServerCommand, SweepCommand,
+    ValidateCommand,
+};
 
 #[derive(Parser)]
 #[command(name = "offline-election")]
@@ -14,9 +17,23 @@ struct Cli {
 #[derive(clap::Subcommand)]
 enum Command {
     /// Run an election simulation
-    Run(RunCommand),
+    Run(Box<RunCommand>),
     /// Start the REST API server
     Server(ServerCommand),
+    /// Inspect or purge the RPC storage cache
+    Cache(CacheCommand),
+    /// Compare what-if scenarios against a baseline
+    Scenario(ScenarioCommand),
+    /// Compare algorithms across active set sizes
+    Matrix(MatrixCommand),
+    /// Chart backing against active set size
+    Sweep(SweepCommand),
+    /// Convert election data between JSON, binary snapshots, CSV and Parquet
+    Convert(ConvertCommand),
+    /// Print the JSON Schema of an input or output format
+    Schema(SchemaCommand),
+    /// Check a JSON file against its schema and report every problem
+    Validate(ValidateCommand),
 }
 
 #[tokio::main]
@@ -36,6 +53,48 @@ async fn main() {
                 std::process::exit(1);
             }
         }
+        Command::Cache(cmd) => {
+            if let Err(e) = cmd.execute().await {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Command::Scenario(cmd) => {
+            if let Err(e) = cmd.execute().await {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Command::Matrix(cmd) => {
+            if let Err(e) = cmd.execute().await {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Command::Sweep(cmd) => {
+            if let Err(e) = cmd.execute().await {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Command::Convert(cmd) => {
+            if let Err(e) = cmd.execute().await {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Command::Schema(cmd) => {
+            if let Err(e) = cmd.execute().await {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Command::Validate(cmd) => {
+            if let Err(e) = cmd.execute().await {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        }
     }
 }
 
@@ -0,0 +1,187 @@
+//! Account ID model with SS58 and hex support
+
+use crate::error::ElectionError;
+use blake2::{Blake2b512, Digest};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Prefix hashed into SS58 checksums
+const SS58_CHECKSUM_PREFIX: &[u8] = b"SS58PRE";
+
+/// Shortest SS58 string treated as an address rather than a label
+const MIN_SS58_LEN: usize = 40;
+
+/// 32-byte account ID
+///
+/// Parses `0x`-prefixed hex and SS58 addresses of any network, validating the
+/// SS58 checksum, and renders in either form.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct AccountId([u8; 32]);
+
+impl AccountId {
+    /// Create an account ID from raw bytes
+    pub fn new(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+
+    /// Raw bytes of the account
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    /// `0x`-prefixed hex form
+    pub fn to_hex(&self) -> String {
+        format!("0x{}", hex::encode(self.0))
+    }
+
+    /// SS58 form for the network with the given address prefix
+    pub fn to_ss58(&self, prefix: u16) -> String {
+        let mut bytes = match prefix {
+            0..=63 => vec![prefix as u8],
+            _ => vec![
+                (((prefix & 0b1111_1100) >> 2) as u8) | 0b0100_0000,
+                ((prefix >> 8) as u8) | (((prefix & 0b11) as u8) << 6),
+            ],
+        };
+        bytes.extend_from_slice(&self.0);
+        let checksum = ss58_checksum(&bytes);
+        bytes.extend_from_slice(&checksum[..2]);
+        bs58::encode(bytes).into_string()
+    }
+
+    /// Parse an SS58 address, returning the account and its network prefix
+    pub fn from_ss58(address: &str) -> Result<(Self, u16), ElectionError> {
+        let invalid = |reason: &str| ElectionError::ValidationError {
+            message: format!("Invalid SS58 address '{}': {}", address, reason),
+            field: Some("account_id".to_string()),
+        };
+        let bytes = bs58::decode(address).into_vec().map_err(|_| invalid("not base58"))?;
+        let (prefix, prefix_len) = match bytes.first() {
+            Some(&first) if first < 64 => (first as u16, 1),
+            Some(&first) if first < 128 && bytes.len() > 1 => {
+                let lower = (first << 2) | (bytes[1] >> 6);
+                let upper = bytes[1] & 0b0011_1111;
+                (lower as u16 | ((upper as u16) << 8), 2)
+            }
+            _ => return Err(invalid("unsupported address prefix")),
+        };
+        if bytes.len() != prefix_len + 32 + 2 {
+            return Err(invalid("not a 32-byte account"));
+        }
+        let (body, checksum) = bytes.split_at(prefix_len + 32);
+        if ss58_checksum(body)[..2] != *checksum {
+            return Err(invalid("checksum mismatch"));
+        }
+        let mut account = [0u8; 32];
+        account.copy_from_slice(&body[prefix_len..]);
+        Ok((Self(account), prefix))
+    }
+
+    /// Parse an ID if it is an address rather than a free-form label
+    ///
+    /// Strings starting with `0x` are hex addresses and must be valid. Long
+    /// base58 strings are SS58 addresses if they decode with a valid checksum;
+    /// placeholder IDs that do not, and anything else (e.g. `validator-1` in
+    /// synthetic data), are labels and yield `None`.
+    pub fn parse_address(id: &str) -> Result<Option<(Self, AddressFormat)>, ElectionError> {
+        if id.starts_with("0x") {
+            let account = id.parse::<Self>()?;
+            return Ok(Some((account, AddressFormat::Hex)));
+        }
+        if id.len() < MIN_SS58_LEN {
+            return Ok(None);
+        }
+        Ok(Self::from_ss58(id)
+            .ok()
+            .map(|(account, prefix)| (account, AddressFormat::Ss58(prefix))))
+    }
+
+    /// Key identifying the account behind an ID, independent of its format
+    ///
+    /// Addresses map to their hex form; labels map to themselves.
+    pub fn canonical(id: &str) -> Result<String, ElectionError> {
+        Ok(match Self::parse_address(id)? {
+            Some((account, _)) => account.to_hex(),
+            None => id.to_string(),
+        })
+    }
+}
+
+fn ss58_checksum(data: &[u8]) -> [u8; 64] {
+    let mut hasher = Blake2b512::new();
+    hasher.update(SS58_CHECKSUM_PREFIX);
+    hasher.update(data);
+    hasher.finalize().into()
+}
+
+impl std::str::FromStr for AccountId {
+    type Err = ElectionError;
+
+    /// Parse `0x`-prefixed hex or an SS58 address
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(hex_str) = s.strip_prefix("0x") {
+            let bytes = hex::decode(hex_str).ok().and_then(|bytes| <[u8; 32]>::try_from(bytes).ok());
+            return bytes.map(Self).ok_or_else(|| ElectionError::ValidationError {
+                message: format!("Invalid hex account ID '{}': expected 32 bytes", s),
+                field: Some("account_id".to_string()),
+            });
+        }
+        Self::from_ss58(s).map(|(account, _)| account)
+    }
+}
+
+impl std::fmt::Display for AccountId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.to_hex())
+    }
+}
+
+/// How account IDs are written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum AddressFormat {
+    /// `0x`-prefixed hex
+    Hex,
+    /// SS58 with the given network prefix
+    Ss58(u16),
+}
+
+impl AddressFormat {
+    /// Render an account in this format
+    pub fn render(&self, account: &AccountId) -> String {
+        match self {
+            AddressFormat::Hex => account.to_hex(),
+            AddressFormat::Ss58(prefix) => account.to_ss58(*prefix),
+        }
+    }
+
+    /// Rewrite an ID in this format, leaving labels untouched
+    pub fn normalize(&self, id: &str) -> Result<String, ElectionError> {
+        Ok(match AccountId::parse_address(id)? {
+            Some((account, _)) => self.render(&account),
+            None => id.to_string(),
+        })
+    }
+}
+
+impl std::str::FromStr for AddressFormat {
+    type Err = String;
+
+    /// Parse `hex`, a network name (polkadot, kusama, westend, substrate) or
+    /// an SS58 prefix number
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "hex" => Ok(AddressFormat::Hex),
+            "polkadot" => Ok(AddressFormat::Ss58(0)),
+            "kusama" => Ok(AddressFormat::Ss58(2)),
+            "westend" | "substrate" | "ss58" => Ok(AddressFormat::Ss58(42)),
+            other => match other.parse::<u16>() {
+                Ok(prefix) if prefix < 16_384 => Ok(AddressFormat::Ss58(prefix)),
+                _ => Err(format!(
+                    "Unknown address format: {} (expected hex, polkadot, kusama, westend, substrate or an SS58 prefix)",
+                    s
+                )),
+            },
+        }
+    }
+}
@@ -0,0 +1,172 @@
+//! Chain profile model with network parameters
+
+use crate::error::ElectionError;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Network parameters that shape an election on a specific chain
+///
+/// Limits set to `None` are unbounded. Built-in presets exist for Polkadot,
+/// Kusama and Westend; other chains can be described in a JSON file or read
+/// from chain constants with
+/// [`RpcLoader::load_chain_profile`](crate::input::RpcLoader::load_chain_profile).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ChainProfile {
+    /// Chain name
+    pub name: String,
+    /// Maximum number of targets a nominator may vote for (`MaxNominations`)
+    pub max_nominations: u32,
+    /// Maximum number of voters in the election snapshot (`MaxElectingVoters`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_electing_voters: Option<u32>,
+    /// Maximum number of validators the election may select (`MaxWinners`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_winners: Option<u32>,
+    /// Maximum number of nominators backing a single validator (`MaxBackersPerWinner`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_backers_per_winner: Option<u32>,
+    /// Minimum bond to nominate (`MinNominatorBond`)
+    #[serde(default)]
+    pub min_nominator_bond: u128,
+    /// Minimum bond to validate (`MinValidatorBond`)
+    #[serde(default)]
+    pub min_validator_bond: u128,
+    /// SS58 address format prefix
+    pub ss58_prefix: u16,
+    /// Number of decimals of the native token
+    pub token_decimals: u8,
+    /// Symbol of the native token
+    pub token_symbol: String,
+}
+
+impl ChainProfile {
+    /// Create a profile with Substrate's development chain defaults and no limits
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            max_nominations: 16,
+            max_electing_voters: None,
+            max_winners: None,
+            max_backers_per_winner: None,
+            min_nominator_bond: 0,
+            min_validator_bond: 0,
+            ss58_prefix: 42,
+            token_decimals: 12,
+            token_symbol: "UNIT".to_string(),
+        }
+    }
+
+    /// Polkadot preset
+    pub fn polkadot() -> Self {
+        Self {
+            name: "Polkadot".to_string(),
+            max_nominations: 16,
+            max_electing_voters: Some(22_500),
+            max_winners: Some(1_200),
+            max_backers_per_winner: Some(22_500),
+            min_nominator_bond: 250 * 10u128.pow(10),
+            min_validator_bond: 0,
+            ss58_prefix: 0,
+            token_decimals: 10,
+            token_symbol: "DOT".to_string(),
+        }
+    }
+
+    /// Kusama preset
+    pub fn kusama() -> Self {
+        Self {
+            name: "Kusama".to_string(),
+            max_nominations: 24,
+            max_electing_voters: Some(12_500),
+            max_winners: Some(2_000),
+            max_backers_per_winner: Some(12_500),
+            min_nominator_bond: 10u128.pow(11),
+            min_validator_bond: 0,
+            ss58_prefix: 2,
+            token_decimals: 12,
+            token_symbol: "KSM".to_string(),
+        }
+    }
+
+    /// Westend preset
+    pub fn westend() -> Self {
+        Self {
+            name: "Westend".to_string(),
+            max_nominations: 16,
+            max_electing_voters: Some(22_500),
+            max_winners: Some(1_000),
+            max_backers_per_winner: Some(22_500),
+            min_nominator_bond: 10u128.pow(12),
+            min_validator_bond: 0,
+            ss58_prefix: 42,
+            token_decimals: 12,
+            token_symbol: "WND".to_string(),
+        }
+    }
+
+    /// Preset for a `system_chain` name, e.g. "Polkadot" or "Kusama Asset Hub"
+    ///
+    /// Only the exact names of the known relay chains and their Asset Hubs
+    /// match; other chains have no preset.
+    pub fn for_chain(chain_name: &str) -> Option<Self> {
+        match chain_name {
+            "Polkadot" | "Polkadot Asset Hub" | "Statemint" => Some(Self::polkadot()),
+            "Kusama" | "Kusama Asset Hub" | "Statemine" => Some(Self::kusama()),
+            "Westend" | "Westend Asset Hub" | "Westmint" => Some(Self::westend()),
+            _ => None,
+        }
+    }
+
+    /// Format a balance in planck as whole tokens, e.g. `1,234.5 DOT`
+    ///
+    /// The amount is exact: every significant decimal is kept.
+    pub fn format_balance(&self, amount: u128) -> String {
+        let unit = 10u128.checked_pow(self.token_decimals as u32);
+        let (whole, fraction) = match unit {
+            Some(unit) => (amount / unit, amount % unit),
+            None => (0, amount),
+        };
+        let digits = whole.to_string();
+        let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+        for (i, digit) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i) % 3 == 0 {
+                grouped.push(',');
+            }
+            grouped.push(digit);
+        }
+        if fraction > 0 {
+            let fraction = format!("{:0width$}", fraction, width = self.token_decimals as usize);
+            grouped.push('.');
+            grouped.push_str(fraction.trim_end_matches('0'));
+        }
+        format!("{} {}", grouped, self.token_symbol)
+    }
+
+    /// Load a profile from a JSON file
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ElectionError> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).map_err(|e| ElectionError::FileError {
+            message: format!("Failed to read chain profile: {}", e),
+            path: path.to_path_buf(),
+        })?;
+        serde_json::from_str(&content).map_err(|e| ElectionError::FileError {
+            message: format!("Failed to parse chain profile: {}", e),
+            path: path.to_path_buf(),
+        })
+    }
+}
+
+impl std::str::FromStr for ChainProfile {
+    type Err = String;
+
+    /// Parse a preset name: polkadot, kusama or westend
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "polkadot" => Ok(Self::polkadot()),
+            "kusama" => Ok(Self::kusama()),
+            "westend" => Ok(Self::westend()),
+            _ => Err(format!("Unknown chain profile: {} (expected polkadot, kusama or westend)", s)),
+        }
+    }
+}
@@ -1,6 +1,8 @@
 //! Election configuration model
 
 use crate::error::ElectionError;
+use crate::models::account_id::AddressFormat;
+use crate::models::chain_profile::ChainProfile;
 use crate::models::election_overrides::ElectionOverrides;
 use crate::types::AlgorithmType;
 use serde::{Deserialize, Serialize};
@@ -18,6 +20,24 @@ pub struct ElectionConfiguration {
     /// Optional block number for RPC snapshot
     #[serde(skip_serializing_if = "Option::is_none")]
     pub block_number: Option<u64>,
+    /// Refuse to run on snapshots known to be incomplete instead of warning
+    #[serde(default)]
+    pub strict_snapshot: bool,
+    /// Fail on the first warning instead of collecting it in the result
+    #[serde(default)]
+    pub strict: bool,
+    /// Nominators with less stake are left out of the election
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_nominator_bond: Option<u128>,
+    /// Candidates with less stake are left out of the election
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_validator_bond: Option<u128>,
+    /// Network parameters; inferred from the data's chain name when not set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chain_profile: Option<ChainProfile>,
+    /// How account IDs are written in results; defaults to the data's format
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address_format: Option<AddressFormat>,
 }
 
 impl ElectionConfiguration {
@@ -28,6 +48,12 @@ impl ElectionConfiguration {
             active_set_size: 100,
             overrides: None,
             block_number: None,
+            strict_snapshot: false,
+            strict: false,
+            min_nominator_bond: None,
+            min_validator_bond: None,
+            chain_profile: None,
+            address_format: None,
         }
     }
 
@@ -55,6 +81,47 @@ impl ElectionConfiguration {
         self
     }
 
+    /// Refuse to run on incomplete snapshots instead of warning
+    pub fn strict_snapshot(mut self, strict: bool) -> Self {
+        self.strict_snapshot = strict;
+        self
+    }
+
+    /// Fail on the first warning instead of collecting it in the result
+    ///
+    /// A reduced active set fails with
+    /// [`InsufficientCandidates`](ElectionError::InsufficientCandidates);
+    /// other warnings fail with a validation error. Implies
+    /// [`strict_snapshot`](Self::strict_snapshot()).
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Leave nominators with less than `bond` stake out of the election
+    pub fn min_nominator_bond(mut self, bond: u128) -> Self {
+        self.min_nominator_bond = Some(bond);
+        self
+    }
+
+    /// Leave candidates with less than `bond` stake out of the election
+    pub fn min_validator_bond(mut self, bond: u128) -> Self {
+        self.min_validator_bond = Some(bond);
+        self
+    }
+
+    /// Set the network parameters of the chain
+    pub fn chain_profile(mut self, profile: ChainProfile) -> Self {
+        self.chain_profile = Some(profile);
+        self
+    }
+
+    /// Write account IDs in results in the given format
+    pub fn address_format(mut self, format: AddressFormat) -> Self {
+        self.address_format = Some(format);
+        self
+    }
+
     /// Build and validate the configuration
     pub fn build(self) -> Result<Self, ElectionError> {
         self.validate()?;
@@ -74,25 +141,35 @@ impl ElectionConfiguration {
             });
         }
 
+        if let Some(max_winners) = self.chain_profile.as_ref().and_then(|p| p.max_winners) {
+            if self.active_set_size > max_winners {
+                return Err(ElectionError::ValidationError {
+                    message: format!(
+                        "Active set size {} exceeds the maximum of {} winners on {}",
+                        self.active_set_size,
+                        max_winners,
+                        self.chain_profile.as_ref().map(|p| p.name.as_str()).unwrap_or_default()
+                    ),
+                    field: Some("active_set_size".to_string()),
+                });
+            }
+        }
+
         // Validate overrides if present
         if let Some(ref overrides) = self.overrides {
             // Check for negative stake values in overrides
             for (account_id, stake) in &overrides.candidate_stakes {
                 if *stake == 0 && account_id.is_empty() {
                     return Err(ElectionError::ValidationError {
-                        message: format!(
-                            "Invalid candidate stake override: account_id cannot be empty"
-                        ),
+                        message: "Invalid candidate stake override: account_id cannot be empty".to_string(),
                         field: Some("overrides.candidate_stakes".to_string()),
                     });
                 }
             }
-            for (account_id, _stake) in &overrides.nominator_stakes {
+            for account_id in overrides.nominator_stakes.keys() {
                 if account_id.is_empty() {
                     return Err(ElectionError::ValidationError {
-                        message: format!(
-                            "Invalid nominator stake override: account_id cannot be empty"
-                        ),
+                        message: "Invalid nominator stake override: account_id cannot be empty".to_string(),
                         field: Some("overrides.nominator_stakes".to_string()),
                     });
                 }
@@ -1,13 +1,16 @@
 //! Election data model containing all candidates, nominators, and voting edges
 
 use crate::error::ElectionError;
+use crate::models::account_id::{AccountId, AddressFormat};
 use crate::models::nominator::Nominator;
 use crate::models::validator::ValidatorCandidate;
+use crate::schema::ValidationIssue;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 /// Complete state needed to run an election
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct ElectionData {
     /// List of validator candidates
     pub candidates: Vec<ValidatorCandidate>,
@@ -19,7 +22,7 @@ pub struct ElectionData {
 }
 
 /// Metadata about the election data source
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct ElectionMetadata {
     /// Block number if data came from RPC
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -27,6 +30,78 @@ pub struct ElectionMetadata {
     /// Chain identifier
     #[serde(skip_serializing_if = "Option::is_none")]
     pub chain: Option<String>,
+    /// Era whose election the data was loaded for
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub era: Option<u32>,
+    /// How much of the on-chain staking state was loaded, if known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completeness: Option<SnapshotCompleteness>,
+    /// Whether candidate stakes were not loaded and are left at zero
+    ///
+    /// Chain loaders do not read validators' own bonds, so the minimum
+    /// validator bond cannot be checked against their candidates.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub candidate_stakes_unknown: bool,
+}
+
+/// Loaded counts compared with the chain's own `CounterFor*` storage items
+///
+/// Expected counts are `None` when the chain does not expose the counter, and
+/// the nominator count is `None` when voters were read from an election
+/// snapshot, which the counter does not describe.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+pub struct SnapshotCompleteness {
+    /// `Staking::CounterForValidators` at the snapshot block
+    pub expected_validators: Option<u32>,
+    /// Number of validator intentions (`Staking::Validators` entries) decoded
+    pub loaded_validators: u32,
+    /// `Staking::CounterForNominators` at the snapshot block
+    pub expected_nominators: Option<u32>,
+    /// Number of `Staking::Nominators` entries decoded, including those without targets
+    pub loaded_nominators: u32,
+    /// Storage keys whose entries could not be fetched or decoded
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub undecodable_keys: Vec<String>,
+}
+
+impl SnapshotCompleteness {
+    /// Whether every expected entry was loaded and decoded
+    pub fn is_complete(&self) -> bool {
+        self.undecodable_keys.is_empty()
+            && self.expected_validators.map_or(true, |n| n == self.loaded_validators)
+            && self.expected_nominators.map_or(true, |n| n == self.loaded_nominators)
+    }
+
+    /// Human-readable list of the ways the snapshot is incomplete
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if let Some(expected) = self.expected_validators.filter(|n| *n != self.loaded_validators) {
+            problems.push(format!("loaded {} of {} validators", self.loaded_validators, expected));
+        }
+        if let Some(expected) = self.expected_nominators.filter(|n| *n != self.loaded_nominators) {
+            problems.push(format!("loaded {} of {} nominators", self.loaded_nominators, expected));
+        }
+        if !self.undecodable_keys.is_empty() {
+            problems.push(format!("{} storage entries could not be decoded", self.undecodable_keys.len()));
+        }
+        problems
+    }
+}
+
+/// Format-independent key of an account ID, reporting invalid addresses against `field`
+fn canonical_id(id: &str) -> Result<String, String> {
+    AccountId::canonical(id).map_err(|e| match e {
+        ElectionError::ValidationError { message, .. } => message,
+        other => other.to_string(),
+    })
+}
+
+fn duplicate_message(kind: &str, existing: &str, duplicate: &str) -> String {
+    if existing == duplicate {
+        format!("Duplicate {} account ID: {}", kind, duplicate)
+    } else {
+        format!("Duplicate {} account ID: {} is the same account as {}", kind, duplicate, existing)
+    }
 }
 
 impl ElectionData {
@@ -67,67 +142,145 @@ impl ElectionData {
 
     /// Validate election data
     pub fn validate(&self) -> Result<(), ElectionError> {
+        match self.validation_issues().into_iter().next() {
+            Some(issue) => Err(issue.into()),
+            None => Ok(()),
+        }
+    }
+
+    /// Every problem [`validate`](Self::validate) would reject, with a JSON pointer to each
+    pub fn validation_issues(&self) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+
         // Must contain at least one validator candidate
         if self.candidates.is_empty() {
-            return Err(ElectionError::ValidationError {
-                message: format!(
-                    "Election data must contain at least one validator candidate, but found {}. Please add at least one candidate.",
-                    self.candidates.len()
-                ),
-                field: Some("candidates".to_string()),
-            });
+            issues.push(ValidationIssue::new(
+                "/candidates",
+                "Election data must contain at least one validator candidate, but found 0. Please add at least one candidate.",
+            ));
         }
 
         // Nominators are optional - election can run with just validators (no nominator votes)
         // This allows the tool to work when RPC endpoints don't support storage queries
 
-        // All candidate account IDs must be unique
-        let mut candidate_ids = HashSet::new();
-        for candidate in &self.candidates {
-            if !candidate_ids.insert(&candidate.account_id) {
-                return Err(ElectionError::ValidationError {
-                    message: format!("Duplicate candidate account ID: {}", candidate.account_id),
-                    field: Some("candidates".to_string()),
-                });
+        // All candidate accounts must be unique, whichever format they are written in
+        let mut candidate_ids = HashMap::new();
+        for (i, candidate) in self.candidates.iter().enumerate() {
+            let pointer = format!("/candidates/{}", i);
+            let key = match canonical_id(&candidate.account_id) {
+                Ok(key) => key,
+                Err(message) => {
+                    issues.push(ValidationIssue::new(pointer, message));
+                    continue;
+                }
+            };
+            if let Some(existing) = candidate_ids.insert(key, &candidate.account_id) {
+                issues.push(ValidationIssue::new(
+                    pointer,
+                    duplicate_message("candidate", existing, &candidate.account_id),
+                ));
             }
         }
 
-        // All nominator account IDs must be unique
-        let mut nominator_ids = HashSet::new();
-        for nominator in &self.nominators {
-            if !nominator_ids.insert(&nominator.account_id) {
-                return Err(ElectionError::ValidationError {
-                    message: format!("Duplicate nominator account ID: {}", nominator.account_id),
-                    field: Some("nominators".to_string()),
-                });
+        // All nominator accounts must be unique
+        let mut nominator_ids = HashMap::new();
+        for (i, nominator) in self.nominators.iter().enumerate() {
+            let pointer = format!("/nominators/{}", i);
+            let key = match canonical_id(&nominator.account_id) {
+                Ok(key) => key,
+                Err(message) => {
+                    issues.push(ValidationIssue::new(pointer, message));
+                    continue;
+                }
+            };
+            if let Some(existing) = nominator_ids.insert(key, &nominator.account_id) {
+                issues.push(ValidationIssue::new(
+                    pointer,
+                    duplicate_message("nominator", existing, &nominator.account_id),
+                ));
             }
         }
 
-        // All voting edges must reference existing candidates
-        let candidate_id_set: HashSet<&String> = self.candidates.iter().map(|c| &c.account_id).collect();
-        for nominator in &self.nominators {
-            for target in &nominator.targets {
-                if !candidate_id_set.contains(target) {
+        // All voting edges must reference existing candidates; targets written
+        // exactly like a candidate don't need parsing
+        let raw_candidate_ids: HashSet<&str> = self.candidates.iter().map(|c| c.account_id.as_str()).collect();
+        for (i, nominator) in self.nominators.iter().enumerate() {
+            for (j, target) in nominator.targets.iter().enumerate() {
+                if raw_candidate_ids.contains(target.as_str()) {
+                    continue;
+                }
+                let pointer = format!("/nominators/{}/targets/{}", i, j);
+                let known = match canonical_id(target) {
+                    Ok(key) => candidate_ids.contains_key(&key),
+                    Err(message) => {
+                        issues.push(ValidationIssue::new(pointer, message));
+                        continue;
+                    }
+                };
+                if !known {
                     let available_candidates: Vec<String> = self.candidates.iter().take(5).map(|c| c.account_id.clone()).collect();
                     let candidate_list = if self.candidates.len() > 5 {
                         format!("{} (and {} more)", available_candidates.join(", "), self.candidates.len() - 5)
                     } else {
                         available_candidates.join(", ")
                     };
-                    return Err(ElectionError::ValidationError {
-                        message: format!(
+                    issues.push(ValidationIssue::new(
+                        pointer,
+                        format!(
                             "Nominator '{}' votes for non-existent candidate '{}'. Available candidates: {}",
                             nominator.account_id, target, candidate_list
                         ),
-                        field: Some("nominators.targets".to_string()),
-                    });
+                    ));
                 }
             }
         }
 
+        issues
+    }
+
+    /// Format of the first candidate written as an address, if any
+    pub fn address_format(&self) -> Option<AddressFormat> {
+        self.candidates
+            .iter()
+            .find_map(|c| AccountId::parse_address(&c.account_id).ok().flatten())
+            .map(|(_, format)| format)
+    }
+
+    /// Rewrite every account ID that is an address in `format`
+    ///
+    /// Labels that are not addresses are left as they are. Afterwards the
+    /// same account is always written the same way, so IDs can be compared
+    /// as strings.
+    pub fn normalize_account_ids(&mut self, format: AddressFormat) -> Result<(), ElectionError> {
+        // Targets repeat candidate IDs, so each candidate is only parsed once
+        let mut candidate_ids = HashMap::with_capacity(self.candidates.len());
+        for candidate in &mut self.candidates {
+            let normalized = format.normalize(&candidate.account_id)?;
+            let original = std::mem::replace(&mut candidate.account_id, normalized.clone());
+            candidate_ids.insert(original, normalized);
+        }
+        for nominator in &mut self.nominators {
+            nominator.account_id = format.normalize(&nominator.account_id)?;
+            for target in &mut nominator.targets {
+                *target = match candidate_ids.get(target.as_str()) {
+                    Some(normalized) => normalized.clone(),
+                    None => format.normalize(target)?,
+                };
+            }
+            if let Some(pool) = nominator.metadata.as_mut().and_then(|m| m.pool.as_mut()) {
+                for member in &mut pool.members {
+                    member.account_id = format.normalize(&member.account_id)?;
+                }
+            }
+        }
         Ok(())
     }
 
+    /// Whether candidate stakes are known, i.e. not left at zero by a chain loader
+    pub fn candidate_stakes_known(&self) -> bool {
+        !self.metadata.as_ref().is_some_and(|m| m.candidate_stakes_unknown)
+    }
+
     /// Get reference to candidates
     pub fn candidates(&self) -> &[ValidatorCandidate] {
         &self.candidates
@@ -1,28 +1,45 @@
 //! Election parameter overrides model
 
 use crate::error::ElectionError;
+use crate::models::account_id::{AccountId, AddressFormat};
+use crate::models::nominator::Nominator;
+use crate::models::validator::ValidatorCandidate;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::Path;
 
 /// Optional parameter overrides that modify election data before execution
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
 pub struct ElectionOverrides {
     /// Override stake for specific candidates (account_id -> stake)
-    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub candidate_stakes: HashMap<String, u128>,
     /// Override stake for specific nominators (account_id -> stake)
-    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub nominator_stakes: HashMap<String, u128>,
     /// Voting edge modifications
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub voting_edges: Vec<EdgeModification>,
+    /// Candidates to add to the election
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub added_candidates: Vec<ValidatorCandidate>,
+    /// Candidates to remove; nominations of them are dropped
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub removed_candidates: Vec<String>,
+    /// Nominators to add to the election
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub added_nominators: Vec<Nominator>,
+    /// Nominators to remove
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub removed_nominators: Vec<String>,
     /// Override active set size
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub active_set_size: Option<u32>,
 }
 
 /// Modification to a voting edge
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct EdgeModification {
     /// Action to perform
     pub action: EdgeAction,
@@ -30,13 +47,15 @@ pub struct EdgeModification {
     pub nominator_id: String,
     /// Candidate account ID
     pub candidate_id: String,
-    /// Optional weight for the edge
+    /// Stake of the nominator committed to this edge
+    ///
+    /// The rest of the nominator's stake is shared by its unweighted edges.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub weight: Option<u128>,
 }
 
 /// Action to perform on a voting edge
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum EdgeAction {
     /// Add a new voting edge
@@ -53,12 +72,26 @@ impl ElectionOverrides {
         Self::default()
     }
 
+    /// Load overrides from a JSON file
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ElectionError> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).map_err(|e| ElectionError::FileError {
+            message: format!("Failed to read overrides: {}", e),
+            path: path.to_path_buf(),
+        })?;
+        serde_json::from_str(&content).map_err(|e| ElectionError::FileError {
+            message: format!("Failed to parse overrides: {}", e),
+            path: path.to_path_buf(),
+        })
+    }
+
     /// Set candidate stake override
     pub fn set_candidate_stake(
         &mut self,
         account_id: String,
         stake: u128,
     ) -> Result<(), ElectionError> {
+        AccountId::parse_address(&account_id)?;
         self.candidate_stakes.insert(account_id, stake);
         Ok(())
     }
@@ -69,6 +102,7 @@ impl ElectionOverrides {
         account_id: String,
         stake: u128,
     ) -> Result<(), ElectionError> {
+        AccountId::parse_address(&account_id)?;
         self.nominator_stakes.insert(account_id, stake);
         Ok(())
     }
@@ -79,6 +113,8 @@ impl ElectionOverrides {
         nominator_id: String,
         candidate_id: String,
     ) -> Result<(), ElectionError> {
+        AccountId::parse_address(&nominator_id)?;
+        AccountId::parse_address(&candidate_id)?;
         self.voting_edges.push(EdgeModification {
             action: EdgeAction::Add,
             nominator_id,
@@ -94,6 +130,8 @@ impl ElectionOverrides {
         nominator_id: String,
         candidate_id: String,
     ) -> Result<(), ElectionError> {
+        AccountId::parse_address(&nominator_id)?;
+        AccountId::parse_address(&candidate_id)?;
         self.voting_edges.push(EdgeModification {
             action: EdgeAction::Remove,
             nominator_id,
@@ -103,6 +141,24 @@ impl ElectionOverrides {
         Ok(())
     }
 
+    /// Add a voting edge that commits `weight` of the nominator's stake
+    pub fn add_weighted_voting_edge(
+        &mut self,
+        nominator_id: String,
+        candidate_id: String,
+        weight: u128,
+    ) -> Result<(), ElectionError> {
+        AccountId::parse_address(&nominator_id)?;
+        AccountId::parse_address(&candidate_id)?;
+        self.voting_edges.push(EdgeModification {
+            action: EdgeAction::Add,
+            nominator_id,
+            candidate_id,
+            weight: Some(weight),
+        });
+        Ok(())
+    }
+
     /// Modify a voting edge
     pub fn modify_voting_edge(
         &mut self,
@@ -110,6 +166,8 @@ impl ElectionOverrides {
         candidate_id: String,
         weight: Option<u128>,
     ) -> Result<(), ElectionError> {
+        AccountId::parse_address(&nominator_id)?;
+        AccountId::parse_address(&candidate_id)?;
         self.voting_edges.push(EdgeModification {
             action: EdgeAction::Modify,
             nominator_id,
@@ -118,6 +176,79 @@ impl ElectionOverrides {
         });
         Ok(())
     }
-}
 
+    /// Add a candidate to the election
+    pub fn add_candidate(&mut self, candidate: ValidatorCandidate) -> Result<(), ElectionError> {
+        AccountId::parse_address(&candidate.account_id)?;
+        self.added_candidates.push(candidate);
+        Ok(())
+    }
+
+    /// Remove a candidate and drop all nominations of it
+    pub fn remove_candidate(&mut self, account_id: String) -> Result<(), ElectionError> {
+        AccountId::parse_address(&account_id)?;
+        self.removed_candidates.push(account_id);
+        Ok(())
+    }
+
+    /// Add a nominator to the election
+    pub fn add_nominator(&mut self, nominator: Nominator) -> Result<(), ElectionError> {
+        AccountId::parse_address(&nominator.account_id)?;
+        for target in &nominator.targets {
+            AccountId::parse_address(target)?;
+        }
+        self.added_nominators.push(nominator);
+        Ok(())
+    }
+
+    /// Remove a nominator
+    pub fn remove_nominator(&mut self, account_id: String) -> Result<(), ElectionError> {
+        AccountId::parse_address(&account_id)?;
+        self.removed_nominators.push(account_id);
+        Ok(())
+    }
+
+    /// Override the number of validators to select
+    pub fn set_active_set_size(&mut self, size: u32) -> Result<(), ElectionError> {
+        if size == 0 {
+            return Err(ElectionError::ValidationError {
+                message: "Active set size override must be positive".to_string(),
+                field: Some("overrides.active_set_size".to_string()),
+            });
+        }
+        self.active_set_size = Some(size);
+        Ok(())
+    }
 
+    /// Rewrite every account ID that is an address in `format`
+    ///
+    /// Lets overrides written with SS58 addresses apply to data loaded with
+    /// hex account IDs, and the other way around.
+    pub fn normalize_account_ids(&mut self, format: AddressFormat) -> Result<(), ElectionError> {
+        let normalize_keys = |stakes: &HashMap<String, u128>| -> Result<HashMap<String, u128>, ElectionError> {
+            stakes
+                .iter()
+                .map(|(account_id, stake)| Ok((format.normalize(account_id)?, *stake)))
+                .collect()
+        };
+        self.candidate_stakes = normalize_keys(&self.candidate_stakes)?;
+        self.nominator_stakes = normalize_keys(&self.nominator_stakes)?;
+        for edge in &mut self.voting_edges {
+            edge.nominator_id = format.normalize(&edge.nominator_id)?;
+            edge.candidate_id = format.normalize(&edge.candidate_id)?;
+        }
+        for candidate in &mut self.added_candidates {
+            candidate.account_id = format.normalize(&candidate.account_id)?;
+        }
+        for nominator in &mut self.added_nominators {
+            nominator.account_id = format.normalize(&nominator.account_id)?;
+            for target in &mut nominator.targets {
+                *target = format.normalize(target)?;
+            }
+        }
+        for account_id in self.removed_candidates.iter_mut().chain(&mut self.removed_nominators) {
+            *account_id = format.normalize(account_id)?;
+        }
+        Ok(())
+    }
+}
@@ -1,18 +1,25 @@
 //! Election result model
 
 use crate::diagnostics::models::Diagnostics;
+use crate::models::election_data::ElectionData;
+use crate::models::warning::Warning;
 use crate::types::AlgorithmType;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 
 /// Outcome of an election execution
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct ElectionResult {
     /// List of validators selected for the active set
     pub selected_validators: Vec<SelectedValidator>,
     /// How nominator stakes are allocated across validators
     pub stake_distribution: Vec<StakeAllocation>,
-    /// Total stake participating in election
+    /// Total stake of the nominators taking part in the election
     pub total_stake: u128,
+    /// Where `total_stake` ended up: assigned, unassigned or lost to rounding
+    #[serde(default)]
+    pub accounting: StakeAccounting,
     /// Algorithm that produced these results
     pub algorithm_used: AlgorithmType,
     /// Execution metadata (timing, block number, etc.)
@@ -20,10 +27,16 @@ pub struct ElectionResult {
     /// Optional diagnostics explaining the results
     #[serde(skip_serializing_if = "Option::is_none")]
     pub diagnostics: Option<Diagnostics>,
+    /// Optional breakdown of nomination pool backing to pool members
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pool_backing: Option<Vec<PoolMemberBacking>>,
+    /// Adjustments the engine made while running the election
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<Warning>,
 }
 
 /// Validator that was selected in the election
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct SelectedValidator {
     /// Account ID of the selected validator
     pub account_id: String,
@@ -37,7 +50,7 @@ pub struct SelectedValidator {
 }
 
 /// How a nominator's stake is allocated to a validator
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct StakeAllocation {
     /// Account ID of the nominator
     pub nominator_id: String,
@@ -47,10 +60,74 @@ pub struct StakeAllocation {
     pub amount: u128,
     /// Proportion of nominator's total stake (0.0 to 1.0)
     pub proportion: f64,
+    /// Exact proportion in parts per billion, as the election computed it
+    #[serde(default)]
+    pub perbill: u32,
+}
+
+/// Breakdown of `total_stake` in an [`ElectionResult`]
+///
+/// `total_stake == assigned_stake + unassigned_stake + rounding_residue`
+/// holds exactly.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct StakeAccounting {
+    /// Stake allocated to selected validators
+    pub assigned_stake: u128,
+    /// Stake of nominators that backs no selected validator
+    pub unassigned_stake: u128,
+    /// Stake lost when splitting nominators' stake by `Perbill` proportions
+    pub rounding_residue: u128,
+    /// Unassigned stake per nominator, with the reason
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unassigned: Vec<UnassignedStake>,
+}
+
+/// Stake of one nominator that backs no selected validator
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct UnassignedStake {
+    /// Account ID of the nominator
+    pub nominator_id: String,
+    /// Unassigned amount
+    pub amount: u128,
+    /// Why the stake is unassigned
+    pub reason: UnassignedReason,
+}
+
+/// Why nominator stake backs no selected validator
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum UnassignedReason {
+    /// None of the nominator's targets is a candidate in the election
+    NoCandidateTargets,
+    /// None of the nominator's targets was selected
+    NoElectedTargets,
+    /// The nominator's stake is below `min_nominator_bond`
+    BelowMinNominatorBond,
+    /// None of the nominator's targets is eligible, e.g. all are below
+    /// `min_validator_bond` or blocked
+    NoEligibleTargets,
+    /// The allocation was trimmed to respect `MaxBackersPerWinner`
+    BackerTrimmed {
+        /// Validator the allocation was removed from
+        validator_id: String,
+    },
+}
+
+/// Share of a nomination pool's stake on a validator attributed to one member
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct PoolMemberBacking {
+    /// Pool the stake was allocated by
+    pub pool_id: u32,
+    /// Account ID of the pool member
+    pub member_id: String,
+    /// Account ID of the validator receiving stake
+    pub validator_id: String,
+    /// Member's share of the pool's allocation
+    pub amount: u128,
 }
 
 /// Execution metadata
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct ExecutionMetadata {
     /// Block number if data came from RPC
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -63,6 +140,20 @@ pub struct ExecutionMetadata {
     pub data_source: Option<String>,
 }
 
+/// Quality of an election outcome, as scored by `sp-npos-elections`
+///
+/// A higher minimal stake is better, then a higher sum, then a lower sum of
+/// squares (a more even distribution).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+pub struct ElectionScore {
+    /// Lowest backing of a selected validator
+    pub minimal_stake: u128,
+    /// Sum of the selected validators' backing
+    pub sum_stake: u128,
+    /// Sum of the squares of the selected validators' backing (saturating)
+    pub sum_stake_squared: u128,
+}
+
 impl ElectionResult {
     /// Create a new election result
     pub fn new(
@@ -75,6 +166,7 @@ impl ElectionResult {
             selected_validators,
             stake_distribution,
             total_stake,
+            accounting: StakeAccounting::default(),
             algorithm_used,
             execution_metadata: ExecutionMetadata {
                 block_number: None,
@@ -82,6 +174,8 @@ impl ElectionResult {
                 data_source: None,
             },
             diagnostics: None,
+            pool_backing: None,
+            warnings: Vec::new(),
         }
     }
 
@@ -105,6 +199,11 @@ impl ElectionResult {
         self.algorithm_used
     }
 
+    /// Get warnings raised while running the election
+    pub fn warnings(&self) -> &[Warning] {
+        &self.warnings
+    }
+
     /// Set diagnostics for this result
     pub fn with_diagnostics(mut self, diagnostics: Diagnostics) -> Self {
         self.diagnostics = Some(diagnostics);
@@ -116,11 +215,116 @@ impl ElectionResult {
         self.diagnostics.as_ref()
     }
 
+    /// Get the breakdown of the total stake
+    pub fn accounting(&self) -> &StakeAccounting {
+        &self.accounting
+    }
+
+    /// Derive backing and stake accounting from the stake distribution
+    ///
+    /// Each selected validator's backing becomes the sum of its allocations,
+    /// and every nominator in `data` is accounted for: its stake is either
+    /// allocated, unassigned (with the reason), or rounding residue.
+    pub fn with_stake_accounting(mut self, data: &ElectionData) -> Self {
+        let mut assigned_by_nominator: HashMap<&str, u128> = HashMap::new();
+        let mut backing_by_validator: HashMap<&str, u128> = HashMap::new();
+        for allocation in &self.stake_distribution {
+            *assigned_by_nominator.entry(&allocation.nominator_id).or_default() += allocation.amount;
+            *backing_by_validator.entry(&allocation.validator_id).or_default() += allocation.amount;
+        }
+        for validator in &mut self.selected_validators {
+            validator.total_backing_stake = backing_by_validator.get(validator.account_id.as_str()).copied().unwrap_or(0);
+        }
+
+        let candidates: HashSet<&str> = data.candidates.iter().map(|c| c.account_id.as_str()).collect();
+        let mut accounting = StakeAccounting {
+            assigned_stake: backing_by_validator.values().sum(),
+            ..Default::default()
+        };
+        for nominator in &data.nominators {
+            match assigned_by_nominator.get(nominator.account_id.as_str()) {
+                Some(&assigned) => {
+                    accounting.rounding_residue += nominator.stake.saturating_sub(assigned);
+                }
+                None if nominator.stake > 0 => {
+                    let reason = if nominator.targets.iter().any(|t| candidates.contains(t.as_str())) {
+                        UnassignedReason::NoElectedTargets
+                    } else {
+                        UnassignedReason::NoCandidateTargets
+                    };
+                    accounting.unassigned_stake += nominator.stake;
+                    accounting.unassigned.push(UnassignedStake {
+                        nominator_id: nominator.account_id.clone(),
+                        amount: nominator.stake,
+                        reason,
+                    });
+                }
+                None => {}
+            }
+        }
+        self.total_stake = data.nominators.iter().map(|n| n.stake).sum();
+        self.accounting = accounting;
+        self
+    }
+
+    /// Break the backing of nomination pools down to their members
+    ///
+    /// Each allocation made by a pool's bonded account in `data` is split
+    /// across the pool's members in proportion to their points. Rounding dust
+    /// goes to the member with the most points, so members' amounts always add
+    /// up to the pool's allocation.
+    pub fn with_pool_backing(mut self, data: &ElectionData) -> Self {
+        let mut backing = Vec::new();
+        for nominator in &data.nominators {
+            let Some(pool) = nominator.pool() else { continue };
+            let total_points = pool.total_points();
+            let Some(largest) = (0..pool.members.len()).max_by_key(|&i| pool.members[i].points) else {
+                continue;
+            };
+            if total_points == 0 {
+                continue;
+            }
+
+            for allocation in self.allocations_for_nominator(&nominator.account_id) {
+                let start = backing.len();
+                let mut remaining = allocation.amount;
+                for member in &pool.members {
+                    let amount = proportional_share(allocation.amount, member.points, total_points);
+                    remaining = remaining.saturating_sub(amount);
+                    backing.push(PoolMemberBacking {
+                        pool_id: pool.pool_id,
+                        member_id: member.account_id.clone(),
+                        validator_id: allocation.validator_id.clone(),
+                        amount,
+                    });
+                }
+                backing[start + largest].amount += remaining;
+            }
+        }
+        self.pool_backing = Some(backing);
+        self
+    }
+
+    /// Get the pool member breakdown if it was computed
+    pub fn pool_backing(&self) -> Option<&[PoolMemberBacking]> {
+        self.pool_backing.as_deref()
+    }
+
     /// Get the number of selected validators
     pub fn validator_count(&self) -> usize {
         self.selected_validators.len()
     }
 
+    /// Score of the selected validators' backing
+    pub fn score(&self) -> ElectionScore {
+        let backing = || self.selected_validators.iter().map(|v| v.total_backing_stake);
+        ElectionScore {
+            minimal_stake: backing().min().unwrap_or(0),
+            sum_stake: backing().fold(0u128, u128::saturating_add),
+            sum_stake_squared: backing().fold(0u128, |sum, stake| sum.saturating_add(stake.saturating_mul(stake))),
+        }
+    }
+
     /// Get validator by rank (1-indexed)
     pub fn validator_by_rank(&self, rank: u32) -> Option<&SelectedValidator> {
         self.selected_validators
@@ -158,5 +362,66 @@ impl ElectionResult {
             message: format!("Failed to serialize result to JSON: {}", e),
         })
     }
+
+    /// Write the result as pretty JSON to `writer`
+    ///
+    /// Produces the same output as [`to_json`](Self::to_json), but each
+    /// allocation of the stake distribution is written out as it is
+    /// serialized instead of building the whole document in memory first.
+    /// Wrap unbuffered writers such as files in a `BufWriter`.
+    pub fn write_json(&self, writer: impl std::io::Write) -> Result<(), crate::error::ElectionError> {
+        serde_json::to_writer_pretty(writer, self).map_err(|e| crate::error::ElectionError::InvalidData {
+            message: format!("Failed to write result as JSON: {}", e),
+        })
+    }
+
+    /// Write the selected validators as CSV to `writer`
+    ///
+    /// Columns are `rank,account_id,total_backing_stake,nominator_count`;
+    /// amounts are in planck.
+    pub fn write_validators_csv(&self, writer: impl std::io::Write) -> Result<(), crate::error::ElectionError> {
+        let rows = self.selected_validators.iter().map(|validator| {
+            [
+                validator.rank.map(|rank| rank.to_string()).unwrap_or_default(),
+                validator.account_id.clone(),
+                validator.total_backing_stake.to_string(),
+                validator.nominator_count.to_string(),
+            ]
+        });
+        crate::input::csv::write_records(
+            writer,
+            &["rank", "account_id", "total_backing_stake", "nominator_count"],
+            rows,
+        )
+    }
+
+    /// Write the stake distribution as CSV to `writer`
+    ///
+    /// Columns are `nominator_id,validator_id,amount,proportion,perbill`;
+    /// amounts are in planck.
+    pub fn write_stake_distribution_csv(&self, writer: impl std::io::Write) -> Result<(), crate::error::ElectionError> {
+        let rows = self.stake_distribution.iter().map(|allocation| {
+            [
+                allocation.nominator_id.clone(),
+                allocation.validator_id.clone(),
+                allocation.amount.to_string(),
+                allocation.proportion.to_string(),
+                allocation.perbill.to_string(),
+            ]
+        });
+        crate::input::csv::write_records(
+            writer,
+            &["nominator_id", "validator_id", "amount", "proportion", "perbill"],
+            rows,
+        )
+    }
 }
 
+
+/// `amount * points / total` without overflowing for large balances
+fn proportional_share(amount: u128, points: u128, total: u128) -> u128 {
+    match amount.checked_mul(points) {
+        Some(product) => product / total,
+        None => (amount / total) * points + ((amount % total) as f64 * (points as f64 / total as f64)) as u128,
+    }
+}
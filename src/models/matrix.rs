@@ -0,0 +1,95 @@
+//! Batch runs of several algorithms at several active set sizes
+
+use crate::error::ElectionError;
+use crate::models::election_result::ElectionScore;
+use crate::types::AlgorithmType;
+use serde::{Deserialize, Serialize};
+
+/// Algorithms and active set sizes to run every combination of
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MatrixSpec {
+    /// Algorithms to run; the first is the reference for set overlap
+    pub algorithms: Vec<AlgorithmType>,
+    /// Active set sizes to run each algorithm at
+    pub active_set_sizes: Vec<u32>,
+    /// Worker threads (default: available parallelism)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub threads: Option<usize>,
+}
+
+/// Outcome of one algorithm at one active set size
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MatrixRun {
+    /// Algorithm that ran
+    pub algorithm: AlgorithmType,
+    /// Requested active set size
+    pub active_set_size: u32,
+    /// Number of validators selected
+    pub selected: u32,
+    /// Score of the selected validators' backing
+    pub score: ElectionScore,
+    /// Validators also selected by the reference algorithm at this size
+    pub overlap: u32,
+    /// Wall-clock time of the election in milliseconds
+    pub runtime_ms: f64,
+    /// Number of warnings the run produced
+    pub warnings: u32,
+}
+
+/// Results of a matrix run, ordered by active set size, then algorithm
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MatrixReport {
+    /// Algorithm the other runs' overlap is measured against
+    pub reference: AlgorithmType,
+    /// One entry per algorithm and active set size
+    pub runs: Vec<MatrixRun>,
+}
+
+impl MatrixSpec {
+    /// Run every algorithm at every active set size
+    pub fn new(algorithms: Vec<AlgorithmType>, active_set_sizes: Vec<u32>) -> Self {
+        Self {
+            algorithms,
+            active_set_sizes,
+            threads: None,
+        }
+    }
+
+    /// Set the number of worker threads
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = Some(threads);
+        self
+    }
+
+    /// Validate the spec
+    pub fn validate(&self) -> Result<(), ElectionError> {
+        if self.algorithms.is_empty() {
+            return Err(ElectionError::ValidationError {
+                message: "Matrix needs at least one algorithm".to_string(),
+                field: Some("algorithms".to_string()),
+            });
+        }
+        if self.active_set_sizes.is_empty() || self.active_set_sizes.contains(&0) {
+            return Err(ElectionError::ValidationError {
+                message: "Matrix needs at least one active set size, all greater than 0".to_string(),
+                field: Some("active_set_sizes".to_string()),
+            });
+        }
+        if self.threads == Some(0) {
+            return Err(ElectionError::ValidationError {
+                message: "Matrix needs at least one thread".to_string(),
+                field: Some("threads".to_string()),
+            });
+        }
+        Ok(())
+    }
+}
+
+impl MatrixReport {
+    /// Run of the given algorithm at the given active set size
+    pub fn run(&self, algorithm: AlgorithmType, active_set_size: u32) -> Option<&MatrixRun> {
+        self.runs
+            .iter()
+            .find(|run| run.algorithm == algorithm && run.active_set_size == active_set_size)
+    }
+}
@@ -1,19 +1,31 @@
 //! Data models for election data, configuration, and results
 
+pub mod account_id;
+pub mod chain_profile;
 pub mod election_config;
 pub mod election_data;
 pub mod election_overrides;
 pub mod election_result;
+pub mod matrix;
 pub mod nominator;
+pub mod scenario;
+pub mod sweep;
 pub mod validator;
 pub mod voting_edge;
+pub mod warning;
 
+pub use account_id::{AccountId, AddressFormat};
+pub use chain_profile::ChainProfile;
 pub use election_config::ElectionConfiguration;
 pub use election_data::ElectionData;
 pub use election_overrides::ElectionOverrides;
-pub use election_result::ElectionResult;
+pub use election_result::{ElectionResult, ElectionScore};
+pub use matrix::{MatrixReport, MatrixRun, MatrixSpec};
 pub use nominator::Nominator;
+pub use scenario::{Scenario, ScenarioReport, ScenarioSet};
+pub use sweep::{SweepPoint, SweepReport};
 pub use validator::ValidatorCandidate;
 pub use voting_edge::VotingEdge;
+pub use warning::Warning;
 
 
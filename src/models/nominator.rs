@@ -1,9 +1,10 @@
 //! Nominator model
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Nominator in an election
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct Nominator {
     /// SS58-encoded account identifier (must be unique)
     pub account_id: String,
@@ -17,13 +18,41 @@ pub struct Nominator {
 }
 
 /// Metadata for a nominator
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct NominatorMetadata {
+    /// Set when this voter is the bonded account of a nomination pool
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pool: Option<NominationPool>,
     /// Additional metadata fields as needed
     #[serde(flatten)]
     pub extra: std::collections::HashMap<String, serde_json::Value>,
 }
 
+/// Nomination pool voting through its bonded account
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct NominationPool {
+    /// Pool identifier
+    pub pool_id: u32,
+    /// Members with their share of the pool
+    pub members: Vec<PoolMember>,
+}
+
+/// Member of a nomination pool
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct PoolMember {
+    /// Account ID of the member
+    pub account_id: String,
+    /// Pool points held by the member, proportional to its bonded funds
+    pub points: u128,
+}
+
+impl NominationPool {
+    /// Total points held by all members
+    pub fn total_points(&self) -> u128 {
+        self.members.iter().map(|member| member.points).sum()
+    }
+}
+
 impl Nominator {
     /// Create a new nominator
     pub fn new(account_id: String, stake: u128) -> Self {
@@ -46,6 +75,11 @@ impl Nominator {
     pub fn remove_target(&mut self, candidate_id: &str) {
         self.targets.retain(|id| id != candidate_id);
     }
+
+    /// Nomination pool this voter is the bonded account of, if any
+    pub fn pool(&self) -> Option<&NominationPool> {
+        self.metadata.as_ref().and_then(|metadata| metadata.pool.as_ref())
+    }
 }
 
 
@@ -0,0 +1,143 @@
+//! Scenario model for what-if analysis
+
+use crate::diagnostics::comparison::ResultComparison;
+use crate::error::ElectionError;
+use crate::models::election_config::ElectionConfiguration;
+use crate::models::election_data::ElectionData;
+use crate::models::election_overrides::ElectionOverrides;
+use crate::models::election_result::ElectionResult;
+use crate::types::AlgorithmType;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// A base dataset with named override sets to run against it
+///
+/// Read from JSON or, for files ending in `.toml`, TOML. The baseline is the
+/// base dataset run without overrides.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScenarioSet {
+    /// Election data all scenarios start from
+    pub base: ScenarioBase,
+    /// Algorithm for the baseline and for scenarios that don't set one
+    #[serde(default = "default_algorithm")]
+    pub algorithm: AlgorithmType,
+    /// Number of validators to select, unless a scenario overrides it
+    pub active_set_size: u32,
+    /// Scenarios to compare against the baseline
+    pub scenarios: Vec<Scenario>,
+}
+
+/// Where a scenario set's election data comes from
+///
+/// Exactly one of `input_file` and `data` must be set.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct ScenarioBase {
+    /// JSON or binary election data file, relative to the scenario file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_file: Option<PathBuf>,
+    /// Election data written out in the scenario file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<ElectionData>,
+}
+
+/// Named set of overrides applied to the base dataset
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Scenario {
+    /// Name shown in reports
+    pub name: String,
+    /// Algorithm to run this scenario with instead of the set's
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub algorithm: Option<AlgorithmType>,
+    /// Changes to the base dataset
+    #[serde(default)]
+    pub overrides: ElectionOverrides,
+}
+
+/// Results of a scenario set
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScenarioReport {
+    /// Result of the base dataset without overrides
+    pub baseline: ElectionResult,
+    /// Result of each scenario, in file order
+    pub scenarios: Vec<ScenarioOutcome>,
+}
+
+/// Result of one scenario and how it differs from the baseline
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScenarioOutcome {
+    /// Scenario name
+    pub name: String,
+    /// Election result with the scenario's overrides
+    pub result: ElectionResult,
+    /// Differences from the baseline
+    pub comparison: ResultComparison,
+}
+
+fn default_algorithm() -> AlgorithmType {
+    AlgorithmType::SequentialPhragmen
+}
+
+impl ScenarioSet {
+    /// Load a scenario set from a JSON or TOML file
+    ///
+    /// A relative `base.input_file` is resolved against the scenario file's
+    /// directory.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ElectionError> {
+        let path = path.as_ref();
+        let parse_error = |message: String| ElectionError::FileError {
+            message: format!("Failed to parse scenario file: {}", message),
+            path: path.to_path_buf(),
+        };
+        let content = std::fs::read_to_string(path).map_err(|e| ElectionError::FileError {
+            message: format!("Failed to read scenario file: {}", e),
+            path: path.to_path_buf(),
+        })?;
+        let mut set: Self = if path.extension().is_some_and(|ext| ext == "toml") {
+            // The toml deserializer has no u128 support, so stakes go through JSON values
+            let value: toml::Value = toml::from_str(&content).map_err(|e| parse_error(e.to_string()))?;
+            let value = serde_json::to_value(value).map_err(|e| parse_error(e.to_string()))?;
+            serde_json::from_value(value).map_err(|e| parse_error(e.to_string()))?
+        } else {
+            serde_json::from_str(&content).map_err(|e| parse_error(e.to_string()))?
+        };
+
+        if let Some(ref mut input_file) = set.base.input_file {
+            if input_file.is_relative() {
+                if let Some(dir) = path.parent() {
+                    *input_file = dir.join(&*input_file);
+                }
+            }
+        }
+        Ok(set)
+    }
+
+    /// Baseline configuration: the set's algorithm and active set size
+    pub fn configuration(&self) -> Result<ElectionConfiguration, ElectionError> {
+        ElectionConfiguration::new()
+            .algorithm(self.algorithm)
+            .active_set_size(self.active_set_size)
+            .build()
+    }
+
+    /// Election data of the base dataset
+    pub fn load_base(&self) -> Result<ElectionData, ElectionError> {
+        match (&self.base.input_file, &self.base.data) {
+            (Some(input_file), None) => crate::input::load_data_file(input_file.clone()),
+            (None, Some(data)) => {
+                data.validate()?;
+                Ok(data.clone())
+            }
+            _ => Err(ElectionError::ValidationError {
+                message: "Scenario base must set exactly one of input_file and data".to_string(),
+                field: Some("base".to_string()),
+            }),
+        }
+    }
+}
+
+impl ScenarioReport {
+    /// Outcome of the scenario with the given name
+    pub fn scenario(&self, name: &str) -> Option<&ScenarioOutcome> {
+        self.scenarios.iter().find(|outcome| outcome.name == name)
+    }
+}
@@ -0,0 +1,53 @@
+//! Active set size sweeps
+
+use crate::models::election_result::{ElectionResult, ElectionScore};
+use crate::types::AlgorithmType;
+use serde::{Deserialize, Serialize};
+
+/// Results of one algorithm over a range of active set sizes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SweepReport {
+    /// Algorithm that ran
+    pub algorithm: AlgorithmType,
+    /// Whether smaller sizes were elected from the largest size's winners
+    pub reused_winner_order: bool,
+    /// One point per active set size, smallest first
+    pub points: Vec<SweepPoint>,
+}
+
+/// Backing of the active set at one size
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SweepPoint {
+    /// Requested active set size
+    pub active_set_size: u32,
+    /// Number of validators selected
+    pub selected: u32,
+    /// Lowest backing of a selected validator
+    pub min_stake: u128,
+    /// Mean backing of the selected validators, rounded down
+    pub average_stake: u128,
+    /// Score of the selected validators' backing
+    pub score: ElectionScore,
+}
+
+impl SweepPoint {
+    /// Summarize the result of a run at `active_set_size`
+    pub fn new(active_set_size: u32, result: &ElectionResult) -> Self {
+        let score = result.score();
+        let selected = result.selected_validators.len() as u32;
+        Self {
+            active_set_size,
+            selected,
+            min_stake: score.minimal_stake,
+            average_stake: score.sum_stake / u128::from(selected.max(1)),
+            score,
+        }
+    }
+}
+
+impl SweepReport {
+    /// Point for the given active set size
+    pub fn point(&self, active_set_size: u32) -> Option<&SweepPoint> {
+        self.points.iter().find(|point| point.active_set_size == active_set_size)
+    }
+}
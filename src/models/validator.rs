@@ -1,9 +1,10 @@
 //! Validator candidate model
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Validator candidate in an election
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct ValidatorCandidate {
     /// SS58-encoded account identifier (must be unique)
     pub account_id: String,
@@ -15,7 +16,7 @@ pub struct ValidatorCandidate {
 }
 
 /// Metadata for a validator candidate
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct CandidateMetadata {
     /// Commission rate (0-100)
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -23,6 +24,9 @@ pub struct CandidateMetadata {
     /// On-chain status
     #[serde(skip_serializing_if = "Option::is_none")]
     pub on_chain_status: Option<String>,
+    /// Whether the validator rejects new nominations (`ValidatorPrefs.blocked`)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub blocked: bool,
 }
 
 impl ValidatorCandidate {
@@ -47,6 +51,11 @@ impl ValidatorCandidate {
             metadata: Some(metadata),
         }
     }
+
+    /// Whether the validator rejects new nominations
+    pub fn is_blocked(&self) -> bool {
+        self.metadata.as_ref().is_some_and(|metadata| metadata.blocked)
+    }
 }
 
 
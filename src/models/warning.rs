@@ -0,0 +1,156 @@
+//! Warnings raised while running an election
+
+use crate::error::ElectionError;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Something the engine adjusted or could not do while running an election
+///
+/// Warnings are collected in [`ElectionResult::warnings`](crate::ElectionResult::warnings).
+/// With [`ElectionConfiguration::strict`](crate::ElectionConfiguration::strict())
+/// set, the first warning aborts the election instead.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum Warning {
+    /// Fewer candidates than requested, so the active set was shrunk
+    ActiveSetReduced {
+        /// Requested active set size
+        requested: u32,
+        /// Number of candidates available
+        available: u32,
+    },
+    /// The snapshot the data was loaded from is known to be incomplete
+    IncompleteSnapshot {
+        /// What is missing from the snapshot
+        problems: Vec<String>,
+    },
+    /// Voters, targets or nominations were left out by eligibility rules
+    IneligibleExcluded {
+        /// Number of exclusions; details are in the diagnostics
+        count: usize,
+    },
+    /// The minimum validator bond was not applied because candidate stakes were not loaded
+    ValidatorBondUnchecked {
+        /// Configured minimum validator bond
+        min_bond: u128,
+    },
+    /// Nominations beyond the chain's `MaxNominations` were dropped
+    TargetsDropped {
+        /// Number of nominators that lost targets
+        nominators: usize,
+        /// Maximum number of targets per nominator
+        max_nominations: u32,
+    },
+    /// The lowest-staked voters beyond the chain's `MaxElectingVoters` were left out
+    VotersDropped {
+        /// Number of voters left out
+        dropped: usize,
+        /// Maximum number of voters
+        max_voters: u32,
+    },
+    /// Backing beyond the chain's `MaxBackersPerWinner` was trimmed
+    BackersTrimmed {
+        /// Number of allocations removed
+        allocations: usize,
+        /// Stake no longer backing any validator
+        amount: u128,
+        /// Maximum number of backers per validator
+        max_backers: u32,
+    },
+    /// Voter stakes above `u64::MAX` were capped for the solver, which weighs stake as `u64`
+    StakeTruncated {
+        /// Number of voters whose stake was capped
+        voters: usize,
+    },
+    /// An override named an account or voting edge that is not in the data
+    UnmatchedOverride {
+        /// What the override changes, e.g. `candidate-stake` or `voting-edge`
+        target: String,
+        /// Account, or `nominator -> candidate` edge, it named
+        account_id: String,
+    },
+    /// Diagnostics were requested but could not be generated
+    DiagnosticsFailed {
+        /// Why generation failed
+        message: String,
+    },
+}
+
+impl Warning {
+    /// Error reported for this warning in strict mode
+    pub fn into_error(self) -> ElectionError {
+        let field = match &self {
+            Warning::ActiveSetReduced { requested, available } => {
+                return ElectionError::InsufficientCandidates {
+                    requested: *requested,
+                    available: *available,
+                };
+            }
+            Warning::IncompleteSnapshot { .. } => "metadata.completeness",
+            Warning::IneligibleExcluded { .. } => "eligibility",
+            Warning::ValidatorBondUnchecked { .. } => "min_validator_bond",
+            Warning::TargetsDropped { .. } => "chain_profile.max_nominations",
+            Warning::VotersDropped { .. } => "chain_profile.max_electing_voters",
+            Warning::BackersTrimmed { .. } => "chain_profile.max_backers_per_winner",
+            Warning::StakeTruncated { .. } => "nominators.stake",
+            Warning::UnmatchedOverride { .. } => "overrides",
+            Warning::DiagnosticsFailed { .. } => "diagnostics",
+        };
+        ElectionError::ValidationError {
+            message: format!("{} (strict mode)", self),
+            field: Some(field.to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for Warning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Warning::ActiveSetReduced { requested, available } => write!(
+                f,
+                "Requested {} validators but only {} candidates available. Using {} instead.",
+                requested, available, available
+            ),
+            Warning::IncompleteSnapshot { problems } => write!(
+                f,
+                "Election data snapshot is incomplete: {}. Results may not match the chain.",
+                problems.join(", ")
+            ),
+            Warning::IneligibleExcluded { count } => {
+                write!(f, "{} voters, targets or nominations were excluded by eligibility rules.", count)
+            }
+            Warning::ValidatorBondUnchecked { min_bond } => write!(
+                f,
+                "Candidate stakes were not loaded, so the minimum validator bond of {} was not applied.",
+                min_bond
+            ),
+            Warning::TargetsDropped { nominators, max_nominations } => write!(
+                f,
+                "{} nominators vote for more than {} targets; extra targets were ignored.",
+                nominators, max_nominations
+            ),
+            Warning::VotersDropped { dropped, max_voters } => write!(
+                f,
+                "Voters exceed the limit of {}; the lowest-staked {} were left out.",
+                max_voters, dropped
+            ),
+            Warning::BackersTrimmed { allocations, amount, max_backers } => write!(
+                f,
+                "{} allocations totalling {} were trimmed to keep at most {} backers per validator.",
+                allocations, amount, max_backers
+            ),
+            Warning::StakeTruncated { voters } => write!(
+                f,
+                "{} voters have more stake than the solver can weigh; winners were chosen with their stake capped at {}.",
+                voters,
+                u64::MAX
+            ),
+            Warning::UnmatchedOverride { target, account_id } => write!(
+                f,
+                "Override ({}) of {} matches nothing in the election data and was ignored.",
+                target, account_id
+            ),
+            Warning::DiagnosticsFailed { message } => write!(f, "Failed to generate diagnostics: {}", message),
+        }
+    }
+}
@@ -0,0 +1,367 @@
+//! JSON Schemas for the input and output formats, and validation against them
+//!
+//! [`SchemaKind::schema`] generates the JSON Schema (draft 7) of election
+//! data, overrides, REST API election requests and election results from the
+//! Rust types, so the schemas always match what the tool reads and writes.
+//!
+//! [`validate_json`] checks a document against one of these schemas and then
+//! runs the checks the loaders run (unique accounts, known nomination
+//! targets, ...). Unlike loading, it reports every problem at once, each with
+//! a JSON pointer to the offending value and its line and column.
+
+use crate::api::models::{DataSource, ElectionRequest};
+use crate::error::ElectionError;
+use crate::models::election_data::ElectionData;
+use crate::models::election_overrides::ElectionOverrides;
+use crate::models::election_result::ElectionResult;
+use crate::types::AlgorithmType;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Document whose JSON Schema can be generated and validated against
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemaKind {
+    /// [`ElectionData`], the input of every command
+    ElectionData,
+    /// [`ElectionOverrides`], as read by `--overrides-file`
+    ElectionOverrides,
+    /// [`ElectionRequest`], the body of `POST /elections/run`
+    ElectionRequest,
+    /// [`ElectionResult`], the JSON output of `run`
+    ElectionResult,
+}
+
+impl SchemaKind {
+    /// Every kind, in the order they are listed in help texts
+    pub const ALL: [SchemaKind; 4] = [
+        SchemaKind::ElectionData,
+        SchemaKind::ElectionOverrides,
+        SchemaKind::ElectionRequest,
+        SchemaKind::ElectionResult,
+    ];
+
+    /// Name used on the command line and in REST API paths
+    pub fn name(&self) -> &'static str {
+        match self {
+            SchemaKind::ElectionData => "election-data",
+            SchemaKind::ElectionOverrides => "overrides",
+            SchemaKind::ElectionRequest => "request",
+            SchemaKind::ElectionResult => "result",
+        }
+    }
+
+    /// JSON Schema of this kind of document
+    pub fn schema(&self) -> serde_json::Value {
+        let schema = match self {
+            SchemaKind::ElectionData => schemars::schema_for!(ElectionData),
+            SchemaKind::ElectionOverrides => schemars::schema_for!(ElectionOverrides),
+            SchemaKind::ElectionRequest => schemars::schema_for!(ElectionRequest),
+            SchemaKind::ElectionResult => schemars::schema_for!(ElectionResult),
+        };
+        serde_json::to_value(schema).expect("JSON Schemas serialize to JSON")
+    }
+}
+
+impl std::fmt::Display for SchemaKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for SchemaKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL.into_iter().find(|kind| kind.name() == s).ok_or_else(|| {
+            let names: Vec<&str> = Self::ALL.iter().map(|kind| kind.name()).collect();
+            format!("Unknown schema: {} (expected {})", s, names.join(", "))
+        })
+    }
+}
+
+/// One problem found in a JSON document
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidationIssue {
+    /// JSON pointer to the offending value, e.g. `/nominators/12/targets/3` (empty for the whole document)
+    pub pointer: String,
+    /// What is wrong
+    pub message: String,
+    /// Line of the offending value (1-based), when validating JSON text
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    /// Column of the offending value (1-based), when validating JSON text
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+}
+
+impl ValidationIssue {
+    /// Issue at `pointer` without a position
+    pub fn new(pointer: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            pointer: pointer.into(),
+            message: message.into(),
+            line: None,
+            column: None,
+        }
+    }
+
+    /// Dotted field name of the pointer with array indices left out, e.g. `nominators.targets`
+    pub fn field(&self) -> String {
+        self.pointer
+            .split('/')
+            .filter(|segment| !segment.is_empty() && segment.parse::<usize>().is_err())
+            .collect::<Vec<_>>()
+            .join(".")
+    }
+}
+
+impl std::fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let pointer = if self.pointer.is_empty() { "/" } else { &self.pointer };
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => {
+                write!(f, "{} (line {}, column {}): {}", pointer, line, column, self.message)
+            }
+            _ => write!(f, "{}: {}", pointer, self.message),
+        }
+    }
+}
+
+impl From<ValidationIssue> for ElectionError {
+    fn from(issue: ValidationIssue) -> Self {
+        let field = issue.field();
+        ElectionError::ValidationError {
+            message: issue.message,
+            field: (!field.is_empty()).then_some(field),
+        }
+    }
+}
+
+/// Validate the JSON document `text` as `kind`, reporting every problem
+///
+/// A document that is not valid JSON yields a single issue at the syntax
+/// error. Otherwise the document is checked against the schema of `kind`
+/// and, if it matches, with the checks the loaders run. An empty result
+/// means the document would be accepted.
+pub fn validate_json(kind: SchemaKind, text: &str) -> Vec<ValidationIssue> {
+    let value: serde_json::Value = match serde_json::from_str(text) {
+        Ok(value) => value,
+        Err(e) => {
+            return vec![ValidationIssue {
+                pointer: String::new(),
+                message: format!("Invalid JSON: {}", e),
+                line: Some(e.line()),
+                column: Some(e.column()),
+            }]
+        }
+    };
+
+    let mut issues = schema_issues(kind, &value);
+    if issues.is_empty() {
+        issues = match kind {
+            SchemaKind::ElectionData => typed_issues(text, |data: ElectionData| data.validation_issues()),
+            SchemaKind::ElectionOverrides => typed_issues(text, |_: ElectionOverrides| Vec::new()),
+            SchemaKind::ElectionRequest => typed_issues(text, request_issues),
+            SchemaKind::ElectionResult => typed_issues(text, |_: ElectionResult| Vec::new()),
+        };
+    }
+
+    let pointers: HashSet<&str> = issues.iter().map(|issue| issue.pointer.as_str()).collect();
+    let positions = Locator::new(text, &pointers).locate();
+    for issue in &mut issues {
+        if let Some(&(line, column)) = positions.get(&issue.pointer) {
+            issue.line = Some(line);
+            issue.column = Some(column);
+        }
+    }
+    issues
+}
+
+/// JSON pointer of a path reported by `serde_path_to_error`
+pub(crate) fn json_pointer(path: &serde_path_to_error::Path) -> String {
+    use serde_path_to_error::Segment;
+
+    let mut pointer = String::new();
+    for segment in path.iter() {
+        match segment {
+            Segment::Seq { index } => pointer.push_str(&format!("/{}", index)),
+            Segment::Map { key } => pointer.push_str(&format!("/{}", escape(key))),
+            Segment::Enum { .. } | Segment::Unknown => {}
+        }
+    }
+    pointer
+}
+
+fn escape(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+/// Schema violations of `value`, one per failing keyword
+fn schema_issues(kind: SchemaKind, value: &serde_json::Value) -> Vec<ValidationIssue> {
+    let schema = kind.schema();
+    let compiled = jsonschema::JSONSchema::options()
+        .with_draft(jsonschema::Draft::Draft7)
+        .compile(&schema)
+        .expect("generated schemas are valid draft 7 schemas");
+    let result = compiled.validate(value);
+    match result {
+        Ok(()) => Vec::new(),
+        Err(errors) => errors
+            .map(|error| ValidationIssue::new(error.instance_path.to_string(), error.to_string()))
+            .collect(),
+    }
+}
+
+/// Deserialize `text` as `T` and run `check` on it
+///
+/// Values the schema cannot rule out, such as integers too large for their
+/// type, are reported as a deserialization issue.
+fn typed_issues<T: DeserializeOwned>(text: &str, check: impl FnOnce(T) -> Vec<ValidationIssue>) -> Vec<ValidationIssue> {
+    let mut deserializer = serde_json::Deserializer::from_str(text);
+    match serde_path_to_error::deserialize(&mut deserializer) {
+        Ok(value) => check(value),
+        Err(e) => vec![ValidationIssue::new(json_pointer(e.path()), e.inner().to_string())],
+    }
+}
+
+fn request_issues(request: ElectionRequest) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+    if let Err(e) = request.algorithm.parse::<AlgorithmType>() {
+        issues.push(ValidationIssue::new("/algorithm", format!("Invalid algorithm: {}", e)));
+    }
+    if let DataSource::Json { data } = &request.data_source {
+        issues.extend(data.validation_issues().into_iter().map(|issue| ValidationIssue {
+            pointer: format!("/data_source/data{}", issue.pointer),
+            ..issue
+        }));
+    }
+    issues
+}
+
+/// Finds the line and column of the values at a set of JSON pointers
+///
+/// Walks JSON text that is known to be valid, tracking the pointer of the
+/// current value.
+struct Locator<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    line: usize,
+    line_start: usize,
+    path: Vec<String>,
+    wanted: &'a HashSet<&'a str>,
+    found: HashMap<String, (usize, usize)>,
+}
+
+impl<'a> Locator<'a> {
+    fn new(text: &'a str, wanted: &'a HashSet<&'a str>) -> Self {
+        Self {
+            bytes: text.as_bytes(),
+            pos: 0,
+            line: 1,
+            line_start: 0,
+            path: Vec::new(),
+            wanted,
+            found: HashMap::new(),
+        }
+    }
+
+    fn locate(mut self) -> HashMap<String, (usize, usize)> {
+        if !self.wanted.is_empty() {
+            self.value();
+        }
+        self.found
+    }
+
+    fn value(&mut self) {
+        self.skip_whitespace();
+        let pointer: String = self.path.iter().map(|segment| format!("/{}", segment)).collect();
+        if self.wanted.contains(pointer.as_str()) {
+            self.found.insert(pointer, (self.line, self.pos - self.line_start + 1));
+        }
+        match self.peek() {
+            Some(b'{') => {
+                self.pos += 1;
+                loop {
+                    self.skip_whitespace();
+                    if self.peek() == Some(b'}') {
+                        self.pos += 1;
+                        break;
+                    }
+                    let key = self.string();
+                    self.skip_whitespace();
+                    // ':'
+                    self.pos += 1;
+                    self.path.push(escape(&key));
+                    self.value();
+                    self.path.pop();
+                    self.separator();
+                }
+            }
+            Some(b'[') => {
+                self.pos += 1;
+                let mut index = 0usize;
+                loop {
+                    self.skip_whitespace();
+                    if self.peek() == Some(b']') {
+                        self.pos += 1;
+                        break;
+                    }
+                    self.path.push(index.to_string());
+                    self.value();
+                    self.path.pop();
+                    index += 1;
+                    self.separator();
+                }
+            }
+            Some(b'"') => {
+                self.string();
+            }
+            _ => {
+                while matches!(self.peek(), Some(b) if !matches!(b, b',' | b'}' | b']') && !b.is_ascii_whitespace()) {
+                    self.pos += 1;
+                }
+            }
+        }
+    }
+
+    /// Skip a `,` between items, if there is one
+    fn separator(&mut self) {
+        self.skip_whitespace();
+        if self.peek() == Some(b',') {
+            self.pos += 1;
+        }
+    }
+
+    /// Read a string and return its unescaped contents
+    fn string(&mut self) -> String {
+        let start = self.pos;
+        self.pos += 1;
+        while let Some(byte) = self.peek() {
+            self.pos += 1;
+            match byte {
+                b'\\' => self.pos += 1,
+                b'"' => break,
+                _ => {}
+            }
+        }
+        serde_json::from_slice(&self.bytes[start..self.pos]).unwrap_or_default()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(byte) = self.peek() {
+            if !byte.is_ascii_whitespace() {
+                break;
+            }
+            self.pos += 1;
+            if byte == b'\n' {
+                self.line += 1;
+                self.line_start = self.pos;
+            }
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+}
@@ -0,0 +1,100 @@
+//! Prepared election sessions for repeated what-if runs
+//!
+//! An [`ElectionSession`] indexes election data once and then runs it under
+//! any number of configurations, applying each configuration's overrides as
+//! a delta on top of the index instead of on a copy of the data.
+
+use crate::algorithms::indexed::Index;
+use crate::engine::ElectionEngine;
+use crate::error::ElectionError;
+use crate::models::account_id::AddressFormat;
+use crate::models::election_config::ElectionConfiguration;
+use crate::models::election_data::ElectionData;
+use crate::models::election_result::ElectionResult;
+
+/// Election data prepared for fast repeated runs
+///
+/// Account IDs are interned when the session is created: candidates and
+/// nominators are referred to by their position in the data, and each
+/// nominator's targets are kept as a list of candidate positions. A run
+/// goes through the same pipeline as [`ElectionEngine::execute`], which
+/// applies the configuration's overrides as a delta on top of this index,
+/// so the data is never cloned and no lookups are rebuilt.
+///
+/// Results are the same as [`ElectionEngine::execute`] with the same
+/// configuration. Diagnostics are not generated; use the engine for those.
+///
+/// # Example
+///
+/// ```
+/// use offline_election::{ElectionConfiguration, ElectionData, ElectionOverrides, ElectionSession};
+/// use offline_election::{Nominator, ValidatorCandidate};
+///
+/// # fn example() -> Result<(), offline_election::ElectionError> {
+/// let mut data = ElectionData::new();
+/// data.add_candidate(ValidatorCandidate::new("alice".to_string(), 100))?;
+/// data.add_candidate(ValidatorCandidate::new("bob".to_string(), 100))?;
+/// let mut nominator = Nominator::new("carol".to_string(), 1_000);
+/// nominator.add_target("alice".to_string());
+/// data.add_nominator(nominator)?;
+///
+/// let session = ElectionSession::new(data)?;
+/// for stake in [500, 1_000, 2_000] {
+///     let mut overrides = ElectionOverrides::new();
+///     overrides.set_nominator_stake("carol".to_string(), stake)?;
+///     let config = ElectionConfiguration::new().active_set_size(1).overrides(overrides).build()?;
+///     let result = session.run(&config)?;
+///     assert_eq!(result.selected_validators[0].total_backing_stake, stake);
+/// }
+/// # Ok(())
+/// # }
+/// # example().unwrap();
+/// ```
+pub struct ElectionSession {
+    data: ElectionData,
+    format: AddressFormat,
+    index: Index,
+}
+
+impl ElectionSession {
+    /// Prepare a session, writing account IDs in the data's own format
+    pub fn new(data: ElectionData) -> Result<Self, ElectionError> {
+        let format = data.address_format().unwrap_or(AddressFormat::Hex);
+        Self::with_address_format(data, format)
+    }
+
+    /// Prepare a session, writing account IDs in the given format
+    pub fn with_address_format(mut data: ElectionData, format: AddressFormat) -> Result<Self, ElectionError> {
+        data.validate()?;
+        data.normalize_account_ids(format)?;
+        let index = Index::new(&data)?;
+        Ok(Self { data, format, index })
+    }
+
+    /// The session's election data, with normalized account IDs
+    pub fn data(&self) -> &ElectionData {
+        &self.data
+    }
+
+    /// Format account IDs are written in
+    pub fn address_format(&self) -> AddressFormat {
+        self.format
+    }
+
+    /// Run an election on the session's data
+    ///
+    /// The configuration's overrides apply to this run only. Its address
+    /// format, if set, must be the session's.
+    pub fn run(&self, config: &ElectionConfiguration) -> Result<ElectionResult, ElectionError> {
+        if config.address_format.is_some_and(|format| format != self.format) {
+            return Err(ElectionError::ValidationError {
+                message: format!(
+                    "Session account IDs are written as {:?}; prepare the session with that address format instead",
+                    self.format
+                ),
+                field: Some("address_format".to_string()),
+            });
+        }
+        ElectionEngine::new().run_indexed(config, &self.data, &self.index, self.format, false, None)
+    }
+}
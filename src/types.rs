@@ -1,9 +1,10 @@
 //! Type definitions for algorithm types and data sources
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Election algorithm type
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "kebab-case")]
 pub enum AlgorithmType {
     /// Sequential Phragmen algorithm
@@ -121,6 +121,12 @@ pub fn run_benchmark_with_algorithm(
         algorithm,
         overrides: None,
         block_number: None,
+        strict_snapshot: false,
+        strict: false,
+        min_nominator_bond: None,
+        min_validator_bond: None,
+        chain_profile: None,
+        address_format: None,
     };
     
     let (result, duration) = measure_execution_time(|| {
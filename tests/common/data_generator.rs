@@ -63,3 +63,77 @@ pub fn generate_synthetic_election_data(
     generate_large_scale_election_data(candidate_count, nominator_count, AlgorithmType::SequentialPhragmen)
 }
 
+/// Nominator with the given stake voting for `targets`
+pub fn nominator(id: &str, stake: u128, targets: &[&str]) -> Nominator {
+    let mut nominator = Nominator::new(id.to_string(), stake);
+    nominator.targets = targets.iter().map(|t| t.to_string()).collect();
+    nominator
+}
+
+/// Election data with equally staked candidates and the given nominators
+pub fn election_data(candidates: &[&str], candidate_stake: u128, nominators: Vec<Nominator>) -> ElectionData {
+    ElectionData {
+        candidates: candidates
+            .iter()
+            .map(|id| ValidatorCandidate::new(id.to_string(), candidate_stake))
+            .collect(),
+        nominators,
+        metadata: None,
+    }
+}
+
+/// Generate deterministic data with overlapping nominations of uneven stake
+///
+/// Candidates `v0` to `v11` are staked in multiples of 100, some at zero, and
+/// nominators `n0` to `n39` each vote for one to five of them.
+pub fn generate_overlapping_election_data() -> ElectionData {
+    let candidates = (0..12u128)
+        .map(|i| ValidatorCandidate::new(format!("v{}", i), 100 * (i % 4)))
+        .collect();
+    let nominators = (0..40u128)
+        .map(|i| {
+            let mut nominator = Nominator::new(format!("n{}", i), 1_000 + (i * 7_919) % 5_003);
+            nominator.targets = (0..(1 + i % 5))
+                .map(|k| format!("v{}", (i * 3 + k * 5) % 12))
+                .collect();
+            nominator.targets.sort();
+            nominator.targets.dedup();
+            nominator
+        })
+        .collect();
+    ElectionData {
+        candidates,
+        nominators,
+        metadata: None,
+    }
+}
+
+/// Generate data whose nominator stakes don't fit in a `u64`
+///
+/// Candidate `v{i}` has a stake of `1_000 * (i + 1)`. Nominator `n{i}` has
+/// `u64::MAX * 2 + i` and votes for `i % (max_targets + 1)` consecutive
+/// candidates starting at `v{i}`; `max_targets` must be below
+/// `candidate_count`.
+pub fn generate_large_stake_election_data(
+    candidate_count: usize,
+    nominator_count: usize,
+    max_targets: usize,
+) -> ElectionData {
+    let candidates = (0..candidate_count)
+        .map(|i| ValidatorCandidate::new(format!("v{}", i), 1_000 * (i as u128 + 1)))
+        .collect();
+    let nominators = (0..nominator_count)
+        .map(|i| {
+            let mut nominator = Nominator::new(format!("n{}", i), u64::MAX as u128 * 2 + i as u128);
+            nominator.targets = (0..i % (max_targets + 1))
+                .map(|k| format!("v{}", (i + k) % candidate_count))
+                .collect();
+            nominator
+        })
+        .collect();
+    ElectionData {
+        candidates,
+        nominators,
+        metadata: None,
+    }
+}
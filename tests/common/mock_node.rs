@@ -0,0 +1,370 @@
+//! Local Substrate node stand-in for RPC loader tests
+//!
+//! Serves a fixed, in-memory chain state over JSON-RPC (HTTP and WebSocket on
+//! the same port) using the jsonrpsee server. Only the methods used by
+//! `RpcLoader` are implemented, and every call is counted so tests can assert
+//! on how the loader talks to the node.
+
+use jsonrpsee::server::{RpcModule, ServerBuilder, ServerHandle, SubscriptionMessage};
+use jsonrpsee::types::{ErrorObject, ErrorObjectOwned};
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::hash::Hasher;
+use std::sync::{Arc, Mutex};
+use twox_hash::XxHash64;
+
+/// JSON-RPC error code for unknown methods
+const METHOD_NOT_FOUND: i32 = -32601;
+/// JSON-RPC error code for generic server errors
+const SERVER_ERROR: i32 = -32000;
+
+/// In-memory chain state served by [`MockNode`]
+#[derive(Default)]
+pub struct MockChain {
+    /// Value returned by `system_chain`
+    pub chain_name: String,
+    /// Value returned by `system_properties`
+    pub properties: Value,
+    /// Hex-encoded runtime metadata returned by `state_getMetadata`
+    pub metadata: Option<String>,
+    /// Block number -> block hash
+    pub block_hashes: BTreeMap<u64, String>,
+    /// Block hash -> storage key -> hex-encoded value
+    pub storage: HashMap<String, BTreeMap<String, String>>,
+    /// Methods that respond with "method not found"
+    pub unsupported_methods: HashSet<String>,
+    /// Methods that respond with a transient "service unavailable" error
+    pub failing_methods: HashSet<String>,
+    /// Most changes a `state_queryStorageAt` response includes, like a pruned node answering partially
+    pub max_query_changes: Option<usize>,
+    calls: Mutex<HashMap<String, usize>>,
+}
+
+impl MockChain {
+    /// Create a chain with a single block holding the given storage
+    pub fn with_block(number: u64, hash: &str, storage: BTreeMap<String, String>) -> Self {
+        let mut chain = Self {
+            chain_name: "Development".to_string(),
+            ..Self::default()
+        };
+        chain.block_hashes.insert(number, hash.to_string());
+        chain.storage.insert(hash.to_string(), storage);
+        chain
+    }
+
+    /// Make a method respond with "method not found"
+    pub fn unsupported(mut self, method: &str) -> Self {
+        self.unsupported_methods.insert(method.to_string());
+        self
+    }
+
+    /// Make a method respond with a transient "service unavailable" error
+    pub fn failing(mut self, method: &str) -> Self {
+        self.failing_methods.insert(method.to_string());
+        self
+    }
+
+    /// Leave all but the first `max` keys out of `state_queryStorageAt` responses
+    pub fn partial_queries(mut self, max: usize) -> Self {
+        self.max_query_changes = Some(max);
+        self
+    }
+
+    /// Number of times a method has been called
+    pub fn calls(&self, method: &str) -> usize {
+        self.calls.lock().unwrap().get(method).copied().unwrap_or(0)
+    }
+
+    fn handle(&self, method: &str, args: Vec<Value>) -> Result<Value, ErrorObjectOwned> {
+        *self.calls.lock().unwrap().entry(method.to_string()).or_insert(0) += 1;
+        if self.unsupported_methods.contains(method) {
+            return Err(ErrorObject::owned(METHOD_NOT_FOUND, "Method not found", None::<()>));
+        }
+        if self.failing_methods.contains(method) {
+            return Err(ErrorObject::owned(SERVER_ERROR, "Service unavailable", None::<()>));
+        }
+
+        let arg_str = |i: usize| args.get(i).and_then(|v| v.as_str()).map(str::to_string);
+        let empty = BTreeMap::new();
+        let storage_at = |i: usize| {
+            let hash = arg_str(i).unwrap_or_else(|| self.latest_hash());
+            self.storage.get(&hash).unwrap_or(&empty)
+        };
+
+        match method {
+            "system_chain" => Ok(json!(self.chain_name)),
+            "system_properties" => Ok(self.properties.clone()),
+            "state_getMetadata" => Ok(self.metadata.as_ref().map_or(Value::Null, |m| json!(m))),
+            "chain_getBlockHash" => {
+                let number = match args.first() {
+                    Some(Value::String(s)) => u64::from_str_radix(s.trim_start_matches("0x"), 16).ok(),
+                    Some(Value::Number(n)) => n.as_u64(),
+                    _ => self.block_hashes.keys().next_back().copied(),
+                };
+                Ok(number
+                    .and_then(|n| self.block_hashes.get(&n))
+                    .map_or(Value::Null, |hash| json!(hash)))
+            }
+            "chain_getHeader" => {
+                let hash = arg_str(0).unwrap_or_else(|| self.latest_hash());
+                Ok(self
+                    .block_hashes
+                    .iter()
+                    .find(|(_, h)| **h == hash)
+                    .map_or(Value::Null, |(number, _)| json!({ "number": format!("0x{:x}", number) })))
+            }
+            "state_getStorage" => {
+                let key = arg_str(0).unwrap_or_default();
+                Ok(storage_at(1).get(&key).map_or(Value::Null, |v| json!(v)))
+            }
+            "state_getKeys" => {
+                let prefix = arg_str(0).unwrap_or_default();
+                let keys: Vec<&String> = storage_at(1).keys().filter(|k| k.starts_with(&prefix)).collect();
+                Ok(json!(keys))
+            }
+            "state_getKeysPaged" => {
+                let prefix = arg_str(0).unwrap_or_default();
+                let count = args.get(1).and_then(|v| v.as_u64()).unwrap_or(1000) as usize;
+                let start_key = arg_str(2);
+                let storage = storage_at(3);
+                let keys: Vec<&String> = storage
+                    .keys()
+                    .filter(|k| k.starts_with(&prefix))
+                    .filter(|k| start_key.as_ref().map_or(true, |start| *k > start))
+                    .take(count)
+                    .collect();
+                Ok(json!(keys))
+            }
+            "state_queryStorageAt" => {
+                let keys: Vec<String> = args
+                    .first()
+                    .and_then(|v| serde_json::from_value(v.clone()).ok())
+                    .unwrap_or_default();
+                let hash = arg_str(1).unwrap_or_else(|| self.latest_hash());
+                let storage = storage_at(1);
+                let changes: Vec<Value> = keys
+                    .iter()
+                    .take(self.max_query_changes.unwrap_or(usize::MAX))
+                    .map(|k| json!([k, storage.get(k)]))
+                    .collect();
+                Ok(json!([{ "block": hash, "changes": changes }]))
+            }
+            _ => Err(ErrorObject::owned(METHOD_NOT_FOUND, "Method not found", None::<()>)),
+        }
+    }
+
+    /// Headers announced to `chain_subscribeFinalizedHeads` subscribers, oldest first
+    fn finalized_headers(&self) -> Vec<Value> {
+        let mut parent_hash = format!("0x{}", "00".repeat(32));
+        self.block_hashes
+            .iter()
+            .map(|(number, hash)| {
+                let header = json!({ "number": format!("0x{:x}", number), "parentHash": parent_hash });
+                parent_hash = hash.clone();
+                header
+            })
+            .collect()
+    }
+
+    fn latest_hash(&self) -> String {
+        self.block_hashes.values().next_back().cloned().unwrap_or_default()
+    }
+}
+
+/// Running mock node
+pub struct MockNode {
+    /// HTTP URL of the node
+    pub url: String,
+    /// WebSocket URL of the node
+    pub ws_url: String,
+    /// Chain state served by the node
+    pub chain: Arc<MockChain>,
+    handle: ServerHandle,
+}
+
+impl MockNode {
+    /// Start serving the chain on a random local port
+    pub async fn start(chain: MockChain) -> Self {
+        Self::start_with_max_connections(chain, 100).await
+    }
+
+    /// Start serving the chain, refusing connections beyond `max_connections`
+    pub async fn start_with_max_connections(chain: MockChain, max_connections: u32) -> Self {
+        let chain = Arc::new(chain);
+        let server = ServerBuilder::default()
+            .max_connections(max_connections)
+            .build("127.0.0.1:0")
+            .await
+            .unwrap();
+        let addr = server.local_addr().unwrap();
+
+        let mut module = RpcModule::new(chain.clone());
+        for method in [
+            "system_chain",
+            "system_properties",
+            "state_getMetadata",
+            "chain_getBlockHash",
+            "chain_getHeader",
+            "state_getStorage",
+            "state_getKeys",
+            "state_getKeysPaged",
+            "state_queryStorageAt",
+        ] {
+            module
+                .register_method(method, move |params, chain| {
+                    let args: Vec<Value> = params.parse().unwrap_or_default();
+                    chain.handle(method, args)
+                })
+                .unwrap();
+        }
+        module
+            .register_subscription(
+                "chain_subscribeFinalizedHeads",
+                "chain_finalizedHead",
+                "chain_unsubscribeFinalizedHeads",
+                |_, pending, chain| async move {
+                    *chain.calls.lock().unwrap().entry("chain_subscribeFinalizedHeads".to_string()).or_insert(0) += 1;
+                    let Ok(sink) = pending.accept().await else { return };
+                    for header in chain.finalized_headers() {
+                        let message = SubscriptionMessage::from_json(&header).unwrap();
+                        if sink.send(message).await.is_err() {
+                            return;
+                        }
+                    }
+                    // Keep the subscription open until the client goes away
+                    sink.closed().await;
+                },
+            )
+            .unwrap();
+
+        Self {
+            url: format!("http://{}", addr),
+            ws_url: format!("ws://{}", addr),
+            chain,
+            handle: server.start(module),
+        }
+    }
+}
+
+impl Drop for MockNode {
+    fn drop(&mut self) {
+        self.handle.stop().ok();
+    }
+}
+
+/// Deterministic 32-byte account ID filled with `byte`
+pub fn account(byte: u8) -> [u8; 32] {
+    [byte; 32]
+}
+
+/// Deterministic 32-byte account ID for the `index`-th account of a kind
+pub fn indexed_account(kind: u8, index: u32) -> [u8; 32] {
+    let mut account = [kind; 32];
+    account[..4].copy_from_slice(&index.to_be_bytes());
+    account
+}
+
+/// Hex representation of an account ID, as produced by `RpcLoader`
+pub fn account_hex(account: &[u8; 32]) -> String {
+    format!("0x{}", hex::encode(account))
+}
+
+/// Storage prefix `twox128(pallet) ++ twox128(item)`
+pub fn storage_prefix(pallet: &str, item: &str) -> String {
+    format!("0x{}{}", hex::encode(twox_128(pallet.as_bytes())), hex::encode(twox_128(item.as_bytes())))
+}
+
+/// Storage key of a map entry keyed by account with a `*_concat` hasher
+///
+/// The loader only skips over the hash bytes, so they are zero-filled here.
+pub fn map_key(pallet: &str, item: &str, hash_len: usize, account: &[u8; 32]) -> String {
+    format!("{}{}{}", storage_prefix(pallet, item), "00".repeat(hash_len), hex::encode(account))
+}
+
+/// Storage key of a map entry hashed with `Twox64Concat`
+pub fn twox64_map_key(pallet: &str, item: &str, key: &[u8]) -> String {
+    format!("{}{}", storage_prefix(pallet, item), twox64_concat(key))
+}
+
+/// Hex of `twox64(data) ++ data`, the `Twox64Concat` hasher output
+pub fn twox64_concat(data: &[u8]) -> String {
+    let mut hasher = XxHash64::with_seed(0);
+    hasher.write(data);
+    format!("{}{}", hex::encode(hasher.finish().to_le_bytes()), hex::encode(data))
+}
+
+/// SCALE-encode a `u32`
+pub fn encode_u32(value: u32) -> String {
+    format!("0x{}", hex::encode(value.to_le_bytes()))
+}
+
+/// SCALE-encode a list of accounts (`Vec<AccountId32>`)
+pub fn encode_accounts(accounts: &[[u8; 32]]) -> String {
+    let mut bytes = compact_len(accounts.len());
+    for account in accounts {
+        bytes.extend_from_slice(account);
+    }
+    format!("0x{}", hex::encode(bytes))
+}
+
+/// Encode a `StakingLedger` with the given stash and total stake
+pub fn encode_ledger(stash: &[u8; 32], total: u128) -> String {
+    let mut bytes = stash.to_vec();
+    bytes.extend_from_slice(&total.to_le_bytes());
+    bytes.extend_from_slice(&total.to_le_bytes());
+    format!("0x{}", hex::encode(bytes))
+}
+
+/// Encode `ValidatorPrefs` with the given commission (in parts per billion)
+pub fn encode_validator_prefs(commission: u32, blocked: bool) -> String {
+    let mut bytes = compact_u32(commission);
+    bytes.push(u8::from(blocked));
+    format!("0x{}", hex::encode(bytes))
+}
+
+/// Nominator entry as `(account, stake, targets)`
+pub type MockNominator = ([u8; 32], u128, Vec<[u8; 32]>);
+
+/// Storage of a relay-chain staking snapshot
+pub fn staking_storage(validators: &[[u8; 32]], nominators: &[MockNominator]) -> BTreeMap<String, String> {
+    let mut storage = BTreeMap::new();
+    storage.insert(storage_prefix("Session", "Validators"), encode_accounts(validators));
+    for validator in validators {
+        storage.insert(map_key("Staking", "Validators", 8, validator), encode_validator_prefs(0, false));
+    }
+    for (nominator, stake, targets) in nominators {
+        storage.insert(map_key("Staking", "Nominators", 16, nominator), encode_accounts(targets));
+        storage.insert(map_key("Staking", "Ledger", 8, nominator), encode_ledger(nominator, *stake));
+    }
+    storage
+}
+
+fn compact_u32(value: u32) -> Vec<u8> {
+    match value {
+        0..=0x3fff => compact_len(value as usize),
+        0x4000..=0x3fff_ffff => ((value << 2) | 0b10).to_le_bytes().to_vec(),
+        _ => {
+            let mut bytes = vec![0b11];
+            bytes.extend_from_slice(&value.to_le_bytes());
+            bytes
+        }
+    }
+}
+
+fn compact_len(len: usize) -> Vec<u8> {
+    match len {
+        0..=0x3f => vec![(len as u8) << 2],
+        0x40..=0x3fff => (((len as u16) << 2) | 0b01).to_le_bytes().to_vec(),
+        _ => (((len as u32) << 2) | 0b10).to_le_bytes().to_vec(),
+    }
+}
+
+fn twox_128(data: &[u8]) -> [u8; 16] {
+    let mut result = [0u8; 16];
+    for (seed, chunk) in result.chunks_mut(8).enumerate() {
+        let mut hasher = XxHash64::with_seed(seed as u64);
+        hasher.write(data);
+        chunk.copy_from_slice(&hasher.finish().to_le_bytes());
+    }
+    result
+}
@@ -14,6 +14,7 @@ pub mod benchmark_utils;
 pub mod data_generator;
 pub mod fixture_loader;
 pub mod memory_measurement;
+pub mod mock_node;
 pub mod models;
 pub mod rpc_retry;
 pub mod rpc_utils;
@@ -25,6 +26,7 @@ pub use benchmark_utils::*;
 pub use data_generator::*;
 pub use fixture_loader::*;
 pub use memory_measurement::*;
+pub use mock_node::*;
 pub use models::*;
 pub use rpc_retry::*;
 pub use rpc_utils::*;
@@ -69,6 +69,7 @@ pub async fn fetch_chain_snapshot(
         selected_validators: Vec::new(),
         stake_distribution: Vec::new(),
         total_stake: 0,
+        accounting: Default::default(),
         algorithm_used: AlgorithmType::SequentialPhragmen,
         execution_metadata: ExecutionMetadata {
             block_number: Some(block_number),
@@ -76,6 +77,8 @@ pub async fn fetch_chain_snapshot(
             data_source: Some("chain_snapshot".to_string()),
         },
         diagnostics: None,
+        pool_backing: None,
+        warnings: Vec::new(),
     };
     
     Ok(ChainSnapshot {
@@ -0,0 +1,115 @@
+//! Integration test: SS58 and hex account IDs
+
+mod common;
+
+use common::data_generator::nominator;
+use offline_election::{
+    AccountId, AddressFormat, AlgorithmType, ElectionConfiguration, ElectionData, ElectionEngine, ElectionError,
+    ElectionOverrides, ValidatorCandidate,
+};
+
+const ALICE_SS58: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+const ALICE_HEX: &str = "0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d";
+
+fn account(byte: u8) -> AccountId {
+    AccountId::new([byte; 32])
+}
+
+#[test]
+fn test_ss58_and_hex_round_trip() {
+    let alice: AccountId = ALICE_SS58.parse().unwrap();
+    assert_eq!(alice.to_hex(), ALICE_HEX);
+    assert_eq!(alice.to_ss58(42), ALICE_SS58);
+    assert_eq!(ALICE_HEX.parse::<AccountId>().unwrap(), alice);
+
+    // Two-byte prefixes survive the round trip too
+    let encoded = alice.to_ss58(1_000);
+    assert_eq!(AccountId::from_ss58(&encoded).unwrap(), (alice, 1_000));
+
+    assert_eq!(AddressFormat::Ss58(0).normalize(ALICE_HEX).unwrap(), alice.to_ss58(0));
+    assert_eq!(AddressFormat::Hex.normalize("validator-1").unwrap(), "validator-1");
+}
+
+#[test]
+fn test_bad_checksum_is_a_label_and_malformed_hex_is_rejected() {
+    let mut corrupted = ALICE_SS58.to_string();
+    corrupted.replace_range(10..11, if &corrupted[10..11] == "A" { "B" } else { "A" });
+    assert!(matches!(
+        corrupted.parse::<AccountId>(),
+        Err(ElectionError::ValidationError { .. })
+    ));
+    assert!("0xd435".parse::<AccountId>().is_err());
+
+    // Placeholder IDs that fail the checksum are kept as labels
+    assert!(AccountId::parse_address(&corrupted).unwrap().is_none());
+    assert_eq!(AddressFormat::Hex.normalize(&corrupted).unwrap(), corrupted);
+
+    let mut overrides = ElectionOverrides::new();
+    assert!(overrides.set_candidate_stake(corrupted, 1).is_ok());
+    assert!(overrides.add_voting_edge("n1".to_string(), "0xzz".to_string()).is_err());
+}
+
+#[test]
+fn test_same_account_as_hex_and_ss58_is_a_duplicate() {
+    let alice: AccountId = ALICE_HEX.parse().unwrap();
+    let data = ElectionData {
+        candidates: vec![
+            ValidatorCandidate::new(ALICE_HEX.to_string(), 100),
+            ValidatorCandidate::new(alice.to_ss58(0), 100),
+        ],
+        nominators: vec![],
+        metadata: None,
+    };
+
+    let err = data.validate().unwrap_err();
+    assert!(err.to_string().contains("same account"), "{}", err);
+}
+
+#[test]
+fn test_ss58_override_applies_to_hex_data_and_output_uses_chosen_format() {
+    let (a, b, n1, n2) = (account(1), account(2), account(3), account(4));
+    let data = ElectionData {
+        candidates: vec![
+            ValidatorCandidate::new(a.to_hex(), 1_000),
+            ValidatorCandidate::new(b.to_hex(), 1_000),
+        ],
+        nominators: vec![
+            nominator(&n1.to_hex(), 500, &[&a.to_hex()]),
+            nominator(&n2.to_hex(), 100, &[&b.to_hex()]),
+        ],
+        metadata: None,
+    };
+
+    // n2's stake is raised through its Polkadot address
+    let mut overrides = ElectionOverrides::new();
+    overrides.set_nominator_stake(n2.to_ss58(0), 5_000).unwrap();
+    let config = ElectionConfiguration::new()
+        .algorithm(AlgorithmType::SequentialPhragmen)
+        .active_set_size(2)
+        .overrides(overrides)
+        .build()
+        .unwrap();
+    let result = ElectionEngine::new().execute(&config, &data).unwrap();
+    let backing = |id: String| {
+        result.selected_validators.iter().find(|v| v.account_id == id).unwrap().total_backing_stake
+    };
+    assert_eq!(backing(b.to_hex()), 5_000);
+
+    let config = ElectionConfiguration::new()
+        .algorithm(AlgorithmType::SequentialPhragmen)
+        .active_set_size(2)
+        .address_format(AddressFormat::Ss58(2))
+        .build()
+        .unwrap();
+    let result = ElectionEngine::new().execute(&config, &data).unwrap();
+    let mut selected: Vec<_> = result.selected_validators.iter().map(|v| v.account_id.clone()).collect();
+    selected.sort();
+    let mut expected = vec![a.to_ss58(2), b.to_ss58(2)];
+    expected.sort();
+    assert_eq!(selected, expected);
+    let mut voters: Vec<_> = result.stake_distribution.iter().map(|s| s.nominator_id.clone()).collect();
+    voters.sort();
+    let mut expected = vec![n1.to_ss58(2), n2.to_ss58(2)];
+    expected.sort();
+    assert_eq!(voters, expected);
+}
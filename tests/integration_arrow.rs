@@ -0,0 +1,181 @@
+//! Integration test: Arrow record batches and Parquet export
+
+mod common;
+
+use common::data_generator::generate_large_stake_election_data;
+use clap::Parser;
+use offline_election::cli::{ConvertCommand, RunCommand};
+use offline_election::models::election_data::ElectionMetadata;
+use offline_election::ElectionData;
+
+fn data() -> ElectionData {
+    let mut data = generate_large_stake_election_data(6, 12, 2);
+    data.metadata = Some(ElectionMetadata {
+        block_number: Some(21_000_000),
+        chain: Some("polkadot".to_string()),
+        era: Some(1_500),
+        completeness: None,
+        candidate_stakes_unknown: false,
+    });
+    data
+}
+
+fn temp_dir(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("arrow-{}-{}", name, std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[cfg(feature = "arrow")]
+mod arrow {
+    use super::*;
+    use arrow_array::{Array, Decimal128Array, RecordBatch, StringArray, UInt32Array, UInt64Array};
+    use offline_election::arrow::{
+        allocations_schema, candidates_schema, edges_schema, nominators_schema, winners_schema, DataBatches,
+        Provenance, ResultBatches,
+    };
+    use offline_election::{ElectionConfiguration, ElectionEngine, ElectionError, ValidatorCandidate};
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    fn read_parquet(path: &std::path::Path) -> RecordBatch {
+        let file = std::fs::File::open(path).unwrap();
+        let mut reader = ParquetRecordBatchReaderBuilder::try_new(file).unwrap().build().unwrap();
+        reader.next().unwrap().unwrap()
+    }
+
+    fn column<'a, T: 'static>(batch: &'a RecordBatch, name: &str) -> &'a T {
+        batch.column_by_name(name).unwrap().as_any().downcast_ref::<T>().unwrap()
+    }
+
+    #[test]
+    fn test_data_batches_follow_schemas() {
+        let data = data();
+        let batches = DataBatches::new(&data, &Provenance::of_data(&data)).unwrap();
+        assert_eq!(batches.candidates.schema(), candidates_schema());
+        assert_eq!(batches.nominators.schema(), nominators_schema());
+        assert_eq!(batches.edges.schema(), edges_schema());
+        assert_eq!(batches.candidates.num_rows(), 6);
+        assert_eq!(batches.nominators.num_rows(), 12);
+        let edge_count: usize = data.nominators.iter().map(|n| n.targets.len()).sum();
+        assert_eq!(batches.edges.num_rows(), edge_count);
+
+        let stakes = column::<Decimal128Array>(&batches.nominators, "stake");
+        assert_eq!(stakes.value(3) as u128, data.nominators[3].stake);
+        let positions = column::<UInt32Array>(&batches.edges, "position");
+        assert_eq!((positions.value(0), positions.value(1)), (0, 0));
+        assert_eq!(column::<UInt64Array>(&batches.edges, "block_number").value(0), 21_000_000);
+        assert_eq!(column::<UInt32Array>(&batches.candidates, "era").value(5), 1_500);
+        assert!(column::<StringArray>(&batches.candidates, "algorithm").is_null(0));
+        assert!(column::<arrow_array::BooleanArray>(&batches.candidates, "blocked").is_null(0));
+    }
+
+    #[test]
+    fn test_result_batches_carry_provenance() {
+        let data = data();
+        let result = ElectionEngine::new()
+            .execute(&ElectionConfiguration::new().active_set_size(3), &data)
+            .unwrap();
+        let batches = ResultBatches::new(&result, &Provenance::of_result(&result, &data)).unwrap();
+        assert_eq!(batches.winners.schema(), winners_schema());
+        assert_eq!(batches.allocations.schema(), allocations_schema());
+        assert_eq!(batches.winners.num_rows(), result.selected_validators.len());
+        assert_eq!(batches.allocations.num_rows(), result.stake_distribution.len());
+
+        let account_ids = column::<StringArray>(&batches.winners, "account_id");
+        assert_eq!(account_ids.value(0), result.selected_validators[0].account_id);
+        let algorithm = column::<StringArray>(&batches.allocations, "algorithm");
+        assert_eq!(algorithm.value(0), "sequential-phragmen");
+        assert_eq!(column::<StringArray>(&batches.winners, "chain").value(0), "polkadot");
+    }
+
+    #[test]
+    fn test_rejects_amounts_beyond_decimal_precision() {
+        let data = ElectionData {
+            candidates: vec![ValidatorCandidate::new("v0".to_string(), u128::MAX)],
+            nominators: vec![],
+            metadata: None,
+        };
+        let error = DataBatches::new(&data, &Provenance::default()).err().unwrap();
+        assert!(matches!(error, ElectionError::InvalidData { ref message } if message.contains("Decimal128")));
+    }
+
+    #[tokio::test]
+    async fn test_cli_writes_parquet() {
+        let dir = temp_dir("cli");
+        let input = dir.join("data.json");
+        std::fs::write(&input, serde_json::to_vec(&data()).unwrap()).unwrap();
+
+        let data_dir = dir.join("data");
+        ConvertCommand::parse_from(["convert", input.to_str().unwrap(), data_dir.to_str().unwrap(), "--to", "parquet"])
+            .execute()
+            .await
+            .unwrap();
+        let nominators = read_parquet(&data_dir.join("nominators.parquet"));
+        assert_eq!(nominators.schema(), nominators_schema());
+        assert_eq!(nominators.num_rows(), 12);
+        assert!(data_dir.join("candidates.parquet").exists());
+        assert!(data_dir.join("edges.parquet").exists());
+
+        let result_dir = dir.join("result");
+        RunCommand::parse_from([
+            "run",
+            "--algorithm",
+            "parallel-phragmen",
+            "--active-set-size",
+            "3",
+            "--input-file",
+            input.to_str().unwrap(),
+            "--format",
+            "parquet",
+            "--output-file",
+            result_dir.to_str().unwrap(),
+        ])
+        .execute()
+        .await
+        .unwrap();
+        let winners = read_parquet(&result_dir.join("winners.parquet"));
+        assert_eq!(winners.schema(), winners_schema());
+        assert_eq!(winners.num_rows(), 3);
+        assert_eq!(column::<StringArray>(&winners, "algorithm").value(0), "parallel-phragmen");
+        let allocations = read_parquet(&result_dir.join("allocations.parquet"));
+        assert_eq!(column::<UInt32Array>(&allocations, "era").value(0), 1_500);
+    }
+}
+
+#[cfg(not(feature = "arrow"))]
+#[tokio::test]
+async fn test_parquet_output_requires_feature() {
+    let dir = temp_dir("no-feature");
+    let input = dir.join("data.json");
+    std::fs::write(&input, serde_json::to_vec(&data()).unwrap()).unwrap();
+
+    let error = ConvertCommand::parse_from([
+        "convert",
+        input.to_str().unwrap(),
+        dir.join("out").to_str().unwrap(),
+        "--to",
+        "parquet",
+    ])
+    .execute()
+    .await
+    .unwrap_err();
+    assert!(error.to_string().contains("`arrow` feature"));
+
+    let error = RunCommand::parse_from([
+        "run",
+        "--algorithm",
+        "sequential-phragmen",
+        "--active-set-size",
+        "3",
+        "--input-file",
+        input.to_str().unwrap(),
+        "--format",
+        "parquet",
+        "--output-file",
+        dir.join("result").to_str().unwrap(),
+    ])
+    .execute()
+    .await
+    .unwrap_err();
+    assert!(error.to_string().contains("`arrow` feature"));
+}
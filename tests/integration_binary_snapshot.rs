@@ -0,0 +1,198 @@
+//! Integration test: binary election data snapshots
+
+mod common;
+
+use common::data_generator::generate_large_stake_election_data;
+use clap::Parser;
+use offline_election::cli::ConvertCommand;
+use offline_election::input::binary::{SNAPSHOT_MAGIC, SNAPSHOT_VERSION};
+use offline_election::input::{load_data_file, BinaryLoader, BinaryWriter};
+use offline_election::models::election_data::{ElectionMetadata, SnapshotCompleteness};
+use offline_election::models::nominator::{NominationPool, NominatorMetadata, PoolMember};
+use offline_election::models::validator::CandidateMetadata;
+use offline_election::models::AccountId;
+use offline_election::{ElectionData, ElectionError, Nominator, ValidatorCandidate};
+
+/// Data using every optional field
+fn data() -> ElectionData {
+    let mut data = generate_large_stake_election_data(30, 500, 5);
+    data.candidates[3].metadata = Some(CandidateMetadata {
+        commission_rate: Some(5),
+        on_chain_status: Some("active".to_string()),
+        blocked: true,
+    });
+    data.candidates[4].metadata = Some(CandidateMetadata::default());
+
+    let mut extra = std::collections::HashMap::new();
+    extra.insert("note".to_string(), serde_json::json!({"source": "test", "weights": [1, 2]}));
+    extra.insert("tag".to_string(), serde_json::json!("pool"));
+    data.nominators[7].metadata = Some(NominatorMetadata {
+        pool: Some(NominationPool {
+            pool_id: 12,
+            members: vec![
+                PoolMember {
+                    account_id: "m1".to_string(),
+                    points: 10u128.pow(30),
+                },
+                PoolMember {
+                    account_id: "m2".to_string(),
+                    points: 1,
+                },
+            ],
+        }),
+        extra,
+    });
+
+    data.metadata = Some(ElectionMetadata {
+        block_number: Some(19_000_000),
+        chain: Some("polkadot".to_string()),
+        era: Some(1_400),
+        completeness: Some(SnapshotCompleteness {
+            expected_validators: Some(31),
+            loaded_validators: 30,
+            expected_nominators: None,
+            loaded_nominators: 500,
+            undecodable_keys: vec!["0xdead".to_string()],
+        }),
+        candidate_stakes_unknown: true,
+    });
+    data
+}
+
+fn encode(data: &ElectionData, compress: bool) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    BinaryWriter::new().compressed(compress).write(data, &mut bytes).unwrap();
+    bytes
+}
+
+fn temp_dir(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("binary-snapshot-{}-{}", name, std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn test_round_trips_with_and_without_compression() {
+    let data = data();
+    let plain = encode(&data, false);
+    let compressed = encode(&data, true);
+
+    assert!(plain.starts_with(&SNAPSHOT_MAGIC));
+    assert_eq!(BinaryLoader::new().load_from_reader(plain.as_slice()).unwrap(), data);
+    assert_eq!(BinaryLoader::new().load_from_reader(compressed.as_slice()).unwrap(), data);
+
+    let json = serde_json::to_vec(&data).unwrap();
+    assert!(plain.len() < json.len() / 2, "{} bytes against {} of JSON", plain.len(), json.len());
+    assert!(compressed.len() < plain.len());
+}
+
+#[test]
+fn test_header_describes_snapshot() {
+    let header = BinaryLoader::new().read_header(encode(&data(), true).as_slice()).unwrap();
+    assert_eq!(header.version, SNAPSHOT_VERSION);
+    assert_eq!(header.chain.as_deref(), Some("polkadot"));
+    assert_eq!(header.block_number, Some(19_000_000));
+    assert!(header.compressed);
+
+    let mut no_metadata = data();
+    no_metadata.metadata = None;
+    let header = BinaryLoader::new().read_header(encode(&no_metadata, false).as_slice()).unwrap();
+    assert_eq!((header.chain, header.block_number, header.compressed), (None, None, false));
+}
+
+#[test]
+fn test_rejects_corrupt_and_unknown_snapshots() {
+    let loader = BinaryLoader::new();
+    let invalid = |bytes: &[u8]| match loader.load_from_reader(bytes) {
+        Err(ElectionError::InvalidData { message }) => message,
+        other => panic!("expected invalid data, got {:?}", other),
+    };
+
+    let mut corrupt = encode(&data(), false);
+    let last = corrupt.len() - 1;
+    corrupt[last] ^= 0xff;
+    assert!(invalid(&corrupt).contains("checksum"));
+
+    let mut future = encode(&data(), false);
+    future[SNAPSHOT_MAGIC.len()] = 9;
+    assert!(invalid(&future).contains("Unsupported binary snapshot version 9"));
+
+    let truncated = encode(&data(), true);
+    assert!(!invalid(&truncated[..truncated.len() / 2]).is_empty());
+    assert!(invalid(b"{\"candidates\": []}").contains("magic"));
+}
+
+#[test]
+fn test_nominations_are_stored_by_candidate() {
+    let account = AccountId::new([7; 32]);
+    let mut nominator = Nominator::new("n".to_string(), 10);
+    nominator.targets = vec![account.to_hex()];
+    let data = ElectionData {
+        candidates: vec![ValidatorCandidate::new(account.to_ss58(0), 1)],
+        nominators: vec![nominator],
+        metadata: None,
+    };
+
+    // The hex nomination is read back written like its candidate
+    let loaded = BinaryLoader::new().load_from_reader(encode(&data, false).as_slice()).unwrap();
+    assert_eq!(loaded.nominators[0].targets, vec![account.to_ss58(0)]);
+}
+
+#[test]
+fn test_addresses_are_stored_as_raw_bytes() {
+    let mut candidates: Vec<ValidatorCandidate> =
+        (0..100u8).map(|i| ValidatorCandidate::new(AccountId::new([i; 32]).to_hex(), 1)).collect();
+    // IDs that would not render back the same way stay as they are
+    candidates.push(ValidatorCandidate::new(AccountId::new([200; 32]).to_hex().to_uppercase().replace("0X", "0x"), 1));
+    candidates.push(ValidatorCandidate::new(AccountId::new([201; 32]).to_ss58(2), 1));
+    candidates.push(ValidatorCandidate::new("label".to_string(), 1));
+    let data = ElectionData { candidates, nominators: Vec::new(), metadata: None };
+
+    let bytes = encode(&data, false);
+    // 32 bytes per address rather than 66 hex characters
+    assert!(bytes.len() < 100 * 40, "snapshot is {} bytes", bytes.len());
+    let loaded = BinaryLoader::new().load_from_reader(bytes.as_slice()).unwrap();
+    assert_eq!(loaded, data);
+}
+
+#[test]
+fn test_data_files_are_detected_by_magic_bytes() {
+    let dir = temp_dir("detect");
+    let binary = dir.join("snapshot.json");
+    let json = dir.join("snapshot.bin");
+    BinaryWriter::new().compressed(true).write_to_file(&data(), &binary).unwrap();
+    std::fs::write(&json, serde_json::to_vec(&data()).unwrap()).unwrap();
+
+    assert!(BinaryLoader::is_snapshot_file(&binary).unwrap());
+    assert!(!BinaryLoader::is_snapshot_file(&json).unwrap());
+    assert_eq!(load_data_file(binary).unwrap(), data());
+    assert_eq!(load_data_file(json).unwrap(), data());
+}
+
+#[tokio::test]
+async fn test_convert_command_round_trips() {
+    let dir = temp_dir("convert");
+    let json = dir.join("data.json");
+    let binary = dir.join("data.snap");
+    let back = dir.join("back.json");
+    std::fs::write(&json, serde_json::to_vec_pretty(&data()).unwrap()).unwrap();
+
+    let path = |p: &std::path::PathBuf| p.to_str().unwrap().to_string();
+    ConvertCommand::parse_from(["convert", &path(&json), &path(&binary), "--compress"])
+        .execute()
+        .await
+        .unwrap();
+    assert!(BinaryLoader::is_snapshot_file(&binary).unwrap());
+    ConvertCommand::parse_from(["convert", &path(&binary), &path(&back)])
+        .execute()
+        .await
+        .unwrap();
+    let round_tripped: ElectionData = serde_json::from_slice(&std::fs::read(&back).unwrap()).unwrap();
+    assert_eq!(round_tripped, data());
+
+    let error = ConvertCommand::parse_from(["convert", &path(&json), &path(&back), "--to", "json", "--compress"])
+        .execute()
+        .await
+        .unwrap_err();
+    assert!(matches!(error, ElectionError::ValidationError { field: Some(ref f), .. } if f == "compress"));
+}
@@ -0,0 +1,189 @@
+//! Integration test: chain profiles with network parameters
+
+mod common;
+
+use common::data_generator::{election_data, nominator};
+use common::mock_node::*;
+use frame_metadata::v14::{ExtrinsicMetadata, PalletConstantMetadata, PalletMetadata, RuntimeMetadataV14};
+use frame_metadata::RuntimeMetadataPrefixed;
+use offline_election::input::RpcLoader;
+use offline_election::models::election_data::ElectionMetadata;
+use offline_election::models::election_result::UnassignedReason;
+use offline_election::{
+    AlgorithmType, ChainProfile, ElectionConfiguration, ElectionData, ElectionEngine, ElectionError, Nominator,
+    ValidatorCandidate,
+};
+use parity_scale_codec::Encode;
+use scale_info::meta_type;
+use serde_json::json;
+
+const BLOCK_HASH: &str = "0x8888888888888888888888888888888888888888888888888888888888888888";
+
+fn constant(name: &'static str, value: u32) -> PalletConstantMetadata {
+    PalletConstantMetadata { name, ty: meta_type::<u32>(), value: value.encode(), docs: vec![] }
+}
+
+fn pallet(name: &'static str, index: u8, constants: Vec<PalletConstantMetadata>) -> PalletMetadata {
+    PalletMetadata { name, storage: None, calls: None, event: None, constants, error: None, index }
+}
+
+fn runtime_metadata() -> String {
+    let metadata = RuntimeMetadataV14::new(
+        vec![
+            pallet("Staking", 7, vec![constant("MaxNominations", 24)]),
+            pallet(
+                "ElectionProviderMultiPhase",
+                24,
+                vec![constant("MaxWinners", 2_000), constant("MaxElectingVoters", 12_500)],
+            ),
+        ],
+        ExtrinsicMetadata { ty: meta_type::<()>(), version: 4, signed_extensions: vec![] },
+        meta_type::<()>(),
+    );
+    format!("0x{}", hex::encode(RuntimeMetadataPrefixed::from(metadata).encode()))
+}
+
+fn data(nominators: Vec<Nominator>) -> ElectionData {
+    election_data(&["a", "b", "c"], 100, nominators)
+}
+
+fn profile() -> ChainProfile {
+    ChainProfile::new("Test")
+}
+
+fn run(profile: ChainProfile, active_set_size: u32, data: &ElectionData) -> Result<offline_election::ElectionResult, ElectionError> {
+    let config = ElectionConfiguration::new()
+        .algorithm(AlgorithmType::SequentialPhragmen)
+        .active_set_size(active_set_size)
+        .chain_profile(profile)
+        .build()?;
+    ElectionEngine::new().execute(&config, data)
+}
+
+#[test]
+fn test_presets_and_files() {
+    assert_eq!(ChainProfile::for_chain("Polkadot Asset Hub"), Some(ChainProfile::polkadot()));
+    assert_eq!("kusama".parse::<ChainProfile>().unwrap().ss58_prefix, 2);
+    assert!(ChainProfile::for_chain("Development").is_none());
+    assert!(ChainProfile::for_chain("Polkadot Local Testnet").is_none());
+
+    let path = std::env::temp_dir().join(format!("chain-profile-{}.json", std::process::id()));
+    std::fs::write(
+        &path,
+        json!({ "name": "Local", "max_nominations": 4, "ss58_prefix": 42, "token_decimals": 12, "token_symbol": "UNIT" })
+            .to_string(),
+    )
+    .unwrap();
+    let loaded = ChainProfile::from_file(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+    assert_eq!(loaded.max_nominations, 4);
+    assert_eq!(loaded.max_winners, None);
+}
+
+#[test]
+fn test_active_set_size_is_bounded_by_max_winners() {
+    let config = ElectionConfiguration::new()
+        .active_set_size(3)
+        .chain_profile(ChainProfile { max_winners: Some(2), ..profile() })
+        .build();
+    assert!(matches!(config, Err(ElectionError::ValidationError { .. })));
+
+}
+
+#[test]
+fn test_preset_for_the_data_chain_is_not_applied_unless_requested() {
+    // Polkadot allows 16 nominations, so the 17th target is only ignored under its profile
+    let ids: Vec<String> = (0..17).map(|i| format!("c{}", i)).collect();
+    let targets: Vec<&str> = ids.iter().map(String::as_str).collect();
+    let data = ElectionData {
+        candidates: ids.iter().map(|id| ValidatorCandidate::new(id.clone(), 100)).collect(),
+        nominators: vec![nominator("n1", 17_000, &targets)],
+        metadata: Some(ElectionMetadata {
+            block_number: None,
+            chain: Some("Polkadot".to_string()),
+            era: None,
+            completeness: None,
+            candidate_stakes_unknown: false,
+        }),
+    };
+    let config = ElectionConfiguration::new().active_set_size(17).build().unwrap();
+    let result = ElectionEngine::new().execute(&config, &data).unwrap();
+    assert!(result.stake_distribution.iter().any(|a| a.validator_id == "c16"));
+
+    let config = ElectionConfiguration::new()
+        .active_set_size(17)
+        .chain_profile(ChainProfile::polkadot())
+        .build()
+        .unwrap();
+    let result = ElectionEngine::new().execute(&config, &data).unwrap();
+    assert!(result.stake_distribution.iter().all(|a| a.validator_id != "c16"));
+}
+
+#[test]
+fn test_nominations_and_backers_are_limited() {
+    let data = data(vec![
+        nominator("n1", 1_000, &["a", "b", "c"]),
+        nominator("n2", 2_000, &["a"]),
+        nominator("n3", 3_000, &["a"]),
+    ]);
+
+    // Only the first target of each nominator counts
+    let result = run(ChainProfile { max_nominations: 1, ..profile() }, 1, &data).unwrap();
+    assert_eq!(result.selected_validators[0].account_id, "a");
+    assert!(result.stake_distribution.iter().all(|a| a.validator_id == "a"));
+
+    // Only the two largest backers of "a" are kept
+    let limited = ChainProfile { max_nominations: 1, max_backers_per_winner: Some(2), ..profile() };
+    let result = run(limited, 1, &data).unwrap();
+    let backers: Vec<_> = result.stake_distribution.iter().map(|a| a.nominator_id.as_str()).collect();
+    assert_eq!(backers.len(), 2);
+    assert!(!backers.contains(&"n1"));
+    assert_eq!(result.selected_validators[0].nominator_count, 2);
+    // The trimmed backer's stake is still counted, as unassigned
+    assert_eq!(result.total_stake, 6_000);
+    assert_eq!(result.accounting.assigned_stake, 5_000);
+    assert_eq!(result.accounting.unassigned_stake, 1_000);
+    assert_eq!(
+        result.accounting.unassigned[0].reason,
+        UnassignedReason::BackerTrimmed { validator_id: "a".to_string() }
+    );
+}
+
+#[test]
+fn test_lowest_staked_voters_are_left_out() {
+    let data = data(vec![
+        nominator("n1", 1_000, &["b"]),
+        nominator("n2", 2_000, &["a"]),
+        nominator("n3", 3_000, &["a"]),
+    ]);
+    let result = run(ChainProfile { max_electing_voters: Some(2), ..profile() }, 1, &data).unwrap();
+    assert_eq!(result.total_stake, 5_000);
+    assert!(result.stake_distribution.iter().all(|a| a.nominator_id != "n1"));
+}
+
+#[tokio::test]
+async fn test_profile_is_read_from_chain_constants() {
+    let mut storage = std::collections::BTreeMap::new();
+    storage.insert(
+        storage_prefix("Staking", "MinNominatorBond"),
+        format!("0x{}", hex::encode(7_000_000_000u128.encode())),
+    );
+    let mut chain = MockChain::with_block(100, BLOCK_HASH, storage);
+    chain.chain_name = "Rococo Local".to_string();
+    chain.metadata = Some(runtime_metadata());
+    chain.properties = json!({ "ss58Format": 2, "tokenDecimals": [12], "tokenSymbol": ["ROC"] });
+    let node = MockNode::start(chain).await;
+
+    let profile = RpcLoader::new(&node.url).unwrap().load_chain_profile().await.unwrap();
+
+    assert_eq!(profile.name, "Rococo Local");
+    assert_eq!(profile.max_nominations, 24);
+    assert_eq!(profile.max_winners, Some(2_000));
+    assert_eq!(profile.max_electing_voters, Some(12_500));
+    assert_eq!(profile.max_backers_per_winner, None);
+    assert_eq!(profile.min_nominator_bond, 7_000_000_000);
+    assert_eq!(profile.min_validator_bond, 0);
+    assert_eq!(profile.ss58_prefix, 2);
+    assert_eq!(profile.token_decimals, 12);
+    assert_eq!(profile.token_symbol, "ROC");
+}
@@ -0,0 +1,160 @@
+//! Integration test: CSV import and export
+
+mod common;
+
+use common::data_generator::generate_large_stake_election_data;
+use clap::Parser;
+use offline_election::cli::commands::{STAKE_DISTRIBUTION_CSV_FILE, VALIDATORS_CSV_FILE};
+use offline_election::cli::{ConvertCommand, RunCommand};
+use offline_election::input::{load_data_file, CsvLoader, CsvWriter};
+use offline_election::models::validator::CandidateMetadata;
+use offline_election::{ElectionConfiguration, ElectionData, ElectionEngine, ElectionError, ValidatorCandidate};
+
+fn data() -> ElectionData {
+    let mut data = generate_large_stake_election_data(8, 20, 3);
+    data.candidates[2].metadata = Some(CandidateMetadata {
+        commission_rate: Some(7),
+        on_chain_status: Some("active".to_string()),
+        blocked: true,
+    });
+    data
+}
+
+fn temp_dir(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("csv-{}-{}", name, std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn test_three_file_layout_round_trips() {
+    let dir = temp_dir("round-trip");
+    CsvWriter::new().write_to_dir(&data(), &dir).unwrap();
+
+    let loaded = CsvLoader::new().load_from_dir(dir.clone()).unwrap();
+    assert_eq!(loaded, data());
+    assert_eq!(load_data_file(dir).unwrap(), data());
+}
+
+#[test]
+fn test_plain_candidates_have_no_metadata_columns() {
+    let data = ElectionData {
+        candidates: vec![ValidatorCandidate::new("v0".to_string(), 5)],
+        nominators: vec![],
+        metadata: None,
+    };
+    let mut bytes = Vec::new();
+    CsvWriter::new().write_candidates(&data, &mut bytes).unwrap();
+    assert_eq!(String::from_utf8(bytes).unwrap(), "account_id,stake\nv0,5\n");
+}
+
+#[test]
+fn test_loads_long_format() {
+    let csv = "\
+type,account_id,stake,validator_id,commission_rate
+candidate,v1,1000,,
+candidate,v2,2000,,10
+nominator,n1,500,,
+edge,n1,,v2,
+edge,n1,,v1,
+";
+    let data = CsvLoader::new().load_from_reader(csv.as_bytes()).unwrap();
+    assert_eq!(data.candidates.len(), 2);
+    assert!(data.candidates[0].metadata.is_none());
+    assert_eq!(data.candidates[1].metadata.as_ref().unwrap().commission_rate, Some(10));
+    assert_eq!(data.nominators[0].stake, 500);
+    assert_eq!(data.nominators[0].targets, vec!["v2".to_string(), "v1".to_string()]);
+
+    let path = temp_dir("long").join("election.csv");
+    std::fs::write(&path, csv).unwrap();
+    assert_eq!(load_data_file(path).unwrap(), data);
+}
+
+#[test]
+fn test_errors_name_the_line() {
+    let loader = CsvLoader::new();
+
+    let error = loader
+        .load_from_reader("type,account_id,stake,validator_id\nnominator,n1,abc,\n".as_bytes())
+        .unwrap_err();
+    assert!(matches!(error, ElectionError::InvalidData { ref message } if message.contains("line 2")));
+
+    let error = loader
+        .load_from_reader("type,account_id,stake,validator_id\nvoter,n1,1,\n".as_bytes())
+        .unwrap_err();
+    assert!(error.to_string().contains("Unknown record type 'voter' on line 2"));
+
+    let error = loader
+        .load_from_readers(
+            "account_id,stake\nv1,1\n".as_bytes(),
+            "account_id,stake\nn1,1\n".as_bytes(),
+            "nominator_id,validator_id\nn1,v1\nn2,v1\n".as_bytes(),
+        )
+        .unwrap_err();
+    assert!(matches!(error, ElectionError::ValidationError { ref message, .. } if message.contains("line 3")));
+}
+
+#[tokio::test]
+async fn test_run_command_writes_result_csv() {
+    let dir = temp_dir("run");
+    let input = dir.join("input");
+    let output = dir.join("output");
+    CsvWriter::new().write_to_dir(&data(), &input).unwrap();
+
+    RunCommand::parse_from([
+        "run",
+        "--algorithm",
+        "sequential-phragmen",
+        "--active-set-size",
+        "3",
+        "--input-file",
+        input.to_str().unwrap(),
+        "--input-format",
+        "csv",
+        "--format",
+        "csv",
+        "--output-file",
+        output.to_str().unwrap(),
+    ])
+    .execute()
+    .await
+    .unwrap();
+
+    let expected = ElectionEngine::new()
+        .execute(&ElectionConfiguration::new().active_set_size(3), &data())
+        .unwrap();
+    let validators = std::fs::read_to_string(output.join(VALIDATORS_CSV_FILE)).unwrap();
+    let mut lines = validators.lines();
+    assert_eq!(lines.next(), Some("rank,account_id,total_backing_stake,nominator_count"));
+    let first = &expected.selected_validators[0];
+    assert_eq!(
+        lines.next().unwrap(),
+        format!(
+            "{},{},{},{}",
+            first.rank.map(|rank| rank.to_string()).unwrap_or_default(),
+            first.account_id,
+            first.total_backing_stake,
+            first.nominator_count
+        )
+    );
+    assert_eq!(validators.lines().count(), expected.selected_validators.len() + 1);
+
+    let distribution = std::fs::read_to_string(output.join(STAKE_DISTRIBUTION_CSV_FILE)).unwrap();
+    assert!(distribution.starts_with("nominator_id,validator_id,amount,proportion,perbill\n"));
+    assert_eq!(distribution.lines().count(), expected.stake_distribution.len() + 1);
+}
+
+#[tokio::test]
+async fn test_convert_command_writes_csv() {
+    let dir = temp_dir("convert");
+    let json = dir.join("data.json");
+    let csv = dir.join("csv");
+    std::fs::write(&json, serde_json::to_vec(&data()).unwrap()).unwrap();
+
+    let path = |p: &std::path::Path| p.to_str().unwrap().to_string();
+    ConvertCommand::parse_from(["convert", &path(&json), &path(&csv), "--to", "csv"])
+        .execute()
+        .await
+        .unwrap();
+    assert_eq!(CsvLoader::new().load_from_dir(csv).unwrap(), data());
+}
@@ -26,6 +26,12 @@ fn test_zero_candidates_should_fail() {
         algorithm: AlgorithmType::SequentialPhragmen,
         overrides: None,
         block_number: None,
+        strict_snapshot: false,
+        strict: false,
+        min_nominator_bond: None,
+        min_validator_bond: None,
+        chain_profile: None,
+        address_format: None,
     };
     
     let result = engine.execute(&config, &election_data);
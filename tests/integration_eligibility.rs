@@ -0,0 +1,137 @@
+//! Integration test: eligibility filtering by minimum bonds and blocked validators
+
+mod common;
+
+use common::data_generator::nominator;
+use offline_election::diagnostics::models::{ExcludedRole, Exclusion, ExclusionReason};
+use offline_election::models::election_data::ElectionMetadata;
+use offline_election::models::election_result::{UnassignedReason, UnassignedStake};
+use offline_election::models::validator::CandidateMetadata;
+use offline_election::models::Warning;
+use offline_election::{
+    AlgorithmType, ElectionConfiguration, ElectionData, ElectionEngine, ElectionOverrides, ValidatorCandidate,
+};
+
+fn data() -> ElectionData {
+    let blocked = CandidateMetadata { blocked: true, ..Default::default() };
+    ElectionData {
+        candidates: vec![
+            ValidatorCandidate::new("a".to_string(), 1_000),
+            ValidatorCandidate::with_metadata("b".to_string(), 1_000, blocked),
+            ValidatorCandidate::new("c".to_string(), 10),
+        ],
+        nominators: vec![
+            nominator("n1", 500, &["a", "b"]),
+            nominator("n2", 50, &["a"]),
+            nominator("n3", 800, &["c"]),
+        ],
+        metadata: None,
+    }
+}
+
+#[test]
+fn test_ineligible_voters_targets_and_nominations_are_listed() {
+    let mut overrides = ElectionOverrides::new();
+    // n3 newly nominates blocked "b"; its only other target "c" is below the validator bond
+    overrides.add_voting_edge("n3".to_string(), "b".to_string()).unwrap();
+    let config = ElectionConfiguration::new()
+        .algorithm(AlgorithmType::SequentialPhragmen)
+        .active_set_size(2)
+        .min_nominator_bond(100)
+        .min_validator_bond(100)
+        .overrides(overrides)
+        .build()
+        .unwrap();
+
+    let result = ElectionEngine::new().execute_with_diagnostics(&config, &data(), true).unwrap();
+
+    let exclusions = &result.diagnostics.as_ref().unwrap().exclusions;
+    let excluded = |account: &str, role: ExcludedRole, reason: ExclusionReason| Exclusion {
+        account_id: account.to_string(),
+        role,
+        reason,
+    };
+    assert!(exclusions.contains(&excluded(
+        "c",
+        ExcludedRole::Target,
+        ExclusionReason::BelowMinValidatorBond { stake: 10, min_bond: 100 }
+    )));
+    assert!(exclusions.contains(&excluded(
+        "n2",
+        ExcludedRole::Voter,
+        ExclusionReason::BelowMinNominatorBond { stake: 50, min_bond: 100 }
+    )));
+    assert!(exclusions.contains(&excluded(
+        "n3",
+        ExcludedRole::Voter,
+        ExclusionReason::BlockedTarget { validator_id: "b".to_string() }
+    )));
+    assert!(exclusions.contains(&excluded("n3", ExcludedRole::Voter, ExclusionReason::NoEligibleTargets)));
+    assert_eq!(exclusions.len(), 4);
+
+    // n1's existing nomination of blocked "b" still counts
+    let mut selected: Vec<_> = result.selected_validators.iter().map(|v| v.account_id.as_str()).collect();
+    selected.sort();
+    assert_eq!(selected, vec!["a", "b"]);
+    assert!(result.stake_distribution.iter().all(|a| a.nominator_id == "n1"));
+}
+
+#[test]
+fn test_ineligible_voters_stake_is_unassigned() {
+    let config = ElectionConfiguration::new()
+        .algorithm(AlgorithmType::SequentialPhragmen)
+        .active_set_size(1)
+        .min_nominator_bond(100)
+        .min_validator_bond(100)
+        .build()
+        .unwrap();
+
+    let result = ElectionEngine::new().execute(&config, &data()).unwrap();
+
+    // n2 is below the nominator bond and n3's only target "c" is below the validator bond
+    assert_eq!(result.total_stake, 1_350);
+    let accounting = result.accounting();
+    let unassigned = |nominator_id: &str, amount: u128, reason: UnassignedReason| UnassignedStake {
+        nominator_id: nominator_id.to_string(),
+        amount,
+        reason,
+    };
+    assert!(accounting.unassigned.contains(&unassigned("n2", 50, UnassignedReason::BelowMinNominatorBond)));
+    assert!(accounting.unassigned.contains(&unassigned("n3", 800, UnassignedReason::NoEligibleTargets)));
+    assert_eq!(accounting.unassigned_stake, 850);
+    assert_eq!(
+        accounting.assigned_stake + accounting.unassigned_stake + accounting.rounding_residue,
+        result.total_stake
+    );
+}
+
+#[test]
+fn test_no_thresholds_keep_everyone() {
+    let config = ElectionConfiguration::new().active_set_size(3).build().unwrap();
+    let result = ElectionEngine::new().execute_with_diagnostics(&config, &data(), true).unwrap();
+    assert!(result.diagnostics.unwrap().exclusions.is_empty());
+    assert_eq!(result.selected_validators.len(), 3);
+}
+
+#[test]
+fn test_validator_bond_is_not_checked_against_unloaded_stakes() {
+    let mut data = data();
+    for candidate in &mut data.candidates {
+        candidate.stake = 0;
+    }
+    data.metadata = Some(ElectionMetadata {
+        block_number: Some(1),
+        chain: None,
+        era: None,
+        completeness: None,
+        candidate_stakes_unknown: true,
+    });
+    let config = ElectionConfiguration::new().active_set_size(3).min_validator_bond(100).build().unwrap();
+
+    let result = ElectionEngine::new().execute(&config, &data).unwrap();
+    assert_eq!(result.selected_validators.len(), 3);
+    assert!(result.warnings.contains(&Warning::ValidatorBondUnchecked { min_bond: 100 }));
+
+    let strict = ElectionConfiguration::new().active_set_size(3).min_validator_bond(100).strict(true).build().unwrap();
+    assert!(ElectionEngine::new().execute(&strict, &data).is_err());
+}
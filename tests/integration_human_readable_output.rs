@@ -0,0 +1,65 @@
+//! Integration test: token-denominated human-readable output
+
+use offline_election::cli::{format_validator_table, ValidatorSort};
+use offline_election::models::election_result::SelectedValidator;
+use offline_election::{AlgorithmType, ChainProfile, ElectionResult};
+
+fn validator(account_id: &str, backing: u128, nominators: u32, rank: u32) -> SelectedValidator {
+    SelectedValidator {
+        account_id: account_id.to_string(),
+        total_backing_stake: backing,
+        nominator_count: nominators,
+        rank: Some(rank),
+    }
+}
+
+fn result() -> ElectionResult {
+    let validators = (1..=12)
+        .map(|rank| validator(&format!("v{}", rank), (13 - rank as u128) * 10u128.pow(10), rank, rank))
+        .collect();
+    ElectionResult::new(validators, vec![], 78 * 10u128.pow(10), AlgorithmType::SequentialPhragmen)
+}
+
+#[test]
+fn test_balances_use_chain_decimals_and_symbol() {
+    let polkadot = ChainProfile::polkadot();
+    assert_eq!(polkadot.format_balance(12_345_678_901_234_567_890), "1,234,567,890.123456789 DOT");
+    assert_eq!(polkadot.format_balance(10u128.pow(10)), "1 DOT");
+    assert_eq!(polkadot.format_balance(1), "0.0000000001 DOT");
+    assert_eq!(ChainProfile::kusama().format_balance(1_500_000_000_000), "1.5 KSM");
+
+    let mut custom = ChainProfile::new("custom");
+    custom.token_symbol = "TOK".to_string();
+    custom.token_decimals = 0;
+    assert_eq!(custom.format_balance(1_000), "1,000 TOK");
+}
+
+#[test]
+fn test_table_lists_every_validator_with_share() {
+    let table = format_validator_table(&result(), Some(&ChainProfile::polkadot()), ValidatorSort::Rank);
+    let lines: Vec<&str> = table.lines().collect();
+
+    assert_eq!(lines.len(), 13, "header plus all 12 validators:\n{}", table);
+    assert!(lines[0].starts_with("Rank"));
+    assert!(lines[1].contains("v1 ") && lines[1].contains("12 DOT") && lines[1].contains("15.38%"));
+    assert!(lines[12].contains("v12") && lines[12].contains("1 DOT") && lines[12].contains("1.28%"));
+}
+
+#[test]
+fn test_table_sorts_by_backing_or_nominators() {
+    let data = result();
+    let first_account = |sort| {
+        let table = format_validator_table(&data, None, sort);
+        table.lines().nth(1).unwrap().split_whitespace().nth(1).unwrap().to_string()
+    };
+
+    assert_eq!(first_account(ValidatorSort::Rank), "v1");
+    assert_eq!(first_account(ValidatorSort::Backing), "v1");
+    assert_eq!(first_account(ValidatorSort::Nominators), "v12");
+    assert_eq!("backing".parse::<ValidatorSort>(), Ok(ValidatorSort::Backing));
+    assert!("size".parse::<ValidatorSort>().is_err());
+
+    // Without chain units amounts stay in planck
+    let table = format_validator_table(&data, None, ValidatorSort::Rank);
+    assert!(table.lines().nth(1).unwrap().contains("120000000000"));
+}
@@ -0,0 +1,94 @@
+//! Integration test: streaming JSON input and output
+
+mod common;
+
+use common::data_generator::generate_large_stake_election_data;
+use clap::Parser;
+use offline_election::cli::RunCommand;
+use offline_election::input::JsonLoader;
+use offline_election::{ElectionConfiguration, ElectionData, ElectionEngine, ElectionError, ElectionResult};
+
+fn data() -> ElectionData {
+    // Stakes above u64::MAX must survive the round trip
+    generate_large_stake_election_data(20, 200, 3)
+}
+
+fn temp_dir(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("json-streaming-{}-{}", name, std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn test_loads_from_reader_and_file() {
+    let data = data();
+    let json = serde_json::to_vec_pretty(&data).unwrap();
+    assert_eq!(JsonLoader::new().load_from_reader(json.as_slice()).unwrap(), data);
+
+    let path = temp_dir("load").join("data.json");
+    std::fs::write(&path, &json).unwrap();
+    assert_eq!(JsonLoader::new().load_from_file(path).unwrap(), data);
+}
+
+#[test]
+fn test_load_errors_keep_their_kind() {
+    let dir = temp_dir("errors");
+    let missing = dir.join("missing.json");
+    match JsonLoader::new().load_from_file(missing.clone()) {
+        Err(ElectionError::FileError { message, path }) => {
+            assert!(message.starts_with("Failed to read file"), "{}", message);
+            assert_eq!(path, missing);
+        }
+        other => panic!("expected a file error, got {:?}", other),
+    }
+
+    let truncated = dir.join("truncated.json");
+    std::fs::write(&truncated, r#"{"candidates": [{"account_id": "v1", "stake": 1}"#).unwrap();
+    match JsonLoader::new().load_from_file(truncated) {
+        Err(ElectionError::FileError { message, .. }) => assert!(message.starts_with("Failed to parse JSON"), "{}", message),
+        other => panic!("expected a parse error, got {:?}", other),
+    }
+
+    match JsonLoader::new().load_from_reader(&b"[]"[..]) {
+        Err(ElectionError::InvalidData { message }) => assert!(message.starts_with("Failed to parse JSON"), "{}", message),
+        other => panic!("expected a parse error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_written_json_matches_to_json() {
+    let config = ElectionConfiguration::new().active_set_size(5);
+    let result = ElectionEngine::new().execute(&config, &data()).unwrap();
+
+    let mut written = Vec::new();
+    result.write_json(&mut written).unwrap();
+    assert_eq!(String::from_utf8(written).unwrap(), result.to_json().unwrap());
+}
+
+#[tokio::test]
+async fn test_run_command_streams_file_to_file() {
+    let dir = temp_dir("cli");
+    let input = dir.join("input.json");
+    let output = dir.join("output.json");
+    std::fs::write(&input, serde_json::to_vec(&data()).unwrap()).unwrap();
+
+    let command = RunCommand::parse_from([
+        "run",
+        "--algorithm",
+        "sequential-phragmen",
+        "--active-set-size",
+        "5",
+        "--input-file",
+        input.to_str().unwrap(),
+        "--output-file",
+        output.to_str().unwrap(),
+    ]);
+    command.execute().await.unwrap();
+
+    let result: ElectionResult = serde_json::from_reader(std::fs::File::open(&output).unwrap()).unwrap();
+    let expected = ElectionEngine::new()
+        .execute(&ElectionConfiguration::new().active_set_size(5), &data())
+        .unwrap();
+    assert_eq!(result.selected_validators, expected.selected_validators);
+    assert_eq!(result.stake_distribution.len(), expected.stake_distribution.len());
+}
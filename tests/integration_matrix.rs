@@ -0,0 +1,107 @@
+//! Integration test: batch runs across algorithms and active set sizes
+
+mod common;
+
+use common::data_generator::{election_data, nominator};
+use offline_election::cli::{format_matrix_csv, format_matrix_table};
+use offline_election::models::{ElectionOverrides, MatrixSpec};
+use offline_election::{AlgorithmType, ElectionConfiguration, ElectionData, ElectionEngine};
+
+fn data() -> ElectionData {
+    let nominators = vec![
+        nominator("n1", 1_000, &["a", "b"]),
+        nominator("n2", 600, &["b", "c"]),
+        nominator("n3", 300, &["c", "d"]),
+        nominator("n4", 200, &["d"]),
+    ];
+    election_data(&["a", "b", "c", "d"], 100, nominators)
+}
+
+const ALGORITHMS: [AlgorithmType; 3] = [
+    AlgorithmType::SequentialPhragmen,
+    AlgorithmType::ParallelPhragmen,
+    AlgorithmType::MultiPhase,
+];
+
+#[test]
+fn test_matrix_runs_every_combination_in_order() {
+    let spec = MatrixSpec::new(ALGORITHMS.to_vec(), vec![3, 1, 2]).threads(4);
+    let config = ElectionConfiguration::new().active_set_size(1);
+
+    let report = ElectionEngine::new().execute_matrix(&config, &spec, &data()).unwrap();
+
+    assert_eq!(report.reference, AlgorithmType::SequentialPhragmen);
+    assert_eq!(report.runs.len(), 9);
+    let order: Vec<(u32, AlgorithmType)> = report.runs.iter().map(|r| (r.active_set_size, r.algorithm)).collect();
+    let expected: Vec<(u32, AlgorithmType)> =
+        [3, 1, 2].iter().flat_map(|&size| ALGORITHMS.iter().map(move |&a| (size, a))).collect();
+    assert_eq!(order, expected);
+
+    for run in &report.runs {
+        let result = ElectionEngine::new()
+            .execute(
+                &ElectionConfiguration::new()
+                    .algorithm(run.algorithm)
+                    .active_set_size(run.active_set_size),
+                &data(),
+            )
+            .unwrap();
+        assert_eq!(run.selected, run.active_set_size);
+        assert_eq!(run.score, result.score());
+        assert!(run.overlap <= run.selected);
+        assert!(run.runtime_ms >= 0.0);
+    }
+    let reference = report.run(AlgorithmType::SequentialPhragmen, 2).unwrap();
+    assert_eq!(reference.overlap, 2);
+}
+
+#[test]
+fn test_matrix_sizes_replace_an_active_set_size_override() {
+    let mut overrides = ElectionOverrides::new();
+    overrides.set_active_set_size(1).unwrap();
+    let config = ElectionConfiguration::new().active_set_size(1).overrides(overrides);
+    let spec = MatrixSpec::new(vec![AlgorithmType::SequentialPhragmen], vec![2, 4]).threads(1);
+
+    let report = ElectionEngine::new().execute_matrix(&config, &spec, &data()).unwrap();
+
+    let selected: Vec<u32> = report.runs.iter().map(|r| r.selected).collect();
+    assert_eq!(selected, vec![2, 4]);
+}
+
+#[test]
+fn test_matrix_rejects_empty_specs() {
+    let config = ElectionConfiguration::new().active_set_size(1);
+    let engine = ElectionEngine::new();
+
+    assert!(engine.execute_matrix(&config, &MatrixSpec::new(vec![], vec![1]), &data()).is_err());
+    assert!(engine
+        .execute_matrix(&config, &MatrixSpec::new(ALGORITHMS.to_vec(), vec![]), &data())
+        .is_err());
+    assert!(engine
+        .execute_matrix(&config, &MatrixSpec::new(ALGORITHMS.to_vec(), vec![1]).threads(0), &data())
+        .is_err());
+}
+
+#[test]
+fn test_matrix_formats() {
+    let spec = MatrixSpec::new(ALGORITHMS.to_vec(), vec![2]);
+    let config = ElectionConfiguration::new().active_set_size(2);
+    let report = ElectionEngine::new().execute_matrix(&config, &spec, &data()).unwrap();
+
+    let csv = format_matrix_csv(&report);
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(
+        lines[0],
+        "active_set_size,algorithm,selected,min_stake,sum_stake,sum_stake_squared,overlap,runtime_ms,warnings"
+    );
+    assert_eq!(lines.len(), 4);
+    assert!(lines[1].starts_with("2,sequential-phragmen,2,"));
+
+    let table = format_matrix_table(&report);
+    assert!(table.contains("Min Stake"));
+    assert!(table.contains("parallel-phragmen"));
+
+    let json: serde_json::Value = serde_json::to_value(&report).unwrap();
+    assert_eq!(json["runs"][2]["algorithm"], "multi-phase");
+    assert!(json["runs"][0]["score"]["minimal_stake"].is_u64());
+}
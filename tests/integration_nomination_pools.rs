@@ -0,0 +1,81 @@
+//! Integration test: nomination pools as voters with member-level attribution
+
+mod common;
+
+use common::mock_node::*;
+use offline_election::input::RpcLoader;
+use offline_election::{AlgorithmType, ElectionConfiguration, ElectionEngine};
+use parity_scale_codec::Encode;
+
+const BLOCK_HASH: &str = "0x7777777777777777777777777777777777777777777777777777777777777777";
+
+/// `modl` ++ `py/nopls` ++ (AccountType::Bonded, pool id), zero-padded
+fn bonded_account(pool_id: u32) -> [u8; 32] {
+    let mut account = [0u8; 32];
+    account[..12].copy_from_slice(b"modlpy/nopls");
+    account[13..17].copy_from_slice(&pool_id.to_le_bytes());
+    account
+}
+
+/// `PoolMember { pool_id, points, last_recorded_reward_counter, unbonding_eras }`
+fn pool_member(pool_id: u32, points: u128) -> String {
+    format!("0x{}", hex::encode((pool_id, points, 0u128, Vec::<(u32, u128)>::new()).encode()))
+}
+
+fn snapshot() -> MockChain {
+    let validators = [account(0xaa)];
+    let nominators = vec![
+        (bonded_account(1), 1_001, vec![account(0xaa)]),
+        (account(0xb1), 500, vec![account(0xaa)]),
+    ];
+    let mut storage = staking_storage(&validators, &nominators);
+    storage.insert(
+        twox64_map_key("NominationPools", "BondedPools", &1u32.to_le_bytes()),
+        "0x00".to_string(),
+    );
+    // Pool 2 exists but its bonded account is not nominating
+    storage.insert(
+        twox64_map_key("NominationPools", "BondedPools", &2u32.to_le_bytes()),
+        "0x00".to_string(),
+    );
+    storage.insert(twox64_map_key("NominationPools", "PoolMembers", &account(0xc1)), pool_member(1, 300));
+    storage.insert(twox64_map_key("NominationPools", "PoolMembers", &account(0xc2)), pool_member(1, 700));
+    storage.insert(twox64_map_key("NominationPools", "PoolMembers", &account(0xc3)), pool_member(2, 50));
+    MockChain::with_block(100, BLOCK_HASH, storage)
+}
+
+#[tokio::test]
+async fn test_pool_backing_is_attributed_to_members() {
+    let node = MockNode::start(snapshot()).await;
+    let data = RpcLoader::new(&node.url).unwrap().load_at_block(100).await.unwrap();
+
+    let pool_voter = data
+        .nominators
+        .iter()
+        .find(|n| n.account_id == account_hex(&bonded_account(1)))
+        .unwrap();
+    let pool = pool_voter.pool().expect("bonded account should carry its pool");
+    assert_eq!(pool.pool_id, 1);
+    assert_eq!(pool.total_points(), 1_000);
+    assert_eq!(data.nominators.iter().filter(|n| n.pool().is_some()).count(), 1);
+
+    let config = ElectionConfiguration::new()
+        .algorithm(AlgorithmType::SequentialPhragmen)
+        .active_set_size(1)
+        .build()
+        .unwrap();
+    let result = ElectionEngine::new().execute(&config, &data).unwrap().with_pool_backing(&data);
+
+    let backing = result.pool_backing().unwrap();
+    assert_eq!(backing.len(), 2);
+    let share = |member: u8| {
+        backing
+            .iter()
+            .find(|b| b.member_id == account_hex(&account(member)))
+            .unwrap()
+    };
+    assert!(backing.iter().all(|b| b.pool_id == 1 && b.validator_id == account_hex(&account(0xaa))));
+    // 1_001 split 3:7, with the rounding dust going to the larger member
+    assert_eq!(share(0xc1).amount, 300);
+    assert_eq!(share(0xc2).amount, 701);
+}
@@ -0,0 +1,111 @@
+//! Integration test: adding and removing accounts, weighted edges and unmatched overrides
+
+mod common;
+
+use common::data_generator::{election_data, nominator};
+use offline_election::models::Warning;
+use offline_election::{
+    AlgorithmType, ElectionConfiguration, ElectionData, ElectionEngine, ElectionError, ElectionOverrides,
+    ElectionResult, ValidatorCandidate,
+};
+
+fn data() -> ElectionData {
+    let nominators = vec![
+        nominator("n1", 1_000, &["a", "b"]),
+        nominator("n2", 600, &["b", "c"]),
+        nominator("n3", 300, &["c"]),
+    ];
+    election_data(&["a", "b", "c"], 100, nominators)
+}
+
+fn run(overrides: ElectionOverrides, active_set_size: u32) -> Result<ElectionResult, ElectionError> {
+    let config = ElectionConfiguration::new()
+        .algorithm(AlgorithmType::SequentialPhragmen)
+        .active_set_size(active_set_size)
+        .overrides(overrides)
+        .build()?;
+    ElectionEngine::new().execute(&config, &data())
+}
+
+fn selected(result: &ElectionResult) -> Vec<&str> {
+    let mut ids: Vec<&str> = result.selected_validators.iter().map(|v| v.account_id.as_str()).collect();
+    ids.sort();
+    ids
+}
+
+#[test]
+fn test_candidates_and_nominators_can_be_added_and_removed() {
+    let mut overrides = ElectionOverrides::new();
+    overrides.remove_candidate("b".to_string()).unwrap();
+    overrides.remove_nominator("n3".to_string()).unwrap();
+    overrides.add_candidate(ValidatorCandidate::new("d".to_string(), 100)).unwrap();
+    overrides.add_nominator(nominator("n4", 5_000, &["d"])).unwrap();
+
+    let result = run(overrides, 3).unwrap();
+
+    assert_eq!(selected(&result), vec!["a", "c", "d"]);
+    assert!(result.stake_distribution.iter().all(|a| a.validator_id != "b" && a.nominator_id != "n3"));
+    assert_eq!(result.total_stake, 1_000 + 600 + 5_000);
+    assert!(result.warnings().is_empty());
+}
+
+#[test]
+fn test_active_set_size_override_is_applied() {
+    let mut overrides = ElectionOverrides::new();
+    overrides.set_active_set_size(1).unwrap();
+
+    let result = run(overrides, 3).unwrap();
+
+    assert_eq!(result.selected_validators.len(), 1);
+    assert!(ElectionOverrides::new().set_active_set_size(0).is_err());
+}
+
+#[test]
+fn test_edge_weight_fixes_the_stake_on_that_edge() {
+    // n1 commits exactly 250 to "a"; the other 750 can only go to "b"
+    let mut overrides = ElectionOverrides::new();
+    overrides
+        .modify_voting_edge("n1".to_string(), "a".to_string(), Some(250))
+        .unwrap();
+
+    let result = run(overrides, 3).unwrap();
+
+    let n1: Vec<_> = result.allocations_for_nominator("n1");
+    let on = |validator: &str| n1.iter().find(|a| a.validator_id == validator).map(|a| a.amount);
+    assert_eq!(on("a"), Some(250));
+    assert_eq!(on("b"), Some(750));
+    assert_eq!(n1.iter().find(|a| a.validator_id == "a").unwrap().perbill, 250_000_000);
+    assert!(result.stake_distribution.iter().all(|a| !a.nominator_id.contains('#')));
+
+    // Weights can't exceed the nominator's stake
+    let mut overrides = ElectionOverrides::new();
+    overrides
+        .add_weighted_voting_edge("n3".to_string(), "a".to_string(), 400)
+        .unwrap();
+    assert!(matches!(run(overrides, 3), Err(ElectionError::ValidationError { .. })));
+}
+
+#[test]
+fn test_unmatched_overrides_are_reported() {
+    let mut overrides = ElectionOverrides::new();
+    overrides.set_candidate_stake("nobody".to_string(), 1).unwrap();
+    overrides.remove_voting_edge("n3".to_string(), "a".to_string()).unwrap();
+
+    let result = run(overrides.clone(), 3).unwrap();
+    assert!(result.warnings().contains(&Warning::UnmatchedOverride {
+        target: "candidate-stake".to_string(),
+        account_id: "nobody".to_string(),
+    }));
+    assert!(result.warnings().contains(&Warning::UnmatchedOverride {
+        target: "voting-edge".to_string(),
+        account_id: "n3 -> a".to_string(),
+    }));
+
+    let config = ElectionConfiguration::new()
+        .active_set_size(3)
+        .overrides(overrides)
+        .strict(true)
+        .build()
+        .unwrap();
+    assert!(ElectionEngine::new().execute(&config, &data()).is_err());
+}
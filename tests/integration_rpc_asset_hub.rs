@@ -0,0 +1,108 @@
+//! Integration test: loading from Asset Hub's `pallet-staking-async` layout
+
+mod common;
+
+use common::mock_node::*;
+use offline_election::input::{RpcLoader, StakingProfile};
+use parity_scale_codec::{Compact, Encode};
+use std::collections::BTreeMap;
+
+const BLOCK_HASH: &str = "0x6666666666666666666666666666666666666666666666666666666666666666";
+
+fn scale_hex(value: impl Encode) -> String {
+    format!("0x{}", hex::encode(value.encode()))
+}
+
+/// Key of a `MultiBlockElection` snapshot page, keyed by `(round, page)`
+fn page_key(item: &str, round: u32, page: u32) -> String {
+    format!(
+        "{}{}",
+        twox64_map_key("MultiBlockElection", item, &round.to_le_bytes()),
+        twox64_concat(&page.to_le_bytes())
+    )
+}
+
+fn asset_hub_chain(storage: BTreeMap<String, String>) -> MockChain {
+    let mut chain = MockChain::with_block(100, BLOCK_HASH, storage);
+    chain.chain_name = "Polkadot Asset Hub".to_string();
+    chain
+}
+
+#[tokio::test]
+async fn test_reads_paged_election_snapshot() {
+    let (aa, ab, ac) = (account(0xaa), account(0xab), account(0xac));
+    let mut storage = BTreeMap::new();
+    storage.insert(storage_prefix("MultiBlockElection", "Round"), encode_u32(5));
+    storage.insert(page_key("PagedTargetSnapshot", 5, 0), scale_hex(vec![aa, ab]));
+    storage.insert(page_key("PagedTargetSnapshot", 5, 1), scale_hex(vec![ac]));
+    storage.insert(
+        page_key("PagedVoterSnapshot", 5, 0),
+        scale_hex(vec![(account(0xb1), 100u64, vec![aa]), (account(0xb2), 200u64, vec![ab, ac])]),
+    );
+    storage.insert(page_key("PagedVoterSnapshot", 5, 1), scale_hex(vec![(aa, 500u64, vec![aa])]));
+    // A stale page from an earlier round must be ignored
+    storage.insert(page_key("PagedVoterSnapshot", 4, 0), scale_hex(vec![(account(0xb9), 1u64, vec![aa])]));
+    // Counts nominators only, not validators voting for themselves
+    storage.insert(storage_prefix("Staking", "CounterForNominators"), encode_u32(2));
+
+    let node = MockNode::start(asset_hub_chain(storage)).await;
+    let data = RpcLoader::new(&node.url).unwrap().load_at_block(100).await.unwrap();
+
+    let mut candidates: Vec<_> = data.candidates.iter().map(|c| c.account_id.clone()).collect();
+    candidates.sort();
+    assert_eq!(candidates, vec![account_hex(&aa), account_hex(&ab), account_hex(&ac)]);
+
+    assert_eq!(data.nominators.len(), 3);
+    let voter = |who: u8| data.nominators.iter().find(|n| n.account_id == account_hex(&account(who)));
+    assert!(voter(0xb9).is_none());
+    let b2 = voter(0xb2).unwrap();
+    assert_eq!(b2.stake, 200);
+    assert_eq!(b2.targets, vec![account_hex(&ab), account_hex(&ac)]);
+    // Validators vote for themselves in the snapshot
+    assert_eq!(voter(0xaa).unwrap().stake, 500);
+
+    let completeness = data.metadata.unwrap().completeness.unwrap();
+    assert_eq!(completeness.expected_nominators, None);
+    assert!(completeness.is_complete());
+}
+
+#[tokio::test]
+async fn test_reads_live_staking_maps_outside_election() {
+    let (aa, ab) = (account(0xaa), account(0xab));
+    let (stash, controller) = (account(0xb1), account(0xc1));
+    let mut storage = BTreeMap::new();
+    storage.insert(twox64_map_key("Staking", "Validators", &aa), scale_hex((0u32, false)));
+    storage.insert(twox64_map_key("Staking", "Validators", &ab), scale_hex((0u32, false)));
+    storage.insert(
+        twox64_map_key("Staking", "Nominators", &stash),
+        scale_hex((vec![aa, ab], 7u32, false)),
+    );
+    // Ledgers are keyed by controller; the stash comes from the value
+    storage.insert(
+        map_key("Staking", "Ledger", 16, &controller),
+        scale_hex((stash, Compact(1_500u128), Compact(1_500u128), Vec::<u8>::new())),
+    );
+
+    // A non-Asset Hub name, so the profile is forced
+    let node = MockNode::start(MockChain::with_block(100, BLOCK_HASH, storage)).await;
+    let data = RpcLoader::new(&node.url)
+        .unwrap()
+        .with_profile(StakingProfile::AssetHub)
+        .load_at_block(100)
+        .await
+        .unwrap();
+
+    assert_eq!(data.candidates.len(), 2);
+    assert_eq!(data.nominators.len(), 1);
+    assert_eq!(data.nominators[0].account_id, account_hex(&stash));
+    assert_eq!(data.nominators[0].stake, 1_500);
+    assert_eq!(data.nominators[0].targets, vec![account_hex(&aa), account_hex(&ab)]);
+    assert!(data.metadata.unwrap().completeness.unwrap().undecodable_keys.is_empty());
+}
+
+#[test]
+fn test_profile_from_chain_name() {
+    assert_eq!(StakingProfile::from_chain_name("Polkadot Asset Hub"), Some(StakingProfile::AssetHub));
+    assert_eq!(StakingProfile::from_chain_name("Kusama Asset Hub"), Some(StakingProfile::AssetHub));
+    assert_eq!(StakingProfile::from_chain_name("Polkadot"), None);
+}
@@ -0,0 +1,85 @@
+//! Integration test: batched, concurrent storage fetching in `RpcLoader`
+
+mod common;
+
+use common::mock_node::*;
+use offline_election::input::cache::StorageCache;
+use offline_election::input::RpcLoader;
+
+const BLOCK_HASH: &str = "0x1111111111111111111111111111111111111111111111111111111111111111";
+
+fn snapshot(nominator_count: u32) -> MockChain {
+    let validators: Vec<[u8; 32]> = (0..3).map(|i| indexed_account(0xaa, i)).collect();
+    let nominators: Vec<_> = (0..nominator_count)
+        .map(|i| {
+            let target = validators[i as usize % validators.len()];
+            (indexed_account(0xbb, i), 1_000 + i as u128, vec![target])
+        })
+        .collect();
+    MockChain::with_block(100, BLOCK_HASH, staking_storage(&validators, &nominators))
+}
+
+#[tokio::test]
+async fn test_nominators_are_fetched_in_batches() {
+    let node = MockNode::start(snapshot(600)).await;
+    let loader = RpcLoader::new(&node.url)
+        .unwrap()
+        .with_batch_size(100)
+        .with_concurrency(4);
+
+    let data = loader.load_at_block(100).await.unwrap();
+
+    assert_eq!(data.candidates.len(), 3);
+    assert_eq!(data.nominators.len(), 600);
+    let nominator = data
+        .nominators
+        .iter()
+        .find(|n| n.account_id == account_hex(&indexed_account(0xbb, 42)))
+        .expect("nominator 42 should be loaded");
+    assert_eq!(nominator.stake, 1_042);
+    assert_eq!(nominator.targets, vec![account_hex(&indexed_account(0xaa, 0))]);
+
+    // 600 Nominators + 600 Ledger values in batches of 100
+    assert_eq!(node.chain.calls("state_queryStorageAt"), 12);
+    // Only Session::Validators and the two CounterFor items are fetched individually
+    assert_eq!(node.chain.calls("state_getStorage"), 3);
+}
+
+#[tokio::test]
+async fn test_falls_back_to_single_key_queries() {
+    let node = MockNode::start(snapshot(50).unsupported("state_queryStorageAt")).await;
+    let loader = RpcLoader::new(&node.url)
+        .unwrap()
+        .with_batch_size(10)
+        .with_concurrency(2);
+
+    let data = loader.load_at_block(100).await.unwrap();
+
+    assert_eq!(data.nominators.len(), 50);
+    // Batches already in flight may try once each before the fallback is remembered
+    assert!(node.chain.calls("state_queryStorageAt") <= 2);
+    assert_eq!(node.chain.calls("state_getStorage"), 103);
+}
+
+#[tokio::test]
+async fn test_keys_left_out_of_a_batch_are_queried_again() {
+    let node = MockNode::start(snapshot(50).partial_queries(6)).await;
+    let cache_dir = std::env::temp_dir().join(format!("offline-election-partial-{}", uuid::Uuid::new_v4()));
+    let loader = || {
+        RpcLoader::new(&node.url)
+            .unwrap()
+            .with_batch_size(10)
+            .with_concurrency(2)
+            .with_cache(StorageCache::new(&cache_dir).unwrap())
+    };
+
+    let data = loader().load_at_block(100).await.unwrap();
+    assert_eq!(data.nominators.len(), 50);
+    // 4 of every 10 keys are missing from each batch response
+    assert_eq!(node.chain.calls("state_getStorage"), 3 + 40);
+
+    // Only values the node returned were cached, so a cached reload is complete too
+    let cached = loader().load_at_block(100).await.unwrap();
+    assert_eq!(cached.nominators.len(), 50);
+    std::fs::remove_dir_all(&cache_dir).ok();
+}
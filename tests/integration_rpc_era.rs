@@ -0,0 +1,172 @@
+//! Integration test: locating and loading an era's election block
+
+mod common;
+
+use common::mock_node::*;
+use offline_election::input::RpcLoader;
+use parity_scale_codec::Encode;
+
+const BLOCKS_PER_SESSION: u64 = 10;
+const SESSIONS_PER_ERA: u32 = 2;
+const LATEST_BLOCK: u64 = 79;
+
+fn block_hash(number: u64) -> String {
+    format!("0x{:064x}", number + 1)
+}
+
+/// Signed phase length; the snapshot is taken this many blocks before each election
+const ELECTION_PHASES_LENGTH: u64 = 6;
+
+/// 80 blocks of 10-block sessions and 2-session eras, so era 3 is active at the tip
+fn chain() -> MockChain {
+    let validators = [account(0xaa), account(0xab)];
+    let nominators = vec![(account(0xb1), 1_000, vec![account(0xaa)])];
+    let staking = staking_storage(&validators, &nominators);
+
+    let mut chain = MockChain::with_block(0, &block_hash(0), staking.clone());
+    for number in 0..=LATEST_BLOCK {
+        let session = (number / BLOCKS_PER_SESSION) as u32;
+        let active_era = session / SESSIONS_PER_ERA;
+
+        let mut storage = staking.clone();
+        storage.insert(storage_prefix("Session", "CurrentIndex"), encode_u32(session));
+        storage.insert(storage_prefix("Staking", "ActiveEra"), format!("{}00", encode_u32(active_era)));
+        for era in 0..=active_era {
+            storage.insert(
+                twox64_map_key("Staking", "ErasStartSessionIndex", &era.to_le_bytes()),
+                encode_u32(era * SESSIONS_PER_ERA),
+            );
+        }
+
+        chain.block_hashes.insert(number, block_hash(number));
+        chain.storage.insert(block_hash(number), storage);
+    }
+    chain
+}
+
+/// [`chain`] with an `ElectionProviderMultiPhase` pallet electing at the start of every odd session
+fn chain_with_election_provider() -> MockChain {
+    let mut chain = chain();
+    let election_blocks: Vec<u64> = (1..8).step_by(2).map(|session| session * BLOCKS_PER_SESSION).collect();
+    for number in 0..=LATEST_BLOCK {
+        let round = 1 + election_blocks.iter().filter(|block| **block <= number).count() as u32;
+        let snapshot_taken = election_blocks
+            .iter()
+            .any(|block| number < *block && number + ELECTION_PHASES_LENGTH >= *block);
+        let storage = chain.storage.get_mut(&block_hash(number)).unwrap();
+        storage.insert(storage_prefix("ElectionProviderMultiPhase", "Round"), encode_u32(round));
+        storage.insert(
+            storage_prefix("ElectionProviderMultiPhase", "CurrentPhase"),
+            if snapshot_taken { "0x01" } else { "0x00" }.to_string(),
+        );
+    }
+    chain
+}
+
+/// Blocks per era on the Asset Hub mock
+const BLOCKS_PER_ERA: u64 = 20;
+
+/// Asset Hub where era `n` is planned at block `20n - 5`, its voter snapshot
+/// pages 1 and 0 are stored at blocks `20n - 3` and `20n - 2`, and it is
+/// elected and becomes active at block `20n`
+fn asset_hub_chain() -> MockChain {
+    let (aa, ab) = (account(0xaa), account(0xab));
+    let staking = staking_storage(&[aa, ab], &[(account(0xb1), 1_000, vec![aa])]);
+    let page_key = |item: &str, round: u32, page: u32| {
+        format!(
+            "{}{}",
+            twox64_map_key("MultiBlockElection", item, &round.to_le_bytes()),
+            twox64_concat(&page.to_le_bytes())
+        )
+    };
+
+    let mut chain = MockChain::with_block(0, &block_hash(0), staking.clone());
+    chain.chain_name = "Polkadot Asset Hub".to_string();
+    for number in 0..=59 {
+        let active_era = (number / BLOCKS_PER_ERA) as u32;
+        let until_next = BLOCKS_PER_ERA - number % BLOCKS_PER_ERA;
+        let current_era = active_era + u32::from(until_next <= 5);
+        let round = 1 + active_era;
+
+        let mut storage = staking.clone();
+        storage.insert(storage_prefix("Staking", "ActiveEra"), format!("{}00", encode_u32(active_era)));
+        storage.insert(storage_prefix("Staking", "CurrentEra"), encode_u32(current_era));
+        storage.insert(storage_prefix("MultiBlockElection", "Round"), encode_u32(round));
+        if until_next <= 3 {
+            storage.insert(page_key("PagedTargetSnapshot", round, 0), format!("0x{}", hex::encode(vec![aa].encode())));
+            let voters = vec![(account(0xb2), 700u64, vec![aa]), (account(0xb3), 300u64, vec![aa])];
+            storage.insert(page_key("PagedVoterSnapshot", round, 1), format!("0x{}", hex::encode(voters.encode())));
+        }
+        if until_next <= 2 {
+            let voters = vec![(aa, 500u64, vec![aa])];
+            storage.insert(page_key("PagedVoterSnapshot", round, 0), format!("0x{}", hex::encode(voters.encode())));
+        }
+
+        chain.block_hashes.insert(number, block_hash(number));
+        chain.storage.insert(block_hash(number), storage);
+    }
+    chain
+}
+
+#[tokio::test]
+async fn test_asset_hub_era_is_loaded_from_its_complete_snapshot() {
+    let node = MockNode::start(asset_hub_chain()).await;
+    let loader = RpcLoader::new(&node.url).unwrap();
+
+    let data = loader.load_for_era(2).await.unwrap();
+
+    // Era 2 is planned at block 35; its snapshot is complete from block 38
+    let metadata = data.metadata.unwrap();
+    assert_eq!(metadata.block_number, Some(38));
+    assert_eq!(metadata.era, Some(2));
+    assert_eq!(data.candidates.len(), 1);
+    let mut voters: Vec<_> = data.nominators.iter().map(|n| (n.account_id.clone(), n.stake)).collect();
+    voters.sort();
+    assert_eq!(
+        voters,
+        vec![
+            (account_hex(&account(0xaa)), 500),
+            (account_hex(&account(0xb2)), 700),
+            (account_hex(&account(0xb3)), 300),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_loads_state_the_election_snapshot_was_taken_from() {
+    let node = MockNode::start(chain_with_election_provider()).await;
+    let loader = RpcLoader::new(&node.url).unwrap();
+
+    let data = loader.load_for_era(3).await.unwrap();
+
+    // Era 3 is elected at block 50, from a snapshot taken when the signed phase starts at block 44
+    let metadata = data.metadata.unwrap();
+    assert_eq!(metadata.block_number, Some(43));
+    assert_eq!(metadata.era, Some(3));
+    assert_eq!(data.candidates.len(), 2);
+}
+
+#[tokio::test]
+async fn test_loads_block_before_era_election() {
+    let node = MockNode::start(chain()).await;
+    let loader = RpcLoader::new(&node.url).unwrap();
+
+    let data = loader.load_for_era(3).await.unwrap();
+
+    // Era 3 starts at session 6, so it is elected when session 5 starts at block 50;
+    // without an election provider pallet the election reads the state right before it
+    let metadata = data.metadata.unwrap();
+    assert_eq!(metadata.block_number, Some(49));
+    assert_eq!(metadata.era, Some(3));
+    assert_eq!(data.candidates.len(), 2);
+    assert_eq!(data.nominators.len(), 1);
+}
+
+#[tokio::test]
+async fn test_rejects_eras_without_an_election() {
+    let node = MockNode::start(chain()).await;
+    let loader = RpcLoader::new(&node.url).unwrap();
+
+    assert!(loader.load_for_era(4).await.is_err(), "era 4 has not started");
+    assert!(loader.load_for_era(0).await.is_err(), "era 0 starts at genesis");
+}
@@ -0,0 +1,79 @@
+//! Integration test: RPC endpoint pool failover and block hash agreement
+
+mod common;
+
+use common::mock_node::*;
+use offline_election::input::RpcLoader;
+
+const BLOCK_HASH: &str = "0x2222222222222222222222222222222222222222222222222222222222222222";
+const FORK_HASH: &str = "0x3333333333333333333333333333333333333333333333333333333333333333";
+
+fn snapshot(block_hash: &str) -> MockChain {
+    let validators = [account(0xaa), account(0xab)];
+    let nominators = vec![
+        (account(0xb1), 1_000, vec![account(0xaa)]),
+        (account(0xb2), 2_000, vec![account(0xab)]),
+    ];
+    MockChain::with_block(100, block_hash, staking_storage(&validators, &nominators))
+}
+
+/// URL of a local port nothing is listening on
+fn dead_url() -> String {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    format!("http://{}", listener.local_addr().unwrap())
+}
+
+#[tokio::test]
+async fn test_fails_over_when_primary_is_unreachable() {
+    let fallback = MockNode::start(snapshot(BLOCK_HASH)).await;
+    let loader = RpcLoader::with_endpoints([dead_url(), fallback.url.clone()]).unwrap();
+
+    let data = loader.load_at_block(100).await.unwrap();
+
+    assert_eq!(data.candidates.len(), 2);
+    assert_eq!(data.nominators.len(), 2);
+    assert!(fallback.chain.calls("state_queryStorageAt") > 0);
+}
+
+#[tokio::test]
+async fn test_failover_skips_endpoint_on_another_fork() {
+    let primary = MockNode::start(snapshot(BLOCK_HASH).failing("state_queryStorageAt")).await;
+    let forked = MockNode::start(snapshot(FORK_HASH)).await;
+    let backup = MockNode::start(snapshot(BLOCK_HASH)).await;
+    let loader =
+        RpcLoader::with_endpoints([primary.url.clone(), forked.url.clone(), backup.url.clone()]).unwrap();
+
+    let data = loader.load_at_block(100).await.unwrap();
+
+    assert_eq!(data.nominators.len(), 2);
+    // The forked endpoint was asked for the block hash, but never for storage
+    assert_eq!(forked.chain.calls("chain_getBlockHash"), 1);
+    assert_eq!(forked.chain.calls("state_getStorage"), 0);
+    assert_eq!(forked.chain.calls("state_queryStorageAt"), 0);
+    assert_eq!(backup.chain.calls("state_queryStorageAt"), 1);
+}
+
+#[tokio::test]
+async fn test_paged_key_listing_fails_over() {
+    let primary = MockNode::start(snapshot(BLOCK_HASH).unsupported("state_getKeys").failing("state_getKeysPaged")).await;
+    let backup = MockNode::start(snapshot(BLOCK_HASH).unsupported("state_getKeys")).await;
+    let loader = RpcLoader::with_endpoints([primary.url.clone(), backup.url.clone()]).unwrap();
+
+    let data = loader.load_at_block(100).await.unwrap();
+
+    assert_eq!(data.nominators.len(), 2);
+    assert!(backup.chain.calls("state_getKeysPaged") > 0);
+}
+
+#[tokio::test]
+async fn test_key_listing_and_chain_name_fail_over() {
+    let primary = MockNode::start(snapshot(BLOCK_HASH).failing("state_getKeys").failing("system_chain")).await;
+    let backup = MockNode::start(snapshot(BLOCK_HASH)).await;
+    let loader = RpcLoader::with_endpoints([primary.url.clone(), backup.url.clone()]).unwrap();
+
+    let data = loader.load_at_block(100).await.unwrap();
+
+    assert_eq!(data.nominators.len(), 2);
+    assert!(backup.chain.calls("state_getKeys") > 0);
+    assert_eq!(backup.chain.calls("system_chain"), 1);
+}
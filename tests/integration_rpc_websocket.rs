@@ -0,0 +1,65 @@
+//! Integration test: WebSocket transport and finalized head subscriptions
+
+mod common;
+
+use common::mock_node::*;
+use futures::StreamExt;
+use offline_election::input::{FinalizedHead, RpcLoader};
+
+const BLOCK_HASH: &str = "0x4444444444444444444444444444444444444444444444444444444444444444";
+
+fn snapshot(nominator_count: u32) -> MockChain {
+    let validators: Vec<[u8; 32]> = (0..3).map(|i| indexed_account(0xaa, i)).collect();
+    let nominators: Vec<_> = (0..nominator_count)
+        .map(|i| (indexed_account(0xbb, i), 1_000 + i as u128, vec![validators[0]]))
+        .collect();
+    MockChain::with_block(100, BLOCK_HASH, staking_storage(&validators, &nominators))
+}
+
+#[tokio::test]
+async fn test_loads_over_a_single_websocket_connection() {
+    // A second connection would be refused by the node
+    let node = MockNode::start_with_max_connections(snapshot(300), 1).await;
+    let loader = RpcLoader::new(&node.ws_url)
+        .unwrap()
+        .with_batch_size(50)
+        .with_concurrency(4);
+
+    let data = loader.load_at_block(100).await.unwrap();
+
+    assert_eq!(data.candidates.len(), 3);
+    assert_eq!(data.nominators.len(), 300);
+    assert_eq!(node.chain.calls("state_queryStorageAt"), 12);
+}
+
+#[tokio::test]
+async fn test_subscribes_to_finalized_heads() {
+    let mut chain = snapshot(0);
+    chain.block_hashes.insert(101, "0x0101".to_string());
+    chain.block_hashes.insert(102, "0x0102".to_string());
+    let node = MockNode::start(chain).await;
+    let loader = RpcLoader::new(&node.ws_url).unwrap();
+
+    let heads: Vec<FinalizedHead> = loader
+        .subscribe_finalized_heads()
+        .await
+        .unwrap()
+        .take(3)
+        .map(Result::unwrap)
+        .collect()
+        .await;
+
+    let numbers: Vec<u64> = heads.iter().map(|head| head.number).collect();
+    assert_eq!(numbers, vec![100, 101, 102]);
+    assert_eq!(heads[0].hash, BLOCK_HASH);
+    assert_eq!(heads[2].hash, "0x0102");
+    assert_eq!(heads[2].parent_hash, heads[1].hash);
+}
+
+#[tokio::test]
+async fn test_subscription_requires_websocket_endpoint() {
+    let node = MockNode::start(snapshot(0)).await;
+    let loader = RpcLoader::new(&node.url).unwrap();
+
+    assert!(loader.subscribe_finalized_heads().await.is_err());
+}
@@ -0,0 +1,125 @@
+//! Integration test: scenario files and comparison against the baseline
+
+mod common;
+
+use common::data_generator::{election_data, nominator};
+use offline_election::{ElectionData, ElectionEngine, ScenarioSet};
+use std::fs;
+use std::path::PathBuf;
+
+fn data() -> ElectionData {
+    let nominators = vec![
+        nominator("n1", 1_000, &["a"]),
+        nominator("n2", 600, &["b"]),
+        nominator("n3", 300, &["c"]),
+    ];
+    election_data(&["a", "b", "c"], 100, nominators)
+}
+
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("offline-election-scenarios-{}-{}", name, std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn run(set: &ScenarioSet) -> offline_election::ScenarioReport {
+    let data = set.load_base().unwrap();
+    let config = set.configuration().unwrap();
+    ElectionEngine::new().execute_scenarios(&config, &set.scenarios, &data).unwrap()
+}
+
+#[test]
+fn test_toml_scenarios_are_compared_against_the_baseline() {
+    let dir = scratch_dir("toml");
+    fs::write(dir.join("base.json"), serde_json::to_string(&data()).unwrap()).unwrap();
+    let path = dir.join("scenarios.toml");
+    fs::write(
+        &path,
+        r#"
+active_set_size = 2
+
+[base]
+input_file = "base.json"
+
+[[scenarios]]
+name = "unchanged"
+
+[[scenarios]]
+name = "b leaves"
+[scenarios.overrides]
+removed_candidates = ["b"]
+
+[[scenarios]]
+name = "n3 doubles down"
+algorithm = "parallel-phragmen"
+[scenarios.overrides.nominator_stakes]
+n3 = 2000
+"#,
+    )
+    .unwrap();
+
+    let set = ScenarioSet::from_file(&path).unwrap();
+    assert_eq!(set.base.input_file, Some(dir.join("base.json")));
+    let report = run(&set);
+
+    assert_eq!(report.scenarios.len(), 3);
+    assert!(report.scenario("unchanged").unwrap().comparison.is_unchanged());
+
+    let leaves = &report.scenario("b leaves").unwrap().comparison;
+    assert_eq!(leaves.entered, vec!["c".to_string()]);
+    assert_eq!(leaves.left, vec!["b".to_string()]);
+    assert_eq!(leaves.baseline_total_backing, 1_600);
+    assert_eq!(leaves.total_backing, 1_300);
+    assert_eq!(leaves.min_backing, 300);
+
+    let doubles = report.scenario("n3 doubles down").unwrap();
+    assert_eq!(doubles.comparison.left, vec!["b".to_string()]);
+    assert_eq!(doubles.result.selected_validators[0].account_id, "c");
+
+    fs::remove_dir_all(dir).ok();
+}
+
+#[test]
+fn test_json_scenarios_can_embed_the_base_data() {
+    let dir = scratch_dir("json");
+    let path = dir.join("scenarios.json");
+    let scenarios = serde_json::json!({
+        "base": { "data": data() },
+        "algorithm": "sequential-phragmen",
+        "active_set_size": 3,
+        "scenarios": [
+            { "name": "smaller set", "overrides": { "active_set_size": 1 } },
+            { "name": "n1 moves", "overrides": { "voting_edges": [
+                { "action": "remove", "nominator_id": "n1", "candidate_id": "a" },
+                { "action": "add", "nominator_id": "n1", "candidate_id": "b" }
+            ] } }
+        ]
+    });
+    fs::write(&path, scenarios.to_string()).unwrap();
+
+    let report = run(&ScenarioSet::from_file(&path).unwrap());
+
+    let smaller = &report.scenario("smaller set").unwrap().comparison;
+    assert!(smaller.entered.is_empty());
+    assert_eq!(smaller.left, vec!["b".to_string(), "c".to_string()]);
+
+    let moves = &report.scenario("n1 moves").unwrap().comparison;
+    let b = moves.backing_changes.iter().find(|c| c.validator_id == "b").unwrap();
+    assert_eq!((b.baseline, b.backing), (600, 1_600));
+
+    fs::remove_dir_all(dir).ok();
+}
+
+#[test]
+fn test_unreadable_scenario_file_is_a_file_error() {
+    let dir = scratch_dir("invalid");
+    let path = dir.join("scenarios.toml");
+    fs::write(&path, "active_set_size = \"two\"").unwrap();
+
+    assert!(matches!(
+        ScenarioSet::from_file(&path),
+        Err(offline_election::ElectionError::FileError { .. })
+    ));
+
+    fs::remove_dir_all(dir).ok();
+}
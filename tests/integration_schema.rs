@@ -0,0 +1,168 @@
+//! Integration test: JSON Schemas and validation reporting every problem
+
+mod common;
+
+use common::data_generator::generate_large_stake_election_data;
+use clap::Parser;
+use offline_election::api::models::{DataSource, ElectionRequest};
+use offline_election::cli::{SchemaCommand, ValidateCommand};
+use offline_election::input::JsonLoader;
+use offline_election::models::election_overrides::ElectionOverrides;
+use offline_election::schema::{validate_json, SchemaKind, ValidationIssue};
+use offline_election::{ElectionConfiguration, ElectionData, ElectionEngine, ElectionError};
+
+fn data() -> ElectionData {
+    generate_large_stake_election_data(4, 6, 2)
+}
+
+fn pointers(issues: &[ValidationIssue]) -> Vec<&str> {
+    issues.iter().map(|issue| issue.pointer.as_str()).collect()
+}
+
+#[test]
+fn test_generated_documents_match_their_schemas() {
+    let data = data();
+    let text = serde_json::to_string_pretty(&data).unwrap();
+    assert_eq!(validate_json(SchemaKind::ElectionData, &text), vec![]);
+
+    let result = ElectionEngine::new()
+        .execute_with_diagnostics(&ElectionConfiguration::new().active_set_size(2), &data, true)
+        .unwrap();
+    let text = serde_json::to_string(&result).unwrap();
+    assert_eq!(validate_json(SchemaKind::ElectionResult, &text), vec![]);
+
+    let text = serde_json::to_string(&ElectionOverrides::default()).unwrap();
+    assert_eq!(validate_json(SchemaKind::ElectionOverrides, &text), vec![]);
+
+    for kind in SchemaKind::ALL {
+        let schema = kind.schema();
+        assert_eq!(schema["$schema"], "http://json-schema.org/draft-07/schema#");
+        assert_eq!(kind.name().parse::<SchemaKind>().unwrap(), kind);
+    }
+    assert!("data".parse::<SchemaKind>().is_err());
+}
+
+#[test]
+fn test_reports_every_problem_with_pointer_and_position() {
+    let text = r#"{
+  "candidates": [
+    {"account_id": "v1", "stake": 100},
+    {"account_id": "v2", "stake": "oops"}
+  ],
+  "nominators": [
+    {"account_id": "n1", "targets": ["v1"]},
+    {"account_id": "n2", "stake": -1, "targets": ["v1"]}
+  ]
+}"#;
+    let issues = validate_json(SchemaKind::ElectionData, text);
+    assert_eq!(
+        pointers(&issues),
+        vec!["/candidates/1/stake", "/nominators/0", "/nominators/1/stake"]
+    );
+    assert_eq!((issues[0].line, issues[0].column), (Some(4), Some(35)));
+    assert_eq!((issues[1].line, issues[1].column), (Some(7), Some(5)));
+    assert!(issues[1].message.contains("\"stake\" is a required property"));
+    assert_eq!(
+        issues[0].to_string(),
+        format!("/candidates/1/stake (line 4, column 35): {}", issues[0].message)
+    );
+}
+
+#[test]
+fn test_reports_every_data_problem_once_the_schema_matches() {
+    let text = r#"{
+  "candidates": [
+    {"account_id": "v1", "stake": 100},
+    {"account_id": "v1", "stake": 36893488147419103230}
+  ],
+  "nominators": [
+    {"account_id": "n1", "stake": 5, "targets": ["v1", "v9"]},
+    {"account_id": "n1", "stake": 5, "targets": ["v8"]}
+  ]
+}"#;
+    let issues = validate_json(SchemaKind::ElectionData, text);
+    assert_eq!(
+        pointers(&issues),
+        vec!["/candidates/1", "/nominators/1", "/nominators/0/targets/1", "/nominators/1/targets/0"]
+    );
+    assert_eq!((issues[2].line, issues[2].column), (Some(7), Some(56)));
+    assert_eq!(issues[2].field(), "nominators.targets");
+
+    // Loading still stops at the first problem, with the same field as before
+    let error = JsonLoader::new().load_from_reader(text.as_bytes()).unwrap_err();
+    assert!(
+        matches!(error, ElectionError::ValidationError { ref message, ref field } if message == "Duplicate candidate account ID: v1" && field.as_deref() == Some("candidates"))
+    );
+}
+
+#[test]
+fn test_syntax_errors_and_overflow() {
+    let issues = validate_json(SchemaKind::ElectionData, "{\n  \"candidates\": [,]\n}");
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].pointer, "");
+    assert_eq!(issues[0].line, Some(2));
+    assert!(issues[0].message.starts_with("Invalid JSON"));
+
+    // Passes the schema but does not fit a u128
+    let text = r#"{"candidates": [{"account_id": "v1", "stake": 1000000000000000000000000000000000000000}], "nominators": []}"#;
+    let issues = validate_json(SchemaKind::ElectionData, text);
+    assert_eq!(pointers(&issues), vec!["/candidates/0/stake"]);
+}
+
+#[test]
+fn test_request_data_problems_are_nested() {
+    let request = serde_json::json!({
+        "algorithm": "random",
+        "active_set_size": 2,
+        "data_source": {
+            "type": "json",
+            "data": {"candidates": [{"account_id": "v1", "stake": 1}], "nominators": [
+                {"account_id": "n1", "stake": 1, "targets": ["v2"]}
+            ]}
+        }
+    });
+    let issues = validate_json(SchemaKind::ElectionRequest, &request.to_string());
+    assert_eq!(pointers(&issues), vec!["/algorithm", "/data_source/data/nominators/0/targets/0"]);
+    assert!(issues.iter().all(|issue| issue.line == Some(1)));
+
+    // Stakes of JSON data sources are read as u128
+    let request: ElectionRequest = serde_json::from_str(&request.to_string()).unwrap();
+    assert!(matches!(request.data_source, DataSource::Json { ref data } if data.nominators[0].stake == 1));
+}
+
+#[test]
+fn test_loader_parse_errors_name_the_pointer() {
+    let text = r#"{"candidates": [{"account_id": "v1", "stake": 1}], "nominators": [{"account_id": "n1", "stake": true, "targets": []}]}"#;
+    let error = JsonLoader::new().load_from_reader(text.as_bytes()).unwrap_err();
+    assert!(error.to_string().contains("Failed to parse JSON at /nominators/0/stake: "));
+}
+
+#[tokio::test]
+async fn test_schema_and_validate_commands() {
+    let dir = std::env::temp_dir().join(format!("schema-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let schema = dir.join("result.schema.json");
+    SchemaCommand::parse_from(["schema", "result", "--output-file", schema.to_str().unwrap()])
+        .execute()
+        .await
+        .unwrap();
+    let written: serde_json::Value = serde_json::from_slice(&std::fs::read(&schema).unwrap()).unwrap();
+    assert_eq!(written, SchemaKind::ElectionResult.schema());
+
+    let valid = dir.join("valid.json");
+    std::fs::write(&valid, serde_json::to_vec(&data()).unwrap()).unwrap();
+    ValidateCommand::parse_from(["validate", valid.to_str().unwrap()])
+        .execute()
+        .await
+        .unwrap();
+
+    let invalid = dir.join("invalid.json");
+    std::fs::write(&invalid, r#"{"candidates": [], "nominators": [{"account_id": "n1", "stake": 1, "targets": ["v1"]}]}"#)
+        .unwrap();
+    let error = ValidateCommand::parse_from(["validate", invalid.to_str().unwrap(), "--format", "json"])
+        .execute()
+        .await
+        .unwrap_err();
+    assert!(error.to_string().contains("2 problem(s) found"));
+}
@@ -0,0 +1,174 @@
+//! Integration test: prepared election sessions
+
+mod common;
+
+use common::data_generator::generate_overlapping_election_data;
+use offline_election::models::validator::CandidateMetadata;
+use offline_election::{
+    AlgorithmType, ChainProfile, ElectionConfiguration, ElectionData, ElectionEngine, ElectionError,
+    ElectionOverrides, ElectionResult, ElectionSession, Nominator, ValidatorCandidate,
+};
+
+/// Deterministic data with overlapping nominations of uneven stake and a blocked candidate
+fn data() -> ElectionData {
+    let blocked = CandidateMetadata { blocked: true, ..Default::default() };
+    let mut data = generate_overlapping_election_data();
+    data.candidates[3] = ValidatorCandidate::with_metadata("v3".to_string(), 300, blocked);
+    data
+}
+
+/// Everything but the execution timestamp
+fn assert_same(session: &ElectionResult, engine: &ElectionResult) {
+    assert_eq!(session.selected_validators, engine.selected_validators);
+    assert_eq!(session.stake_distribution, engine.stake_distribution);
+    assert_eq!(session.total_stake, engine.total_stake);
+    assert_eq!(session.accounting, engine.accounting);
+    assert_eq!(session.algorithm_used, engine.algorithm_used);
+    assert_eq!(session.warnings, engine.warnings);
+    assert_eq!(session.execution_metadata.block_number, engine.execution_metadata.block_number);
+}
+
+fn assert_matches_engine(session: &ElectionSession, config: &ElectionConfiguration) {
+    let engine = ElectionEngine::new().execute(config, &data());
+    match (session.run(config), engine) {
+        (Ok(session), Ok(engine)) => assert_same(&session, &engine),
+        (Err(session), Err(engine)) => assert_eq!(session.to_string(), engine.to_string()),
+        (session, engine) => panic!("session gave {:?}, engine gave {:?}", session, engine),
+    }
+}
+
+fn overrides() -> ElectionOverrides {
+    let mut overrides = ElectionOverrides::new();
+    overrides.set_nominator_stake("n4".to_string(), 50_000).unwrap();
+    overrides.set_candidate_stake("v2".to_string(), 0).unwrap();
+    overrides.remove_candidate("v7".to_string()).unwrap();
+    overrides.remove_nominator("n9".to_string()).unwrap();
+    overrides
+        .add_candidate(ValidatorCandidate::new("v12".to_string(), 250))
+        .unwrap();
+    let mut newcomer = Nominator::new("n40".to_string(), 8_000);
+    newcomer.targets = vec!["v12".to_string(), "v5".to_string()];
+    overrides.add_nominator(newcomer).unwrap();
+    overrides.add_voting_edge("n0".to_string(), "v11".to_string()).unwrap();
+    overrides.remove_voting_edge("n2".to_string(), "v6".to_string()).unwrap();
+    overrides
+        .add_weighted_voting_edge("n3".to_string(), "v1".to_string(), 700)
+        .unwrap();
+    // New nomination of the blocked candidate
+    overrides.add_voting_edge("n6".to_string(), "v3".to_string()).unwrap();
+    // Unmatched overrides
+    overrides.set_nominator_stake("nobody".to_string(), 1).unwrap();
+    overrides.remove_candidate("v7".to_string()).unwrap();
+    overrides.modify_voting_edge("n1".to_string(), "v0".to_string(), Some(5)).unwrap();
+    overrides
+}
+
+#[test]
+fn test_session_matches_engine_without_overrides() {
+    let session = ElectionSession::new(data()).unwrap();
+    for algorithm in [
+        AlgorithmType::SequentialPhragmen,
+        AlgorithmType::ParallelPhragmen,
+        AlgorithmType::MultiPhase,
+    ] {
+        for size in [1, 5, 12, 20] {
+            let config = ElectionConfiguration::new()
+                .algorithm(algorithm)
+                .active_set_size(size)
+                .block_number(42);
+            assert_matches_engine(&session, &config);
+        }
+    }
+}
+
+#[test]
+fn test_session_matches_engine_with_overrides() {
+    let session = ElectionSession::new(data()).unwrap();
+    for algorithm in [
+        AlgorithmType::SequentialPhragmen,
+        AlgorithmType::ParallelPhragmen,
+        AlgorithmType::MultiPhase,
+    ] {
+        let config = ElectionConfiguration::new()
+            .algorithm(algorithm)
+            .active_set_size(6)
+            .overrides(overrides());
+        let result = session.run(&config).unwrap();
+        assert_eq!(result.warnings.len(), 4, "{:?}", result.warnings);
+        assert_matches_engine(&session, &config);
+    }
+}
+
+#[test]
+fn test_session_matches_engine_with_bonds_and_chain_limits() {
+    let session = ElectionSession::new(data()).unwrap();
+    let mut profile = ChainProfile::new("test");
+    profile.max_nominations = 2;
+    profile.max_electing_voters = Some(25);
+    profile.max_backers_per_winner = Some(3);
+    profile.max_winners = Some(8);
+
+    let config = ElectionConfiguration::new()
+        .active_set_size(6)
+        .min_nominator_bond(1_500)
+        .min_validator_bond(100)
+        .chain_profile(profile)
+        .overrides(overrides());
+    assert!(session.run(&config).unwrap().warnings.len() > 4);
+    assert_matches_engine(&session, &config);
+    assert_matches_engine(&session, &config.clone().active_set_size(9));
+}
+
+#[test]
+fn test_session_runs_are_independent() {
+    let session = ElectionSession::new(data()).unwrap();
+    let baseline = ElectionConfiguration::new().active_set_size(5);
+    let first = session.run(&baseline).unwrap();
+
+    let modified = baseline.clone().overrides(overrides());
+    assert_matches_engine(&session, &modified);
+
+    // Overrides of one run don't leak into the next
+    assert_same(&session.run(&baseline).unwrap(), &first);
+    assert_eq!(session.data(), &data());
+}
+
+#[test]
+fn test_session_reports_override_errors_like_engine() {
+    let session = ElectionSession::new(data()).unwrap();
+
+    let mut duplicate = ElectionOverrides::new();
+    duplicate.add_candidate(ValidatorCandidate::new("v1".to_string(), 1)).unwrap();
+    let mut overweight = ElectionOverrides::new();
+    overweight
+        .add_weighted_voting_edge("n0".to_string(), "v8".to_string(), 1_000_000)
+        .unwrap();
+    let mut strict_unmatched = ElectionOverrides::new();
+    strict_unmatched.remove_nominator("nobody".to_string()).unwrap();
+
+    for (overrides, strict) in [(duplicate, false), (overweight, false), (strict_unmatched, true)] {
+        let config = ElectionConfiguration::new()
+            .active_set_size(3)
+            .strict(strict)
+            .overrides(overrides);
+        assert!(session.run(&config).is_err());
+        assert_matches_engine(&session, &config);
+    }
+}
+
+#[test]
+fn test_session_rejects_other_address_format() {
+    let alice = "0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d";
+    let mut data = ElectionData::new();
+    data.add_candidate(ValidatorCandidate::new(alice.to_string(), 100)).unwrap();
+    let session = ElectionSession::new(data).unwrap();
+    assert_eq!(session.address_format(), offline_election::models::AddressFormat::Hex);
+
+    let config = ElectionConfiguration::new()
+        .active_set_size(1)
+        .address_format(offline_election::models::AddressFormat::Ss58(0));
+    match session.run(&config) {
+        Err(ElectionError::ValidationError { field, .. }) => assert_eq!(field.as_deref(), Some("address_format")),
+        other => panic!("expected an address format error, got {:?}", other),
+    }
+}
@@ -0,0 +1,100 @@
+//! Integration test: RPC snapshot completeness against `CounterFor*` storage items
+
+mod common;
+
+use common::mock_node::*;
+use offline_election::input::RpcLoader;
+use offline_election::{AlgorithmType, ElectionConfiguration, ElectionEngine, ElectionError};
+
+const BLOCK_HASH: &str = "0x5555555555555555555555555555555555555555555555555555555555555555";
+
+/// Two decodable nominators plus one whose nominations are truncated
+fn snapshot() -> MockChain {
+    let validators = [account(0xaa), account(0xab)];
+    let nominators = vec![
+        (account(0xb1), 1_000, vec![account(0xaa)]),
+        (account(0xb2), 2_000, vec![account(0xab)]),
+    ];
+    let mut storage = staking_storage(&validators, &nominators);
+    storage.insert(map_key("Staking", "Nominators", 16, &account(0xb3)), "0x08aaaa".to_string());
+    storage.insert(map_key("Staking", "Ledger", 8, &account(0xb3)), encode_ledger(&account(0xb3), 3_000));
+    storage.insert(storage_prefix("Staking", "CounterForValidators"), encode_u32(2));
+    storage.insert(storage_prefix("Staking", "CounterForNominators"), encode_u32(3));
+    MockChain::with_block(100, BLOCK_HASH, storage)
+}
+
+#[tokio::test]
+async fn test_incomplete_snapshot_is_recorded_and_refused_in_strict_mode() {
+    let node = MockNode::start(snapshot()).await;
+    let data = RpcLoader::new(&node.url).unwrap().load_at_block(100).await.unwrap();
+
+    let completeness = data.metadata.as_ref().unwrap().completeness.as_ref().unwrap();
+    assert_eq!(completeness.expected_validators, Some(2));
+    assert_eq!(completeness.loaded_validators, 2);
+    assert_eq!(completeness.expected_nominators, Some(3));
+    assert_eq!(completeness.loaded_nominators, 2);
+    assert_eq!(
+        completeness.undecodable_keys,
+        vec![map_key("Staking", "Nominators", 16, &account(0xb3))]
+    );
+    assert!(!completeness.is_complete());
+
+    let config = ElectionConfiguration::new()
+        .algorithm(AlgorithmType::SequentialPhragmen)
+        .active_set_size(2);
+    let engine = ElectionEngine::new();
+
+    // Without strict mode the engine only warns
+    engine.execute(&config, &data).unwrap();
+
+    let strict = config.strict_snapshot(true);
+    match engine.execute(&strict, &data) {
+        Err(ElectionError::ValidationError { field, .. }) => {
+            assert_eq!(field.as_deref(), Some("metadata.completeness"));
+        }
+        other => panic!("Expected a validation error, got {:?}", other.map(|_| ())),
+    }
+}
+
+#[tokio::test]
+async fn test_counters_are_compared_with_decoded_entries() {
+    // Three validator intentions, two of them in the active set
+    let validators = [account(0xaa), account(0xab)];
+    let nominators = vec![(account(0xb1), 1_000, vec![account(0xaa)])];
+    let mut storage = staking_storage(&validators, &nominators);
+    storage.insert(map_key("Staking", "Validators", 8, &account(0xac)), encode_validator_prefs(0, false));
+    // A nominator whose targets were all chilled still counts towards the counter
+    storage.insert(map_key("Staking", "Nominators", 16, &account(0xb2)), encode_accounts(&[]));
+    storage.insert(map_key("Staking", "Ledger", 8, &account(0xb2)), encode_ledger(&account(0xb2), 2_000));
+    storage.insert(storage_prefix("Staking", "CounterForValidators"), encode_u32(3));
+    storage.insert(storage_prefix("Staking", "CounterForNominators"), encode_u32(2));
+    let node = MockNode::start(MockChain::with_block(100, BLOCK_HASH, storage)).await;
+
+    let data = RpcLoader::new(&node.url).unwrap().load_at_block(100).await.unwrap();
+    assert_eq!(data.candidates.len(), 2);
+    assert_eq!(data.nominators.len(), 1);
+    let completeness = data.metadata.unwrap().completeness.unwrap();
+    assert_eq!((completeness.expected_validators, completeness.loaded_validators), (Some(3), 3));
+    assert_eq!((completeness.expected_nominators, completeness.loaded_nominators), (Some(2), 2));
+    assert!(completeness.is_complete(), "{:?}", completeness.problems());
+}
+
+#[tokio::test]
+async fn test_concurrent_loads_keep_their_own_undecodable_keys() {
+    const CLEAN_HASH: &str = "0x6666666666666666666666666666666666666666666666666666666666666666";
+    let mut chain = snapshot();
+    let validators = [account(0xaa), account(0xab)];
+    let clean = staking_storage(&validators, &[(account(0xb1), 1_000, vec![account(0xaa)])]);
+    chain.block_hashes.insert(101, CLEAN_HASH.to_string());
+    chain.storage.insert(CLEAN_HASH.to_string(), clean);
+    let node = MockNode::start(chain).await;
+
+    let loader = RpcLoader::new(&node.url).unwrap();
+    let (incomplete, clean) = tokio::join!(loader.load_at_block(100), loader.load_at_block(101));
+    let undecodable = |data: offline_election::ElectionData| data.metadata.unwrap().completeness.unwrap().undecodable_keys;
+    assert_eq!(
+        undecodable(incomplete.unwrap()),
+        vec![map_key("Staking", "Nominators", 16, &account(0xb3))]
+    );
+    assert!(undecodable(clean.unwrap()).is_empty());
+}
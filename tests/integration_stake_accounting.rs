@@ -0,0 +1,143 @@
+//! Integration test: exact stake accounting
+
+mod common;
+
+use common::data_generator::nominator;
+use offline_election::models::election_result::{UnassignedReason, UnassignedStake};
+use offline_election::{
+    AlgorithmType, ElectionConfiguration, ElectionData, ElectionEngine, ElectionResult, ValidatorCandidate,
+};
+
+fn run(algorithm: AlgorithmType, active_set_size: u32, data: &ElectionData) -> ElectionResult {
+    let config = ElectionConfiguration::new()
+        .algorithm(algorithm)
+        .active_set_size(active_set_size)
+        .build()
+        .unwrap();
+    ElectionEngine::new().execute(&config, data).unwrap()
+}
+
+#[test]
+fn test_unassigned_stake_is_reported_per_nominator() {
+    let data = ElectionData {
+        candidates: vec![
+            ValidatorCandidate::new("a".to_string(), 100),
+            ValidatorCandidate::new("b".to_string(), 100),
+        ],
+        nominators: vec![
+            nominator("n1", 5_000, &["a"]),
+            nominator("n2", 100, &["b"]),
+            nominator("n3", 700, &[]),
+        ],
+        metadata: None,
+    };
+
+    // Only "a" is elected, so n2's stake backs nobody
+    let result = run(AlgorithmType::SequentialPhragmen, 1, &data);
+    let accounting = result.accounting();
+
+    assert_eq!(result.total_stake, 5_800);
+    assert_eq!(accounting.assigned_stake, 5_000);
+    assert_eq!(accounting.unassigned_stake, 800);
+    assert_eq!(accounting.rounding_residue, 0);
+    assert_eq!(
+        accounting.unassigned,
+        vec![
+            UnassignedStake {
+                nominator_id: "n2".to_string(),
+                amount: 100,
+                reason: UnassignedReason::NoElectedTargets,
+            },
+            UnassignedStake {
+                nominator_id: "n3".to_string(),
+                amount: 700,
+                reason: UnassignedReason::NoCandidateTargets,
+            },
+        ]
+    );
+    assert_eq!(result.selected_validators[0].total_backing_stake, 5_000);
+}
+
+#[test]
+fn test_split_stake_accounts_for_rounding_residue_exactly() {
+    let data = ElectionData {
+        candidates: vec![
+            ValidatorCandidate::new("a".to_string(), 0),
+            ValidatorCandidate::new("b".to_string(), 0),
+            ValidatorCandidate::new("c".to_string(), 0),
+        ],
+        nominators: vec![
+            nominator("n1", 1_000_000_007, &["a", "b", "c"]),
+            nominator("n2", 10, &["a", "b"]),
+            nominator("n3", 333_333_333_333, &["b", "c"]),
+        ],
+        metadata: None,
+    };
+
+    for algorithm in [AlgorithmType::SequentialPhragmen, AlgorithmType::ParallelPhragmen, AlgorithmType::MultiPhase] {
+        let result = run(algorithm, 3, &data);
+        let accounting = result.accounting();
+
+        assert_eq!(
+            accounting.assigned_stake + accounting.unassigned_stake + accounting.rounding_residue,
+            result.total_stake,
+            "{:?}",
+            algorithm
+        );
+        for nominator in &data.nominators {
+            let allocations = result.allocations_for_nominator(&nominator.account_id);
+            let allocated: u128 = allocations.iter().map(|a| a.amount).sum();
+            assert!(allocated <= nominator.stake);
+            let parts: u32 = allocations.iter().map(|a| a.perbill).sum();
+            assert!(parts <= 1_000_000_000);
+            for allocation in allocations {
+                assert!((allocation.proportion - allocation.perbill as f64 / 1e9).abs() < 1e-12);
+            }
+        }
+        for validator in &result.selected_validators {
+            let allocated: u128 =
+                result.allocations_for_validator(&validator.account_id).iter().map(|a| a.amount).sum();
+            assert_eq!(validator.total_backing_stake, allocated);
+        }
+    }
+}
+
+#[test]
+fn test_backer_counts_match_allocations() {
+    // "a" also nominates, under the same account ID it validates with
+    let data = ElectionData {
+        candidates: vec![
+            ValidatorCandidate::new("a".to_string(), 100),
+            ValidatorCandidate::new("b".to_string(), 100),
+            ValidatorCandidate::new("c".to_string(), 100),
+        ],
+        nominators: vec![
+            nominator("a", 1_000, &["a"]),
+            nominator("n1", 3_000, &["a", "b", "c"]),
+            nominator("n2", 2_000, &["b", "c"]),
+            nominator("n3", 500, &["c"]),
+        ],
+        metadata: None,
+    };
+
+    for algorithm in [
+        AlgorithmType::SequentialPhragmen,
+        AlgorithmType::ParallelPhragmen,
+        AlgorithmType::MultiPhase,
+    ] {
+        let result = run(algorithm, 3, &data);
+        assert_eq!(result.total_stake, 6_500);
+        for validator in &result.selected_validators {
+            let allocations = result.allocations_for_validator(&validator.account_id);
+            assert_eq!(validator.nominator_count as usize, allocations.len(), "{:?}", algorithm);
+            assert_eq!(
+                validator.total_backing_stake,
+                allocations.iter().map(|a| a.amount).sum::<u128>()
+            );
+        }
+        assert!(result
+            .stake_distribution
+            .iter()
+            .any(|a| a.nominator_id == "a" && a.validator_id == "a"));
+    }
+}
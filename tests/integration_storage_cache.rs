@@ -0,0 +1,104 @@
+//! Integration test: on-disk RPC storage cache
+
+use offline_election::input::cache::{KeyListing, StorageCache};
+use std::io::Write;
+use std::path::PathBuf;
+
+const BLOCK_HASH: &str = "0xAB12000000000000000000000000000000000000000000000000000000000000";
+const PREFIX: &str = "0x5f3e4907f716ac89b6347d15ececedca9c6a637f62ae2af1c7e31eed7e96be04";
+
+fn temp_cache_dir() -> PathBuf {
+    std::env::temp_dir().join(format!("offline-election-cache-{}", uuid::Uuid::new_v4()))
+}
+
+#[test]
+fn test_cache_serves_values_and_listings_across_instances() {
+    let dir = temp_cache_dir();
+
+    {
+        let cache = StorageCache::new(&dir).unwrap();
+        cache.store_value(BLOCK_HASH, "0x01", Some("0xdeadbeef")).unwrap();
+        cache.store_value(BLOCK_HASH, "0x02", None).unwrap();
+        cache
+            .store_key_listing(
+                BLOCK_HASH,
+                PREFIX,
+                &KeyListing {
+                    keys: vec!["0x01".to_string(), "0x02".to_string()],
+                    complete: false,
+                },
+            )
+            .unwrap();
+    }
+
+    // A fresh instance reads everything back from disk
+    let cache = StorageCache::new(&dir).unwrap();
+    assert_eq!(cache.value(BLOCK_HASH, "0x01").unwrap(), Some(Some("0xdeadbeef".to_string())));
+    assert_eq!(cache.value(BLOCK_HASH, "0x02").unwrap(), Some(None));
+    assert_eq!(cache.value(BLOCK_HASH, "0x03").unwrap(), None);
+
+    // Block hashes are matched case-insensitively
+    let lowercase_hash = BLOCK_HASH.to_lowercase();
+    let listing = cache.key_listing(&lowercase_hash, PREFIX).unwrap().unwrap();
+    assert!(!listing.complete, "Partial listing should be resumable");
+    assert_eq!(listing.last_key(), Some(&"0x02".to_string()));
+
+    let summary = cache.inspect().unwrap();
+    assert_eq!(summary.len(), 1);
+    assert_eq!(summary[0].value_count, 2);
+    assert_eq!(summary[0].key_listings, vec![(PREFIX.to_string(), 2, false)]);
+
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_cache_ignores_truncated_value_log_tail() {
+    let dir = temp_cache_dir();
+
+    {
+        let cache = StorageCache::new(&dir).unwrap();
+        cache.store_value(BLOCK_HASH, "0x01", Some("0x00")).unwrap();
+    }
+
+    // Simulate a run interrupted halfway through writing a record
+    let log_path = dir.join(BLOCK_HASH.to_lowercase()).join("values.jsonl");
+    let mut log = std::fs::OpenOptions::new().append(true).open(&log_path).unwrap();
+    log.write_all(b"{\"k\":\"0x02\",\"v\":\"0x").unwrap();
+    drop(log);
+
+    let cache = StorageCache::new(&dir).unwrap();
+    assert_eq!(cache.value(BLOCK_HASH, "0x01").unwrap(), Some(Some("0x00".to_string())));
+    assert_eq!(cache.value(BLOCK_HASH, "0x02").unwrap(), None);
+
+    // The record written after the interruption survives the next load
+    cache.store_value(BLOCK_HASH, "0x03", Some("0x33")).unwrap();
+    drop(cache);
+    let cache = StorageCache::new(&dir).unwrap();
+    assert_eq!(cache.value(BLOCK_HASH, "0x01").unwrap(), Some(Some("0x00".to_string())));
+    assert_eq!(cache.value(BLOCK_HASH, "0x03").unwrap(), Some(Some("0x33".to_string())));
+
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_cache_purge() {
+    let dir = temp_cache_dir();
+    let other_hash = "0xcd34000000000000000000000000000000000000000000000000000000000000";
+
+    let cache = StorageCache::new(&dir).unwrap();
+    cache.store_value(BLOCK_HASH, "0x01", Some("0x00")).unwrap();
+    cache.store_value(other_hash, "0x01", Some("0x00")).unwrap();
+
+    assert!(cache.purge_block(BLOCK_HASH).unwrap());
+    assert!(!cache.purge_block(BLOCK_HASH).unwrap());
+    assert_eq!(cache.value(BLOCK_HASH, "0x01").unwrap(), None);
+    assert_eq!(cache.inspect().unwrap().len(), 1);
+
+    assert_eq!(cache.purge_all().unwrap(), 1);
+    assert!(cache.inspect().unwrap().is_empty());
+
+    // Block hashes are used as directory names, so non-hex input is rejected
+    assert!(cache.purge_block("../etc").is_err());
+
+    std::fs::remove_dir_all(&dir).ok();
+}
@@ -0,0 +1,84 @@
+//! Integration test: active set size sweeps
+
+mod common;
+
+use common::data_generator::generate_overlapping_election_data;
+use offline_election::models::{ElectionOverrides, SweepPoint};
+use offline_election::{AlgorithmType, ElectionConfiguration, ElectionEngine};
+
+
+fn config(algorithm: AlgorithmType) -> ElectionConfiguration {
+    ElectionConfiguration::new().algorithm(algorithm).active_set_size(1)
+}
+
+#[test]
+fn test_sweep_matches_independent_runs() {
+    let data = generate_overlapping_election_data();
+    let engine = ElectionEngine::new();
+    let sizes: Vec<u32> = (1..=12).collect();
+
+    for algorithm in [AlgorithmType::SequentialPhragmen, AlgorithmType::ParallelPhragmen] {
+        let report = engine.execute_sweep(&config(algorithm), &sizes, &data).unwrap();
+
+        assert_eq!(report.algorithm, algorithm);
+        assert_eq!(report.reused_winner_order, algorithm == AlgorithmType::SequentialPhragmen);
+        assert_eq!(report.points.len(), sizes.len());
+        for &size in &sizes {
+            let direct = engine
+                .execute(&config(algorithm).active_set_size(size), &data)
+                .unwrap();
+            assert_eq!(report.point(size), Some(&SweepPoint::new(size, &direct)), "{:?} at {}", algorithm, size);
+        }
+    }
+}
+
+#[test]
+fn test_sweep_points_are_sorted_and_deduplicated() {
+    let report = ElectionEngine::new()
+        .execute_sweep(&config(AlgorithmType::SequentialPhragmen), &[8, 2, 8, 5], &generate_overlapping_election_data())
+        .unwrap();
+
+    let sizes: Vec<u32> = report.points.iter().map(|p| p.active_set_size).collect();
+    assert_eq!(sizes, vec![2, 5, 8]);
+    for point in &report.points {
+        assert_eq!(point.selected, point.active_set_size);
+        assert_eq!(point.min_stake, point.score.minimal_stake);
+        assert_eq!(point.average_stake, point.score.sum_stake / point.selected as u128);
+    }
+    // A larger set can only lower the weakest validator's backing
+    assert!(report.points.windows(2).all(|w| w[0].min_stake >= w[1].min_stake));
+}
+
+#[test]
+fn test_sweep_ignores_active_set_size_override_and_rejects_zero() {
+    let mut overrides = ElectionOverrides::new();
+    overrides.set_active_set_size(1).unwrap();
+    let engine = ElectionEngine::new();
+
+    let report = engine
+        .execute_sweep(&config(AlgorithmType::SequentialPhragmen).overrides(overrides), &[3, 4], &generate_overlapping_election_data())
+        .unwrap();
+    assert_eq!(report.points[1].selected, 4);
+
+    assert!(engine
+        .execute_sweep(&config(AlgorithmType::SequentialPhragmen), &[0, 3], &generate_overlapping_election_data())
+        .is_err());
+    assert!(engine.execute_sweep(&config(AlgorithmType::SequentialPhragmen), &[], &generate_overlapping_election_data()).is_err());
+}
+
+#[test]
+fn test_sweep_csv_has_one_row_per_size() {
+    let report = ElectionEngine::new()
+        .execute_sweep(&config(AlgorithmType::SequentialPhragmen), &[1, 2, 3], &generate_overlapping_election_data())
+        .unwrap();
+
+    let csv = offline_election::cli::format_sweep_csv(&report);
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines[0], "active_set_size,selected,min_stake,average_stake,sum_stake,sum_stake_squared");
+    assert_eq!(lines.len(), 4);
+    let first = &report.points[0];
+    assert_eq!(
+        lines[1],
+        format!("1,1,{},{},{},{}", first.min_stake, first.average_stake, first.score.sum_stake, first.score.sum_stake_squared)
+    );
+}
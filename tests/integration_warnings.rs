@@ -0,0 +1,87 @@
+//! Integration test: structured warnings and strict mode
+
+mod common;
+
+use common::data_generator::{election_data, nominator};
+use offline_election::models::Warning;
+use offline_election::{AlgorithmType, ChainProfile, ElectionConfiguration, ElectionData, ElectionEngine, ElectionError};
+
+fn data() -> ElectionData {
+    election_data(&["a", "b"], 100, vec![nominator("n1", 500, &["a", "b"]), nominator("n2", 300, &["b"])])
+}
+
+fn config(active_set_size: u32) -> ElectionConfiguration {
+    ElectionConfiguration::new()
+        .algorithm(AlgorithmType::SequentialPhragmen)
+        .active_set_size(active_set_size)
+}
+
+#[test]
+fn test_reduced_active_set_is_reported_in_result() {
+    let result = ElectionEngine::new().execute(&config(5).build().unwrap(), &data()).unwrap();
+
+    assert_eq!(result.selected_validators.len(), 2);
+    assert_eq!(result.warnings(), &[Warning::ActiveSetReduced { requested: 5, available: 2 }]);
+
+    let json = serde_json::to_value(&result).unwrap();
+    assert_eq!(json["warnings"][0]["kind"], "active-set-reduced");
+    assert_eq!(json["warnings"][0]["available"], 2);
+}
+
+#[test]
+fn test_strict_mode_turns_reduced_active_set_into_error() {
+    let config = config(5).strict(true).build().unwrap();
+    let err = ElectionEngine::new().execute(&config, &data()).unwrap_err();
+
+    assert!(matches!(err, ElectionError::InsufficientCandidates { requested: 5, available: 2 }));
+}
+
+#[test]
+fn test_chain_limit_adjustments_are_warnings() {
+    let mut profile = ChainProfile::new("Test");
+    profile.max_nominations = 1;
+    let config = config(2).chain_profile(profile.clone());
+
+    let result = ElectionEngine::new().execute(&config.clone().build().unwrap(), &data()).unwrap();
+    assert!(result
+        .warnings()
+        .contains(&Warning::TargetsDropped { nominators: 1, max_nominations: 1 }));
+
+    let err = ElectionEngine::new()
+        .execute(&config.strict(true).build().unwrap(), &data())
+        .unwrap_err();
+    match err {
+        ElectionError::ValidationError { field, .. } => {
+            assert_eq!(field.as_deref(), Some("chain_profile.max_nominations"))
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+}
+
+#[test]
+fn test_clean_election_has_no_warnings() {
+    let config = config(2).strict(true).build().unwrap();
+    let result = ElectionEngine::new().execute(&config, &data()).unwrap();
+
+    assert!(result.warnings().is_empty());
+    assert!(serde_json::to_value(&result).unwrap().get("warnings").is_none());
+}
+
+#[test]
+fn test_stakes_capped_for_the_solver_are_warnings() {
+    let mut data = data();
+    data.nominators[0].stake = u64::MAX as u128 + 7;
+    data.nominators[1].stake = u64::MAX as u128 * 2;
+
+    let result = ElectionEngine::new().execute(&config(2).build().unwrap(), &data).unwrap();
+    assert_eq!(result.warnings(), &[Warning::StakeTruncated { voters: 2 }]);
+    assert_eq!(serde_json::to_value(&result).unwrap()["warnings"][0]["kind"], "stake-truncated");
+
+    let err = ElectionEngine::new()
+        .execute(&config(2).strict(true).build().unwrap(), &data)
+        .unwrap_err();
+    match err {
+        ElectionError::ValidationError { field, .. } => assert_eq!(field.as_deref(), Some("nominators.stake")),
+        other => panic!("unexpected error: {:?}", other),
+    }
+}